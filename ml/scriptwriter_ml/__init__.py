@@ -2,4 +2,6 @@
 
 from .transliteration import transliterate_tamil
 
-__all__ = ["transliterate_tamil"]
+__version__ = "0.1.0"
+
+__all__ = ["__version__", "transliterate_tamil"]
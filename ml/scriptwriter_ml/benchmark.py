@@ -0,0 +1,137 @@
+"""Latency benchmarks so writers can pick models that suit their hardware."""
+
+from __future__ import annotations
+
+import re
+import tempfile
+import time
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Callable, Optional
+
+from .llm import _draft_scene_local
+from .models import MODEL_REGISTRY, locate_model
+from .stt import transcribe_audio_file
+from .tts import synthesize_to_file
+
+DEFAULT_RUNS = 3
+MAX_RUNS = 10
+BENCHMARK_MAX_TOKENS = 64
+
+# Every run uses the same inputs so results compare across machines.
+SAMPLE_TEXT = "The rain had not stopped for three days and the village road had become a river."
+SAMPLE_PROMPT = (
+    "Write two lines of dialogue between a tea seller and a late customer "
+    "at a bus stop in the rain."
+)
+
+
+@dataclass(slots=True)
+class BenchmarkResult:
+    model: str
+    model_type: str
+    runs: int
+    latencies_ms: list[float] = field(default_factory=list)
+    average_ms: Optional[float] = None
+    engine: Optional[str] = None
+    # Speech-to-text only: word accuracy of the last run against SAMPLE_TEXT.
+    accuracy: Optional[float] = None
+    transcript: Optional[str] = None
+    error: Optional[str] = None
+
+
+def _words(text: str) -> list[str]:
+    return re.findall(r"\w+", text.lower())
+
+
+def word_accuracy(reference: str, hypothesis: str) -> float:
+    """1 - word error rate, floored at 0, ignoring case and punctuation."""
+    expected, heard = _words(reference), _words(hypothesis)
+    if not expected:
+        return 1.0 if not heard else 0.0
+    previous = list(range(len(heard) + 1))
+    for row, word in enumerate(expected, start=1):
+        current = [row]
+        for column, candidate in enumerate(heard, start=1):
+            current.append(
+                min(
+                    previous[column] + 1,
+                    current[column - 1] + 1,
+                    previous[column - 1] + (word != candidate),
+                )
+            )
+        previous = current
+    return round(max(0.0, 1.0 - previous[-1] / len(expected)), 3)
+
+
+def benchmark_model(
+    identifier: str,
+    runs: int = DEFAULT_RUNS,
+    root: str | Path | None = None,
+    on_run: Optional[Callable[[int, int, float], None]] = None,
+) -> BenchmarkResult:
+    """Time ``runs`` passes of a fixed sample through a registry model.
+
+    Speech models run through the engine behind ``identifier``, never the
+    default one, so each id reports its own timings. Speech-to-text is fed
+    audio synthesized from SAMPLE_TEXT, which doubles as the known
+    transcript for the accuracy estimate. Language models must
+    be downloaded, since timing the cloud API says nothing about the machine.
+    """
+    spec = next((spec for spec in MODEL_REGISTRY if spec.identifier == identifier), None)
+    if spec is None:
+        return BenchmarkResult(identifier, "unknown", 0, error=f"Unknown model id: {identifier}")
+    runs = max(1, min(runs, MAX_RUNS))
+    result = BenchmarkResult(identifier, spec.model_type, runs)
+
+    with tempfile.TemporaryDirectory(prefix="scriptwriter-benchmark-") as workdir:
+        sample_audio = str(Path(workdir) / "sample.wav")
+
+        if spec.model_type == "language_model":
+            model_path = locate_model(identifier, root=root)
+            if not model_path.exists():
+                result.error = f"{spec.title} is not downloaded"
+                return result
+
+            def task() -> dict:
+                draft = _draft_scene_local(SAMPLE_PROMPT, "", model_path, BENCHMARK_MAX_TOKENS)
+                return {"error": draft.error, "engine": "llama.cpp"}
+
+        elif spec.model_type == "text_to_speech":
+
+            def task() -> dict:
+                return synthesize_to_file(SAMPLE_TEXT, sample_audio, model_id=identifier)
+
+        elif spec.model_type == "speech_to_text":
+            prepared = synthesize_to_file(SAMPLE_TEXT, sample_audio)
+            if not prepared.get("success"):
+                result.error = f"Could not prepare sample audio: {prepared.get('error')}"
+                return result
+
+            def task() -> dict:
+                return transcribe_audio_file(
+                    sample_audio, language="en-IN", model_id=identifier, root=root
+                )
+
+        else:
+            result.error = f"Cannot benchmark {spec.model_type} models"
+            return result
+
+        for index in range(1, runs + 1):
+            started = time.perf_counter()
+            outcome = task()
+            elapsed_ms = round((time.perf_counter() - started) * 1000, 1)
+            if outcome.get("error"):
+                result.error = str(outcome["error"])
+                break
+            result.latencies_ms.append(elapsed_ms)
+            result.engine = outcome.get("engine") or result.engine
+            if spec.model_type == "speech_to_text":
+                result.transcript = outcome.get("text", "")
+                result.accuracy = word_accuracy(SAMPLE_TEXT, result.transcript)
+            if on_run is not None:
+                on_run(index, runs, elapsed_ms)
+
+    if result.latencies_ms:
+        result.average_ms = round(sum(result.latencies_ms) / len(result.latencies_ms), 1)
+    return result
@@ -4,21 +4,42 @@ from __future__ import annotations
 
 import argparse
 import json
+import platform
 import sys
+from dataclasses import asdict
 from typing import Any
 
-from .models import describe_models
-from .transliteration import transliterate_tamil
-from .stt import transcribe_audio_file, transcribe_from_microphone
-from .tts import synthesize_to_file, speak_text
-from .llm import draft_scene, download_model, DEFAULT_LOCAL_MODELS
+from . import __version__
+from .benchmark import DEFAULT_RUNS, benchmark_model
+from .models import describe_models, download_registry_model
+from .phonetics import phonetic_hint
+from .suggestions import DEFAULT_SUGGESTIONS, suggest
+from .transliteration import TRANSLITERATION_MODES, transliterate_tamil
+from .stt import list_input_devices, supported_languages, transcribe_audio_file, transcribe_from_microphone
+from .tts import list_voices, preview_speech, synthesize_to_file, speak_text
+from .llm import (
+    draft_inline,
+    draft_scene,
+    download_model,
+    estimate_request,
+    extract_outline,
+    summarize_scene,
+    test_credentials,
+    DEFAULT_API_MODEL,
+    DEFAULT_LOCAL_MODELS,
+    DEFAULT_MAX_TOKENS,
+    INLINE_MAX_TOKENS,
+    OUTLINE_MAX_TOKENS,
+    SUMMARY_MAX_TOKENS,
+    SUPPORTED_PROVIDERS,
+)
 
 
 def _transliterate_command(args: argparse.Namespace) -> dict[str, Any]:
     text = args.text
     if args.stdin or text is None:
         text = sys.stdin.read()
-    result = transliterate_tamil(text=text, scheme=args.scheme)
+    result = transliterate_tamil(text=text, scheme=args.scheme, mode=args.mode)
     return {
         "candidates": result.candidates,
         "engine": result.engine,
@@ -26,15 +47,40 @@ def _transliterate_command(args: argparse.Namespace) -> dict[str, Any]:
     }
 
 
+def _phonetics_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Romanize a Tamil word and break it into syllables."""
+    text = args.text
+    if args.stdin or text is None:
+        text = sys.stdin.read()
+    return asdict(phonetic_hint(text))
+
+
+def _suggest_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Suggest Tamil words for a partially typed Latin prefix."""
+    return {"suggestions": [asdict(item) for item in suggest(args.prefix, args.limit)]}
+
+
 def _transcribe_audio_command(args: argparse.Namespace) -> dict[str, Any]:
     """Transcribe an audio file to text."""
-    result = transcribe_audio_file(args.audio_file, language=args.language)
+    result = transcribe_audio_file(
+        args.audio_file,
+        language=args.language,
+        start=args.start,
+        end=args.end,
+        diarize=args.diarize,
+    )
     return result
 
 
 def _transcribe_mic_command(args: argparse.Namespace) -> dict[str, Any]:
     """Record from microphone and transcribe."""
-    result = transcribe_from_microphone(duration=args.duration, language=args.language)
+    result = transcribe_from_microphone(
+        duration=args.duration,
+        language=args.language,
+        device_id=args.device,
+        sample_rate=args.sample_rate,
+        output=args.output,
+    )
     return result
 
 
@@ -52,6 +98,14 @@ def _tts_command(args: argparse.Namespace) -> dict[str, Any]:
     return result
 
 
+def _tts_preview_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Synthesize a short sample and return the audio inline."""
+    text = args.text
+    if args.stdin or text is None:
+        text = sys.stdin.read()
+    return preview_speech(text, language=args.language, voice=args.voice)
+
+
 def _llm_generate_command(args: argparse.Namespace) -> dict[str, Any]:
     """Generate scene using LLM."""
     prompt = args.prompt
@@ -74,6 +128,54 @@ def _llm_generate_command(args: argparse.Namespace) -> dict[str, Any]:
     }
 
 
+def _llm_inline_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Fill in text at the cursor from JSON {before, after, instruction, api_key} on STDIN."""
+    payload = json.loads(sys.stdin.read() or "{}")
+    result = draft_inline(
+        before=payload.get("before", ""),
+        after=payload.get("after", ""),
+        instruction=payload.get("instruction", ""),
+        api_key=payload.get("api_key") or None,
+        max_tokens=args.max_tokens,
+        use_local=not args.no_local,
+    )
+    return {
+        "text": result.response,
+        "model_id": result.model_id,
+        "error": result.error,
+    }
+
+
+def _summarize_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Summarize a scene from JSON {scene, api_key, local_model_path} on STDIN."""
+    payload = json.loads(sys.stdin.read() or "{}")
+    return asdict(
+        summarize_scene(
+            payload.get("scene", ""),
+            api_key=payload.get("api_key") or None,
+            max_tokens=args.max_tokens,
+            use_local=not args.no_local,
+            local_model_path=payload.get("local_model_path") or None,
+            use_api=not args.offline,
+        )
+    )
+
+
+def _outline_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Outline a draft from JSON {draft, structure, api_key} on STDIN."""
+    payload = json.loads(sys.stdin.read() or "{}")
+    return asdict(
+        extract_outline(
+            payload.get("draft", ""),
+            payload.get("structure") or [],
+            api_key=payload.get("api_key") or None,
+            max_tokens=args.max_tokens,
+            use_local=not args.no_local,
+            use_api=not args.offline,
+        )
+    )
+
+
 def _llm_download_command(args: argparse.Namespace) -> dict[str, Any]:
     """Download a local LLM model."""
     try:
@@ -90,6 +192,51 @@ def _llm_download_command(args: argparse.Namespace) -> dict[str, Any]:
         }
 
 
+def _download_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Download a registry model, streaming progress as JSON lines."""
+
+    def report(downloaded: int, total: int) -> None:
+        progress = {"event": "progress", "downloaded": downloaded, "total": total}
+        sys.stdout.write(json.dumps(progress) + "\n")
+        sys.stdout.flush()
+
+    try:
+        path = download_registry_model(args.model, root=args.root, on_progress=report)
+        return {"event": "complete", "success": True, "model": args.model, "path": str(path)}
+    except Exception as e:
+        return {"event": "complete", "success": False, "model": args.model, "error": str(e)}
+
+
+def _benchmark_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Time a registry model on a fixed sample, streaming each run as JSON lines."""
+
+    def report(run: int, total: int, latency_ms: float) -> None:
+        progress = {"event": "progress", "run": run, "total": total, "latency_ms": latency_ms}
+        sys.stdout.write(json.dumps(progress) + "\n")
+        sys.stdout.flush()
+
+    result = benchmark_model(args.model, runs=args.runs, root=args.root, on_run=report)
+    return {"event": "complete", **asdict(result)}
+
+
+def _test_llm_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Check an LLM API key read from STDIN without persisting it."""
+    api_key = sys.stdin.read().strip()
+    return asdict(test_credentials(args.provider, api_key))
+
+
+def _count_tokens_command(args: argparse.Namespace) -> dict[str, Any]:
+    """Estimate tokens and cost for a generation request read as JSON from STDIN."""
+    payload = json.loads(sys.stdin.read() or "{}")
+    return estimate_request(
+        payload.get("prompt", ""),
+        payload.get("context", ""),
+        model_id=args.model,
+        max_tokens=args.max_tokens,
+        variations=args.variations,
+    )
+
+
 def _llm_list_command(args: argparse.Namespace) -> dict[str, Any]:
     """List available local models."""
     return {
@@ -105,6 +252,12 @@ def build_parser() -> argparse.ArgumentParser:
     translit = subparsers.add_parser("transliterate", help="Transliterate to Tamil")
     translit.add_argument("--text", help="Text to transliterate")
     translit.add_argument("--scheme", default="itrans", help="Input phonetic scheme")
+    translit.add_argument(
+        "--mode",
+        default="phonetic",
+        choices=list(TRANSLITERATION_MODES),
+        help="Which engine's suggestions to rank first",
+    )
     translit.add_argument(
         "--stdin",
         action="store_true",
@@ -112,18 +265,45 @@ def build_parser() -> argparse.ArgumentParser:
     )
     translit.set_defaults(func=_transliterate_command)
 
+    # Phonetic hints
+    phonetics = subparsers.add_parser("phonetics", help="Romanize Tamil text with syllables")
+    phonetics.add_argument("--text", help="Tamil text to romanize")
+    phonetics.add_argument("--stdin", action="store_true", help="Read text from STDIN")
+    phonetics.set_defaults(func=_phonetics_command)
+
+    # Typing suggestions
+    suggest_parser = subparsers.add_parser("suggest", help="Suggest Tamil words for a Latin prefix")
+    suggest_parser.add_argument("--prefix", required=True, help="Latin letters typed so far")
+    suggest_parser.add_argument("--limit", type=int, default=DEFAULT_SUGGESTIONS, help="Maximum suggestions")
+    suggest_parser.set_defaults(func=_suggest_command)
+
     # Speech-to-Text from file
     stt_file = subparsers.add_parser("transcribe-file", help="Transcribe audio file to text")
     stt_file.add_argument("audio_file", help="Path to audio file (WAV, FLAC, etc.)")
     stt_file.add_argument("--language", default="en-IN", help="Language code (e.g., en-IN, ta-IN)")
+    stt_file.add_argument("--start", type=float, help="Clip start in seconds")
+    stt_file.add_argument("--end", type=float, help="Clip end in seconds")
+    stt_file.add_argument("--diarize", action="store_true", help="Label speaker turns (requires pyannote.audio)")
     stt_file.set_defaults(func=_transcribe_audio_command)
 
     # Speech-to-Text from microphone
     stt_mic = subparsers.add_parser("transcribe-mic", help="Record from microphone and transcribe")
     stt_mic.add_argument("--duration", type=int, default=5, help="Recording duration in seconds")
     stt_mic.add_argument("--language", default="en-IN", help="Language code (e.g., en-IN, ta-IN)")
+    stt_mic.add_argument("--device", type=int, help="Input device id (see audio-devices)")
+    stt_mic.add_argument("--sample-rate", type=int, help="Recording sample rate in Hz")
+    stt_mic.add_argument("--output", help="Save the captured audio to this WAV path")
     stt_mic.set_defaults(func=_transcribe_mic_command)
 
+    # STT: Supported languages
+    stt_languages = subparsers.add_parser("stt-languages", help="List languages a speech-to-text model supports")
+    stt_languages.add_argument("--model", required=True, help="Speech-to-text model id")
+    stt_languages.set_defaults(func=lambda args: supported_languages(args.model))
+
+    # Audio input devices
+    audio_devices = subparsers.add_parser("audio-devices", help="List microphone input devices")
+    audio_devices.set_defaults(func=lambda args: list_input_devices())
+
     # Text-to-Speech
     tts = subparsers.add_parser("tts", help="Convert text to speech")
     tts.add_argument("--text", help="Text to synthesize")
@@ -132,11 +312,38 @@ def build_parser() -> argparse.ArgumentParser:
     tts.add_argument("--language", default="en", help="Language code")
     tts.set_defaults(func=_tts_command)
 
+    # TTS preview
+    tts_preview = subparsers.add_parser("tts-preview", help="Synthesize a sample and return base64 audio")
+    tts_preview.add_argument("--text", help="Text to synthesize")
+    tts_preview.add_argument("--stdin", action="store_true", help="Read text from STDIN")
+    tts_preview.add_argument("--language", default="en", help="Language code")
+    tts_preview.add_argument("--voice", help="Voice id (see tts-voices)")
+    tts_preview.set_defaults(func=_tts_preview_command)
+
+    # TTS voices
+    tts_voices = subparsers.add_parser("tts-voices", help="List voices for a TTS model")
+    tts_voices.add_argument("--model", required=True, help="TTS model id")
+    tts_voices.add_argument("--root", help="Override model storage root")
+    tts_voices.set_defaults(func=lambda args: list_voices(args.model, root=args.root))
+
     # Model inventory
     models = subparsers.add_parser("models", help="Inspect local model assets")
     models.add_argument("--root", help="Override model storage root")
     models.set_defaults(func=lambda args: {"models": describe_models(root=args.root)})
 
+    # Registry model download
+    download = subparsers.add_parser("download", help="Download a registry model")
+    download.add_argument("--model", required=True, help="Registry model id")
+    download.add_argument("--root", help="Override model storage root")
+    download.set_defaults(func=_download_command)
+
+    # Model benchmark
+    benchmark = subparsers.add_parser("benchmark", help="Time a registry model on a fixed sample")
+    benchmark.add_argument("--model", required=True, help="Registry model id")
+    benchmark.add_argument("--runs", type=int, default=DEFAULT_RUNS, help="Number of timed runs")
+    benchmark.add_argument("--root", help="Override model storage root")
+    benchmark.set_defaults(func=_benchmark_command)
+
     # LLM: Generate scene
     llm_gen = subparsers.add_parser("llm-generate", help="Generate scene using LLM")
     llm_gen.add_argument("--prompt", help="Scene generation prompt")
@@ -146,16 +353,54 @@ def build_parser() -> argparse.ArgumentParser:
     llm_gen.add_argument("--no-local", action="store_true", help="Disable local model fallback")
     llm_gen.set_defaults(func=_llm_generate_command)
 
+    # LLM: Fill in at the cursor
+    llm_inline = subparsers.add_parser("llm-inline", help="Generate text to insert at a cursor position")
+    llm_inline.add_argument("--max-tokens", type=int, default=INLINE_MAX_TOKENS, help="Maximum tokens to generate")
+    llm_inline.add_argument("--no-local", action="store_true", help="Disable local model fallback")
+    llm_inline.set_defaults(func=_llm_inline_command)
+
+    # LLM: Summarize a scene
+    summarize = subparsers.add_parser("summarize", help="Summarize a scene read from STDIN")
+    summarize.add_argument("--max-tokens", type=int, default=SUMMARY_MAX_TOKENS, help="Maximum tokens to generate")
+    summarize.add_argument("--no-local", action="store_true", help="Disable local model fallback")
+    summarize.add_argument("--offline", action="store_true", help="Use only the local model, never the API")
+    summarize.set_defaults(func=_summarize_command)
+
+    # LLM: Outline a draft
+    outline = subparsers.add_parser("outline", help="Outline a draft read from STDIN")
+    outline.add_argument("--max-tokens", type=int, default=OUTLINE_MAX_TOKENS, help="Maximum tokens to generate")
+    outline.add_argument("--no-local", action="store_true", help="Disable local model fallback")
+    outline.add_argument("--offline", action="store_true", help="Use only the local model, never the API")
+    outline.set_defaults(func=_outline_command)
+
     # LLM: Download model
     llm_dl = subparsers.add_parser("llm-download", help="Download a local LLM model")
     llm_dl.add_argument("model", choices=list(DEFAULT_LOCAL_MODELS.keys()), help="Model to download")
     llm_dl.add_argument("--root", help="Override model storage root")
     llm_dl.set_defaults(func=_llm_download_command)
 
+    # LLM: Test credentials
+    test_llm = subparsers.add_parser("test-llm", help="Verify an LLM API key read from STDIN")
+    test_llm.add_argument("--provider", default="openrouter", choices=SUPPORTED_PROVIDERS, help="LLM provider")
+    test_llm.set_defaults(func=_test_llm_command)
+
+    # LLM: Estimate request size
+    count_tokens = subparsers.add_parser("count-tokens", help="Estimate tokens/cost for a prompt read from STDIN")
+    count_tokens.add_argument("--model", default=DEFAULT_API_MODEL, help="Model id to price against")
+    count_tokens.add_argument("--max-tokens", type=int, default=DEFAULT_MAX_TOKENS, help="Completion token budget")
+    count_tokens.add_argument("--variations", type=int, default=1, help="Number of drafts requested")
+    count_tokens.set_defaults(func=_count_tokens_command)
+
     # LLM: List models
     llm_list = subparsers.add_parser("llm-list", help="List available local models")
     llm_list.set_defaults(func=_llm_list_command)
 
+    # Version details for bug reports
+    version = subparsers.add_parser("version", help="Report toolkit and Python versions")
+    version.set_defaults(
+        func=lambda args: {"toolkit": __version__, "python": platform.python_version()}
+    )
+
     return parser
 
 
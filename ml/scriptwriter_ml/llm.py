@@ -2,11 +2,12 @@
 
 from __future__ import annotations
 
+import json
 import logging
 import os
 from dataclasses import dataclass
 from pathlib import Path
-from typing import Optional
+from typing import Any, Callable, Optional
 
 from .models import ensure_directories, locate_model
 
@@ -45,6 +46,43 @@ class PromptResult:
     error: Optional[str] = None
 
 
+DEFAULT_API_MODEL = "meta-llama/llama-3.1-8b-instruct:free"
+MAX_VARIATIONS = 5
+
+# Substrings llama.cpp, CUDA/Metal and Python use when a model does not fit.
+OUT_OF_MEMORY_MARKERS = (
+    "out of memory",
+    "memoryerror",
+    "failed to allocate",
+    "cannot allocate memory",
+    "not enough space in the context",
+    "insufficient memory",
+    "outofmemory",
+)
+
+
+def is_out_of_memory(error: Optional[str]) -> bool:
+    """Whether a generation error looks like the model did not fit in memory."""
+    if not error:
+        return False
+    lowered = error.lower()
+    return any(marker in lowered for marker in OUT_OF_MEMORY_MARKERS)
+
+
+@dataclass(slots=True)
+class CredentialCheck:
+    provider: str
+    success: bool
+    label: Optional[str] = None
+    usage: Optional[float] = None
+    limit: Optional[float] = None
+    is_free_tier: Optional[bool] = None
+    error: Optional[str] = None
+
+
+SUPPORTED_PROVIDERS = ("openrouter",)
+
+
 # Popular small models suitable for local inference
 DEFAULT_LOCAL_MODELS = {
     # Tiny models (fast, good for testing)
@@ -136,6 +174,7 @@ def _draft_scene_local(
     context: str,
     model_path: Path,
     max_tokens: int = 1000,
+    system_prompt: Optional[str] = None,
 ) -> PromptResult:
     """Generate scene using local llama.cpp model."""
     if not _HAVE_LLAMA_CPP:
@@ -159,9 +198,7 @@ def _draft_scene_local(
             verbose=False,
         )
         
-        system_prompt = """You are a creative screenwriting assistant. Help write engaging scenes.
-Focus on natural dialogue, clear descriptions, character development, and pacing.
-Format in screenplay style."""
+        system_prompt = system_prompt or SYSTEM_PROMPT
         
         user_message = f"Context:\n{context}\n\nPrompt:\n{prompt}" if context else prompt
         
@@ -198,18 +235,21 @@ Format in screenplay style."""
             prompt=prompt,
             response="",
             model_id=str(model_path.name),
-            error=str(e)
+            error=f"{type(e).__name__}: {e}" if isinstance(e, MemoryError) else str(e)
         )
 
 
 def draft_scene(
     prompt: str,
-    model_id: str = "meta-llama/llama-3.1-8b-instruct:free",
+    model_id: str = DEFAULT_API_MODEL,
     root: str | Path | None = None,
     api_key: Optional[str] = None,
     context: str = "",
     max_tokens: int = 1000,
     use_local: bool = True,  # NEW: Enable local fallback by default
+    local_model_path: str | Path | None = None,
+    use_api: bool = True,
+    system_prompt: Optional[str] = None,
 ) -> PromptResult:
     """
     Generate a scene using LLM with smart fallback:
@@ -225,14 +265,17 @@ def draft_scene(
         context: Additional context for generation
         max_tokens: Maximum tokens to generate
         use_local: Enable local model fallback
+        local_model_path: Specific downloaded .gguf to use for the local fallback
+        use_api: Allow the cloud API; False keeps generation on this machine
+        system_prompt: Persona instructions replacing the default system prompt
     
     Returns:
         PromptResult with generated text or error
     """
     # Try cloud API first if we have an API key
-    if _HAVE_REQUESTS and (api_key or os.getenv("OPENROUTER_API_KEY")):
+    if use_api and _HAVE_REQUESTS and (api_key or os.getenv("OPENROUTER_API_KEY")):
         _logger.info("Trying OpenRouter API...")
-        result = _draft_scene_api(prompt, model_id, api_key, context, max_tokens)
+        result = _draft_scene_api(prompt, model_id, api_key, context, max_tokens, system_prompt)
         if result.response:
             return result
         _logger.warning(f"API failed: {result.error}")
@@ -251,7 +294,10 @@ def draft_scene(
         # Find any existing .gguf models
         existing_models = list(models_dir.glob("*.gguf"))
         
-        if existing_models:
+        if local_model_path and Path(local_model_path).exists():
+            model_path = Path(local_model_path)
+            _logger.info(f"Using selected model: {model_path.name}")
+        elif existing_models:
             model_path = existing_models[0]
             _logger.info(f"Using existing model: {model_path.name}")
         else:
@@ -269,14 +315,17 @@ def draft_scene(
                 )
         
         # Generate with local model
-        return _draft_scene_local(prompt, context, model_path, max_tokens)
+        return _draft_scene_local(prompt, context, model_path, max_tokens, system_prompt)
     
     # No options available
     error_msg = []
-    if not _HAVE_REQUESTS:
-        error_msg.append("requests library not installed")
-    if not api_key and not os.getenv("OPENROUTER_API_KEY"):
-        error_msg.append("no API key provided")
+    if not use_api:
+        error_msg.append("offline mode skips the API")
+    else:
+        if not _HAVE_REQUESTS:
+            error_msg.append("requests library not installed")
+        if not api_key and not os.getenv("OPENROUTER_API_KEY"):
+            error_msg.append("no API key provided")
     if not use_local:
         error_msg.append("local model disabled")
     elif not _HAVE_LLAMA_CPP:
@@ -290,12 +339,54 @@ def draft_scene(
     )
 
 
+SYSTEM_PROMPT = """You are a creative screenwriting assistant. Help write engaging scenes.
+Focus on natural dialogue, clear descriptions, character development, and pacing.
+Format in screenplay style."""
+
+
+def _post_completion(
+    key: str,
+    model: str,
+    prompt: str,
+    context: str,
+    max_tokens: int,
+    n: int = 1,
+    system_prompt: Optional[str] = None,
+) -> dict:
+    """POST a chat completion to OpenRouter and return the decoded JSON body."""
+    user_message = f"Context:\n{context}\n\nPrompt:\n{prompt}" if context else prompt
+    body = {
+        "model": model,
+        "messages": [
+            {"role": "system", "content": system_prompt or SYSTEM_PROMPT},
+            {"role": "user", "content": user_message}
+        ],
+        "max_tokens": max_tokens,
+        "temperature": 0.7,
+    }
+    if n > 1:
+        body["n"] = n
+
+    response = requests.post(
+        "https://openrouter.ai/api/v1/chat/completions",
+        headers={
+            "Authorization": f"Bearer {key}",
+            "Content-Type": "application/json",
+        },
+        json=body,
+        timeout=30 * n,
+    )
+    response.raise_for_status()
+    return response.json()
+
+
 def _draft_scene_api(
     prompt: str,
     model: str,
     api_key: Optional[str],
     context: str,
     max_tokens: int,
+    system_prompt: Optional[str] = None,
 ) -> PromptResult:
     """Generate scene using OpenRouter API."""
     key = api_key or os.getenv("OPENROUTER_API_KEY")
@@ -307,33 +398,8 @@ def _draft_scene_api(
             error="No API key. Set OPENROUTER_API_KEY environment variable."
         )
     
-    system_prompt = """You are a creative screenwriting assistant. Help write engaging scenes.
-Focus on natural dialogue, clear descriptions, character development, and pacing.
-Format in screenplay style."""
-    
-    user_message = f"Context:\n{context}\n\nPrompt:\n{prompt}" if context else prompt
-    
     try:
-        response = requests.post(
-            "https://openrouter.ai/api/v1/chat/completions",
-            headers={
-                "Authorization": f"Bearer {key}",
-                "Content-Type": "application/json",
-            },
-            json={
-                "model": model,
-                "messages": [
-                    {"role": "system", "content": system_prompt},
-                    {"role": "user", "content": user_message}
-                ],
-                "max_tokens": max_tokens,
-                "temperature": 0.7,
-            },
-            timeout=30,
-        )
-        
-        response.raise_for_status()
-        data = response.json()
+        data = _post_completion(key, model, prompt, context, max_tokens, system_prompt=system_prompt)
         generated_text = data["choices"][0]["message"]["content"]
         
         return PromptResult(
@@ -350,3 +416,423 @@ Format in screenplay style."""
             model_id=model,
             error=str(e)
         )
+
+
+def draft_scene_variations(
+    prompt: str,
+    variations: int = 1,
+    model_id: str = DEFAULT_API_MODEL,
+    root: str | Path | None = None,
+    api_key: Optional[str] = None,
+    context: str = "",
+    max_tokens: int = 1000,
+    use_local: bool = True,
+    on_variation: Optional[Callable[[int, int, PromptResult], None]] = None,
+    local_model_path: str | Path | None = None,
+    system_prompt: Optional[str] = None,
+) -> list[PromptResult]:
+    """
+    Generate several alternative drafts for the same prompt.
+
+    Asks OpenRouter for ``n`` choices in one request first. Providers that
+    ignore ``n`` return fewer choices, so the remainder is filled by repeated
+    ``draft_scene`` calls, which also covers the local llama.cpp fallback.
+    ``on_variation`` is called with (index, total, result) as each draft lands.
+    ``system_prompt`` replaces the default instructions, e.g. with a persona.
+    """
+    count = max(1, min(int(variations), MAX_VARIATIONS))
+    results: list[PromptResult] = []
+
+    def record(result: PromptResult) -> None:
+        results.append(result)
+        if on_variation:
+            on_variation(len(results) - 1, count, result)
+
+    key = api_key or os.getenv("OPENROUTER_API_KEY")
+    if count > 1 and _HAVE_REQUESTS and key:
+        try:
+            data = _post_completion(
+                key, model_id, prompt, context, max_tokens, n=count, system_prompt=system_prompt
+            )
+            for choice in data.get("choices", [])[:count]:
+                text = choice.get("message", {}).get("content")
+                if text:
+                    record(PromptResult(prompt=prompt, response=text, model_id=model_id))
+        except Exception as e:
+            _logger.warning(f"Batched variations failed, falling back to single requests: {e}")
+
+    while len(results) < count:
+        result = draft_scene(
+            prompt=prompt,
+            model_id=model_id,
+            root=root,
+            api_key=api_key,
+            context=context,
+            max_tokens=max_tokens,
+            use_local=use_local,
+            local_model_path=local_model_path,
+            system_prompt=system_prompt,
+        )
+        record(result)
+        if not result.response:
+            break
+
+    return results
+
+
+def test_credentials(provider: str, api_key: str) -> CredentialCheck:
+    """Verify an API key with a lightweight, non-billable request."""
+    if provider not in SUPPORTED_PROVIDERS:
+        return CredentialCheck(
+            provider=provider,
+            success=False,
+            error=f"Unsupported provider '{provider}'",
+        )
+    if not api_key.strip():
+        return CredentialCheck(provider=provider, success=False, error="API key is empty")
+    if not _HAVE_REQUESTS:
+        return CredentialCheck(
+            provider=provider,
+            success=False,
+            error="requests library not installed",
+        )
+
+    try:
+        response = requests.get(
+            "https://openrouter.ai/api/v1/key",
+            headers={"Authorization": f"Bearer {api_key.strip()}"},
+            timeout=15,
+        )
+    except Exception as e:
+        _logger.error(f"Credential check failed: {e}")
+        return CredentialCheck(provider=provider, success=False, error=str(e))
+
+    if response.status_code in (401, 403):
+        return CredentialCheck(provider=provider, success=False, error="Invalid API key")
+    if not response.ok:
+        return CredentialCheck(
+            provider=provider,
+            success=False,
+            error=f"OpenRouter returned HTTP {response.status_code}",
+        )
+
+    data = response.json().get("data") or {}
+    return CredentialCheck(
+        provider=provider,
+        success=True,
+        label=data.get("label"),
+        usage=data.get("usage"),
+        limit=data.get("limit"),
+        is_free_tier=data.get("is_free_tier"),
+    )
+
+
+DEFAULT_MAX_TOKENS = 1000
+
+# Rough OpenRouter list prices in USD per million (prompt, completion) tokens.
+# Only used for estimates; the provider's bill is authoritative.
+MODEL_PRICING: dict[str, tuple[float, float]] = {
+    "meta-llama/llama-3.1-8b-instruct:free": (0.0, 0.0),
+    "meta-llama/llama-3.1-8b-instruct": (0.02, 0.03),
+    "mistralai/mistral-7b-instruct": (0.03, 0.05),
+    "openai/gpt-4o-mini": (0.15, 0.60),
+    "anthropic/claude-3.5-haiku": (0.80, 4.00),
+}
+
+try:  # pragma: no cover - optional dependency
+    import tiktoken
+
+    _ENCODING = tiktoken.get_encoding("cl100k_base")
+except Exception:  # pragma: no cover - fallback path
+    _ENCODING = None
+
+
+def count_tokens(text: str) -> tuple[int, str]:
+    """Count tokens with tiktoken when installed, otherwise approximate.
+
+    The approximation assumes ~4 Latin characters per token and one token per
+    non-ASCII character, which is close for Tamil on BPE vocabularies.
+    """
+    if _ENCODING is not None:
+        return len(_ENCODING.encode(text)), "tiktoken:cl100k_base"
+    ascii_chars = sum(1 for char in text if char.isascii())
+    other_chars = len(text) - ascii_chars
+    return -(-ascii_chars // 4) + other_chars, "heuristic"
+
+
+def estimate_request(
+    prompt: str,
+    context: str = "",
+    model_id: str = DEFAULT_API_MODEL,
+    max_tokens: int = DEFAULT_MAX_TOKENS,
+    variations: int = 1,
+) -> dict:
+    """Estimate the tokens and worst-case cost of a draft_scene call."""
+    user_message = f"Context:\n{context}\n\nPrompt:\n{prompt}" if context else prompt
+    prompt_tokens, tokenizer = count_tokens(SYSTEM_PROMPT + "\n" + user_message)
+    completion_tokens = max_tokens * variations
+
+    local = model_id in DEFAULT_LOCAL_MODELS
+    pricing = (0.0, 0.0) if local else MODEL_PRICING.get(model_id)
+    cost = None
+    if pricing is not None:
+        prompt_price, completion_price = pricing
+        cost = (prompt_tokens * variations * prompt_price + completion_tokens * completion_price) / 1_000_000
+
+    return {
+        "model": model_id,
+        "local": local,
+        "tokenizer": tokenizer,
+        "prompt_tokens": prompt_tokens,
+        "max_completion_tokens": completion_tokens,
+        "total_tokens": prompt_tokens * variations + completion_tokens,
+        "estimated_cost_usd": cost,
+    }
+
+
+# Keep the surrounding text well inside small local context windows.
+INLINE_BEFORE_CHARS = 6000
+INLINE_AFTER_CHARS = 2000
+INLINE_MAX_TOKENS = 400
+
+
+def build_fim_prompt(before: str, after: str, instruction: str = "") -> str:
+    """Assemble a fill-in-the-middle prompt for chat models.
+
+    Chat endpoints have no native FIM tokens, so the text around the cursor is
+    wrapped in tags and the model is told to answer with the insertion only.
+    """
+    before = before[-INLINE_BEFORE_CHARS:]
+    after = after[:INLINE_AFTER_CHARS]
+    lines = [
+        "Continue the document at the <CURSOR/> marker.",
+        "Write only the text that belongs at the cursor so it reads naturally into what follows.",
+        "Do not repeat the surrounding text, add commentary, or wrap the answer in quotes or code fences.",
+    ]
+    if instruction.strip():
+        lines.append(f"Guidance: {instruction.strip()}")
+    lines.append("")
+    lines.append(f"<DOCUMENT>{before}<CURSOR/>{after}</DOCUMENT>")
+    return "\n".join(lines)
+
+
+def _clean_insertion(text: str, before: str, after: str) -> str:
+    """Strip wrappers and echoed context that chat models tend to add."""
+    text = text.strip("\n")
+    if text.startswith("```") and text.endswith("```"):
+        text = text.strip("`").split("\n", 1)[-1].rstrip("\n")
+    for marker in ("<CURSOR/>", "<DOCUMENT>", "</DOCUMENT>"):
+        text = text.replace(marker, "")
+
+    # Drop a leading echo of the text just before the cursor.
+    tail = before[-200:]
+    for size in range(len(tail), 10, -1):
+        if text.startswith(tail[-size:]):
+            text = text[size:]
+            break
+    # And a trailing echo of the text right after it.
+    head = after[:200]
+    for size in range(len(head), 10, -1):
+        if text.endswith(head[:size]):
+            text = text[:-size]
+            break
+    return text
+
+
+def draft_inline(
+    before: str,
+    after: str = "",
+    instruction: str = "",
+    model_id: str = DEFAULT_API_MODEL,
+    root: str | Path | None = None,
+    api_key: Optional[str] = None,
+    max_tokens: int = INLINE_MAX_TOKENS,
+    use_local: bool = True,
+) -> PromptResult:
+    """Generate the text to insert between ``before`` and ``after``."""
+    result = draft_scene(
+        prompt=build_fim_prompt(before, after, instruction),
+        model_id=model_id,
+        root=root,
+        api_key=api_key,
+        max_tokens=max_tokens,
+        use_local=use_local,
+    )
+    if result.response:
+        result.response = _clean_insertion(result.response, before, after)
+    return result
+
+
+# Scenes beyond this are trimmed from the end; the opening carries the setup.
+SUMMARY_INPUT_CHARS = 12000
+SUMMARY_MAX_TOKENS = 300
+
+
+@dataclass(slots=True)
+class SceneSummary:
+    summary: str
+    logline: str
+    model_id: str
+    error: Optional[str] = None
+
+
+def build_summary_prompt(scene: str) -> str:
+    """Ask for a logline and a summary in a fixed, parseable layout."""
+    return "\n".join(
+        [
+            "Summarize the following screenplay scene.",
+            "Answer in exactly this format and nothing else:",
+            "LOGLINE: <one sentence capturing the scene's dramatic core>",
+            "SUMMARY: <one paragraph describing what happens and why it matters>",
+            "",
+            f"<SCENE>{scene[:SUMMARY_INPUT_CHARS]}</SCENE>",
+        ]
+    )
+
+
+def _parse_summary(text: str) -> tuple[str, str]:
+    """Split a model reply into (summary, logline), tolerating loose formatting."""
+    sections: dict[str, list[str]] = {"logline": [], "summary": []}
+    current: Optional[str] = None
+    for line in text.strip().splitlines():
+        stripped = line.strip().lstrip("*#").strip()
+        label, _, rest = stripped.partition(":")
+        key = label.strip().strip("*").lower()
+        if key in sections:
+            current = key
+            stripped = rest.strip().lstrip("*").strip()
+        if current and stripped:
+            sections[current].append(stripped)
+
+    summary = " ".join(sections["summary"])
+    logline = " ".join(sections["logline"])
+    if not summary:
+        summary = " ".join(line.strip() for line in text.strip().splitlines() if line.strip())
+    if not logline:
+        sentence_end = summary.find(". ")
+        logline = summary if sentence_end < 0 else summary[: sentence_end + 1]
+    return summary, logline
+
+
+def summarize_scene(
+    scene: str,
+    model_id: str = DEFAULT_API_MODEL,
+    root: str | Path | None = None,
+    api_key: Optional[str] = None,
+    max_tokens: int = SUMMARY_MAX_TOKENS,
+    use_local: bool = True,
+    local_model_path: str | Path | None = None,
+    use_api: bool = True,
+) -> SceneSummary:
+    """Produce a one-paragraph summary and a one-line logline for ``scene``."""
+    result = draft_scene(
+        prompt=build_summary_prompt(scene),
+        model_id=model_id,
+        root=root,
+        api_key=api_key,
+        max_tokens=max_tokens,
+        use_local=use_local,
+        local_model_path=local_model_path,
+        use_api=use_api,
+    )
+    if not result.response:
+        return SceneSummary(summary="", logline="", model_id=result.model_id, error=result.error)
+    summary, logline = _parse_summary(result.response)
+    return SceneSummary(summary=summary, logline=logline, model_id=result.model_id)
+
+
+# Drafts beyond this are trimmed from the end, as with summaries.
+OUTLINE_INPUT_CHARS = 16000
+OUTLINE_MAX_TOKENS = 900
+
+
+@dataclass(slots=True)
+class OutlineBeat:
+    section: str
+    beat: str
+    summary: str
+
+
+@dataclass(slots=True)
+class DraftOutline:
+    beats: list[OutlineBeat]
+    model_id: str
+    error: Optional[str] = None
+
+
+def build_outline_prompt(draft: str, structure: list[dict[str, Any]]) -> str:
+    """Ask for a JSON beat list, naming the template's sections when there are any."""
+    lines = [
+        "Read the following draft and write a beat-by-beat outline of what happens in it.",
+    ]
+    if structure:
+        lines.append("Organize the beats under these sections, in order:")
+        for section in structure:
+            beats = ", ".join(section.get("beats") or [])
+            lines.append(f"- {section.get('name', '')}" + (f" (beats: {beats})" if beats else ""))
+        lines.append("Use a section's named beats where the draft covers them; skip any it does not.")
+    lines += [
+        "Answer with only a JSON array, no commentary, where each item is",
+        '{"section": "<section name>", "beat": "<short beat title>", "summary": "<one sentence>"}.',
+        "",
+        f"<DRAFT>{draft[:OUTLINE_INPUT_CHARS]}</DRAFT>",
+    ]
+    return "\n".join(lines)
+
+
+def _parse_outline(text: str) -> list[OutlineBeat]:
+    """Read the JSON array out of a model reply, falling back to one beat per bullet line."""
+    start, end = text.find("["), text.rfind("]")
+    if 0 <= start < end:
+        try:
+            items = json.loads(text[start : end + 1])
+        except json.JSONDecodeError:
+            items = None
+        if isinstance(items, list):
+            beats = [
+                OutlineBeat(
+                    section=str(item.get("section") or "").strip(),
+                    beat=str(item.get("beat") or "").strip(),
+                    summary=str(item.get("summary") or "").strip(),
+                )
+                for item in items
+                if isinstance(item, dict)
+            ]
+            return [beat for beat in beats if beat.beat or beat.summary]
+
+    beats = []
+    for line in text.splitlines():
+        stripped = line.strip()
+        if not stripped.startswith(("-", "*")):
+            continue
+        title, _, summary = stripped.lstrip("-* ").partition(":")
+        beats.append(OutlineBeat(section="", beat=title.strip("* "), summary=summary.strip()))
+    return beats
+
+
+def extract_outline(
+    draft: str,
+    structure: list[dict[str, Any]],
+    model_id: str = DEFAULT_API_MODEL,
+    root: str | Path | None = None,
+    api_key: Optional[str] = None,
+    max_tokens: int = OUTLINE_MAX_TOKENS,
+    use_local: bool = True,
+    use_api: bool = True,
+) -> DraftOutline:
+    """Outline ``draft`` beat by beat, fitted to the template ``structure``."""
+    result = draft_scene(
+        prompt=build_outline_prompt(draft, structure),
+        model_id=model_id,
+        root=root,
+        api_key=api_key,
+        max_tokens=max_tokens,
+        use_local=use_local,
+        use_api=use_api,
+    )
+    if not result.response:
+        return DraftOutline(beats=[], model_id=result.model_id, error=result.error)
+    beats = _parse_outline(result.response)
+    if not beats:
+        return DraftOutline(beats=[], model_id=result.model_id, error="The model did not return an outline")
+    return DraftOutline(beats=beats, model_id=result.model_id)
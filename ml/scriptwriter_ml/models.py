@@ -2,9 +2,12 @@
 
 from __future__ import annotations
 
+import urllib.request
 from dataclasses import asdict, dataclass
 from pathlib import Path
-from typing import Iterable
+from typing import Callable, Iterable
+
+DOWNLOAD_CHUNK_BYTES = 1024 * 1024
 
 
 @dataclass(slots=True)
@@ -17,6 +20,7 @@ class ModelSpec:
     filename: str
     supports_mlx: bool = False
     requires_gpu: bool = False
+    url: str | None = None
 
 
 MODEL_REGISTRY: list[ModelSpec] = [
@@ -61,6 +65,7 @@ MODEL_REGISTRY: list[ModelSpec] = [
         provider="Mistral AI",
         size_mb=4100,
         filename="mistral-7b-q4km.gguf",
+        url="https://huggingface.co/TheBloke/Mistral-7B-Instruct-v0.2-GGUF/resolve/main/mistral-7b-instruct-v0.2.Q4_K_M.gguf",
     ),
     ModelSpec(
         identifier="phi-2-int4",
@@ -69,6 +74,7 @@ MODEL_REGISTRY: list[ModelSpec] = [
         provider="Microsoft",
         size_mb=1800,
         filename="phi-2-int4.gguf",
+        url="https://huggingface.co/TheBloke/phi-2-GGUF/resolve/main/phi-2.Q4_K_M.gguf",
     ),
     ModelSpec(
         identifier="ibm-granite-7b-slim",
@@ -121,3 +127,43 @@ def ensure_directories(root: str | Path | None = None, identifiers: Iterable[str
         if identifiers is not None and spec.identifier not in identifiers:
             continue
         (base / spec.identifier).mkdir(parents=True, exist_ok=True)
+
+
+def download_registry_model(
+    identifier: str,
+    root: str | Path | None = None,
+    on_progress: Callable[[int, int], None] | None = None,
+) -> Path:
+    """Download a registry model into ``<root>/<identifier>/<filename>``.
+
+    Data is streamed into a ``.part`` file that is only renamed into place
+    once the transfer completes, so an interrupted download never looks
+    installed.
+    """
+
+    spec = next((spec for spec in MODEL_REGISTRY if spec.identifier == identifier), None)
+    if spec is None:
+        raise KeyError(f"Unknown model id: {identifier}")
+    if spec.url is None:
+        raise ValueError(f"No download source configured for {identifier}")
+
+    target = locate_model(identifier, root=root)
+    if target.exists():
+        return target
+
+    target.parent.mkdir(parents=True, exist_ok=True)
+    partial = target.with_name(target.name + ".part")
+    with urllib.request.urlopen(spec.url) as response, partial.open("wb") as handle:
+        total = int(response.headers.get("Content-Length") or 0)
+        downloaded = 0
+        while True:
+            chunk = response.read(DOWNLOAD_CHUNK_BYTES)
+            if not chunk:
+                break
+            handle.write(chunk)
+            downloaded += len(chunk)
+            if on_progress is not None:
+                on_progress(downloaded, total)
+
+    partial.replace(target)
+    return target
@@ -0,0 +1,166 @@
+"""Tamil → Latin phonetic hints with syllable breakdowns."""
+
+from __future__ import annotations
+
+import logging
+from dataclasses import dataclass, field
+
+_logger = logging.getLogger(__name__)
+
+try:  # pragma: no cover - optional dependency
+    from indic_transliteration import sanscript
+    from indic_transliteration.sanscript import transliterate as indic_transliterate
+
+    _HAVE_INDIC = True
+except Exception:  # pragma: no cover - fallback path
+    _HAVE_INDIC = False
+
+VIRAMA = "்"
+AYTHAM = "ஃ"
+
+VOWELS: dict[str, str] = {
+    "அ": "a",
+    "ஆ": "aa",
+    "இ": "i",
+    "ஈ": "ii",
+    "உ": "u",
+    "ஊ": "uu",
+    "எ": "e",
+    "ஏ": "ee",
+    "ஐ": "ai",
+    "ஒ": "o",
+    "ஓ": "oo",
+    "ஔ": "au",
+}
+
+CONSONANTS: dict[str, str] = {
+    "க": "k",
+    "ங": "ng",
+    "ச": "ch",
+    "ஞ": "nj",
+    "ட": "t",
+    "ண": "n",
+    "த": "th",
+    "ந": "n",
+    "ப": "p",
+    "ம": "m",
+    "ய": "y",
+    "ர": "r",
+    "ல": "l",
+    "வ": "v",
+    "ழ": "zh",
+    "ள": "l",
+    "ற": "r",
+    "ன": "n",
+    "ஜ": "j",
+    "ஷ": "sh",
+    "ஸ": "s",
+    "ஹ": "h",
+}
+
+VOWEL_SIGNS: dict[str, str] = {
+    "ா": "aa",
+    "ி": "i",
+    "ீ": "ii",
+    "ு": "u",
+    "ூ": "uu",
+    "ெ": "e",
+    "ே": "ee",
+    "ை": "ai",
+    "ொ": "o",
+    "ோ": "oo",
+    "ௌ": "au",
+}
+
+
+@dataclass
+class Syllable:
+    text: str
+    roman: str
+
+
+@dataclass
+class PhoneticHint:
+    word: str
+    romanized: str
+    syllables: list[Syllable]
+    engine: str
+    notes: list[str] = field(default_factory=list)
+
+
+def _letters(word: str) -> list[tuple[str, str, bool]]:
+    """Split Tamil text into (letter, roman, is_pure_consonant) units."""
+
+    letters: list[tuple[str, str, bool]] = []
+    index = 0
+    while index < len(word):
+        char = word[index]
+        if char in CONSONANTS:
+            if word.startswith("க்ஷ", index):
+                base, roman, index = "க்ஷ", "ksh", index + 3
+            else:
+                base, roman, index = char, CONSONANTS[char], index + 1
+            following = word[index] if index < len(word) else ""
+            if following == VIRAMA:
+                letters.append((base + VIRAMA, roman, True))
+                index += 1
+            elif following in VOWEL_SIGNS:
+                letters.append((base + following, roman + VOWEL_SIGNS[following], False))
+                index += 1
+            else:
+                letters.append((base, roman + "a", False))
+        elif char in VOWELS:
+            letters.append((char, VOWELS[char], False))
+            index += 1
+        elif char == AYTHAM:
+            letters.append((char, "h", True))
+            index += 1
+        else:
+            letters.append((char, char, False))
+            index += 1
+    return letters
+
+
+def syllabify(word: str) -> list[Syllable]:
+    """Group letters into spoken syllables, closing them with pure consonants."""
+
+    syllables: list[Syllable] = []
+    for text, roman, pure in _letters(word):
+        if text.isspace():
+            continue
+        if pure and syllables:
+            syllables[-1].text += text
+            syllables[-1].roman += roman
+        else:
+            syllables.append(Syllable(text=text, roman=roman))
+    return syllables
+
+
+def phonetic_hint(word: str) -> PhoneticHint:
+    cleaned = word.strip()
+    syllables = syllabify(cleaned)
+    notes: list[str] = []
+
+    if _HAVE_INDIC:
+        try:
+            romanized = indic_transliterate(cleaned, sanscript.TAMIL, sanscript.ISO)
+            return PhoneticHint(
+                word=cleaned,
+                romanized=romanized,
+                syllables=syllables,
+                engine="indic-transliteration",
+                notes=notes,
+            )
+        except Exception as exc:  # pragma: no cover - log and continue
+            _logger.warning("indic-transliteration phonetics failed: %s", exc, exc_info=True)
+            notes.append("indic-transliteration failed; using reverse transliteration")
+    else:
+        notes.append("Install optional dependency 'indic-transliteration' for ISO romanization")
+
+    return PhoneticHint(
+        word=cleaned,
+        romanized="".join(roman for _, roman, _ in _letters(cleaned)),
+        syllables=syllables,
+        engine="reverse-transliteration",
+        notes=notes,
+    )
@@ -3,6 +3,7 @@
 from __future__ import annotations
 
 import logging
+import os
 from pathlib import Path
 from typing import Iterable
 
@@ -18,6 +19,32 @@ except ImportError:
     _HAVE_SR = False
     _logger.warning("speech_recognition not installed. Install with: pip install SpeechRecognition")
 
+DIARIZATION_MODEL = "pyannote/speaker-diarization-3.1"
+
+# faster-whisper model sizes behind each registry id.
+WHISPER_SIZES = {
+    "faster-whisper-base": "base",
+    "faster-whisper-small": "small",
+}
+WHISPER_SAMPLE_RATE = 16000
+
+# Language codes each speech-to-text model can transcribe. Both Whisper
+# entries share a list because recognition currently runs through the same
+# backend; give a model its own tuple once it is loaded directly.
+WHISPER_LANGUAGES = ("en-IN", "en-US", "en-GB", "ta-IN", "hi-IN", "te-IN", "ml-IN", "kn-IN")
+MODEL_LANGUAGES: dict[str, tuple[str, ...]] = {
+    "faster-whisper-base": WHISPER_LANGUAGES,
+    "faster-whisper-small": WHISPER_LANGUAGES,
+}
+
+
+def supported_languages(model_id: str) -> dict:
+    """Language codes ``model_id`` accepts, or an 'error' for unknown models."""
+    languages = MODEL_LANGUAGES.get(model_id)
+    if languages is None:
+        return {"model": model_id, "languages": [], "error": f"Unknown speech-to-text model: {model_id}"}
+    return {"model": model_id, "languages": list(languages)}
+
 
 def warmup(model_id: str, root: str | Path | None = None) -> None:
     """Ensure the requested model assets are present on disk."""
@@ -27,17 +54,37 @@ def warmup(model_id: str, root: str | Path | None = None) -> None:
     # Actual loading is performed lazily by the runtime (mlx-whisper/faster-whisper).
 
 
-def transcribe_audio_file(audio_path: str, language: str = "en-IN") -> dict:
+def transcribe_audio_file(
+    audio_path: str,
+    language: str = "en-IN",
+    start: float | None = None,
+    end: float | None = None,
+    diarize: bool = False,
+    model_id: str | None = None,
+    root: str | Path | None = None,
+) -> dict:
     """
     Transcribe an audio file to text using speech_recognition library.
     
     Args:
         audio_path: Path to audio file (WAV, FLAC, etc.)
         language: Language code (e.g., 'en-IN', 'ta-IN')
+        start: Optional clip start in seconds from the beginning of the file
+        end: Optional clip end in seconds from the beginning of the file
+        diarize: Label each turn with a speaker id when pyannote.audio is installed
+        model_id: Registry Whisper model to transcribe with instead of Google
+            recognition; its weights are fetched into the model folder under root
     
     Returns:
-        Dictionary with 'text', 'confidence', and optional 'error' keys
+        Dictionary with 'text', 'confidence', and optional 'error' keys.
+        When a range is requested, 'start_secs'/'end_secs' describe the clip
+        within the file so timestamps can be placed on the full timeline.
+        With diarization, 'segments' lists each speaker turn and 'script'
+        holds the transcript as 'SPEAKER N:' lines. If diarization is not
+        available the plain transcript is returned with a 'notes' entry.
     """
+    if model_id is not None:
+        return _transcribe_whisper(audio_path, model_id, language, start, end, root)
     if not _HAVE_SR:
         return {
             "text": "",
@@ -49,7 +96,23 @@ def transcribe_audio_file(audio_path: str, language: str = "en-IN") -> dict:
     
     try:
         with sr.AudioFile(audio_path) as source:
-            audio_data = recognizer.record(source)
+            clip = _resolve_clip(source.DURATION, start, end)
+            if "error" in clip:
+                return {"text": "", "confidence": 0.0, **clip}
+            notes: list[str] = []
+            if diarize:
+                turns = _speaker_turns(audio_path, clip["start_secs"], clip["end_secs"])
+                if turns is not None:
+                    return _transcribe_turns(recognizer, source, turns, language, clip)
+                notes.append("Speaker diarization unavailable; returning a plain transcript")
+            if start is None and end is None:
+                audio_data = recognizer.record(source)
+            else:
+                audio_data = recognizer.record(
+                    source,
+                    offset=clip["start_secs"],
+                    duration=clip["end_secs"] - clip["start_secs"],
+                )
             
         # Try Google Speech Recognition (free, no API key needed)
         try:
@@ -57,7 +120,9 @@ def transcribe_audio_file(audio_path: str, language: str = "en-IN") -> dict:
             return {
                 "text": text,
                 "confidence": 1.0,
-                "engine": "google"
+                "engine": "google",
+                **clip,
+                **({"notes": notes} if notes else {}),
             }
         except sr.UnknownValueError:
             return {
@@ -82,13 +147,180 @@ def transcribe_audio_file(audio_path: str, language: str = "en-IN") -> dict:
         }
 
 
-def transcribe_from_microphone(duration: int = 5, language: str = "en-IN") -> dict:
+def _transcribe_whisper(
+    audio_path: str,
+    model_id: str,
+    language: str,
+    start: float | None,
+    end: float | None,
+    root: str | Path | None,
+) -> dict:
+    """Transcribe with the faster-whisper size behind a registry model id."""
+    size = WHISPER_SIZES.get(model_id)
+    if size is None:
+        return {"text": "", "confidence": 0.0, "error": f"Unknown speech-to-text model: {model_id}"}
+    if language not in MODEL_LANGUAGES[model_id]:
+        return {"text": "", "confidence": 0.0, "error": f"{model_id} does not support {language}"}
+    try:
+        from faster_whisper import WhisperModel
+        from faster_whisper.audio import decode_audio
+    except ImportError:
+        return {
+            "text": "",
+            "confidence": 0.0,
+            "error": "faster-whisper not installed. Run: pip install faster-whisper",
+        }
+
+    try:
+        samples = decode_audio(audio_path, sampling_rate=WHISPER_SAMPLE_RATE)
+        clip = _resolve_clip(len(samples) / WHISPER_SAMPLE_RATE, start, end)
+        if "error" in clip:
+            return {"text": "", "confidence": 0.0, **clip}
+        first = int(clip["start_secs"] * WHISPER_SAMPLE_RATE)
+        last = int(clip["end_secs"] * WHISPER_SAMPLE_RATE)
+        ensure_directories(root, identifiers=[model_id])
+        model = WhisperModel(size, download_root=str(locate_model(model_id, root=root).parent))
+        segments, _ = model.transcribe(samples[first:last], language=language.split("-")[0])
+        text = " ".join(segment.text.strip() for segment in segments).strip()
+    except Exception as e:
+        _logger.error(f"Whisper transcription error: {e}")
+        return {"text": "", "confidence": 0.0, "error": str(e)}
+
+    if not text:
+        return {"text": "", "confidence": 0.0, "error": "Could not understand audio"}
+    return {"text": text, "confidence": 1.0, "engine": "faster-whisper", **clip}
+
+
+def _speaker_turns(audio_path: str, start: float, end: float) -> list[dict] | None:
+    """Split [start, end) into speaker turns, or None when diarization can't run."""
+    # Speaker diarization is optional, and importing pyannote pulls in torch,
+    # so only transcriptions that ask for speakers pay for it.
+    try:
+        from pyannote.audio import Pipeline as DiarizationPipeline
+    except ImportError:
+        return None
+    try:
+        pipeline = DiarizationPipeline.from_pretrained(
+            DIARIZATION_MODEL, use_auth_token=os.environ.get("HF_TOKEN")
+        )
+        if pipeline is None:
+            return None
+        diarization = pipeline(audio_path)
+    except Exception as e:
+        _logger.warning(f"Speaker diarization failed: {e}")
+        return None
+
+    speakers: dict[str, str] = {}
+    turns: list[dict] = []
+    for turn, _, label in diarization.itertracks(yield_label=True):
+        turn_start = max(float(turn.start), start)
+        turn_end = min(float(turn.end), end)
+        if turn_end <= turn_start:
+            continue
+        speaker = speakers.setdefault(label, f"SPEAKER {len(speakers) + 1}")
+        if turns and turns[-1]["speaker"] == speaker:
+            turns[-1]["end_secs"] = turn_end
+        else:
+            turns.append({"speaker": speaker, "start_secs": turn_start, "end_secs": turn_end})
+    return turns
+
+
+def _transcribe_turns(recognizer, source, turns: list[dict], language: str, clip: dict) -> dict:
+    """Recognize each speaker turn separately and assemble a labelled script."""
+    segments = []
+    # record() reads forward from the current stream position, so offsets are relative
+    position = 0.0
+    for turn in turns:
+        # Overlapping speech is attributed to whoever started talking first
+        begin = max(turn["start_secs"], position)
+        if turn["end_secs"] <= begin:
+            continue
+        audio_data = recognizer.record(
+            source,
+            offset=begin - position,
+            duration=turn["end_secs"] - begin,
+        )
+        position = turn["end_secs"]
+        try:
+            text = recognizer.recognize_google(audio_data, language=language)
+        except sr.UnknownValueError:
+            text = ""
+        if text:
+            segments.append({**turn, "text": text})
+
+    if not segments:
+        return {"text": "", "confidence": 0.0, "error": "Could not understand audio"}
+
+    return {
+        "text": " ".join(segment["text"] for segment in segments),
+        "confidence": 1.0,
+        "engine": "google+pyannote",
+        "segments": segments,
+        "script": "\n\n".join(f"{s['speaker']}: {s['text']}" for s in segments),
+        **clip,
+    }
+
+
+def _resolve_clip(duration: float, start: float | None, end: float | None) -> dict:
+    """Clamp an optional [start, end) range to the file, reporting invalid ranges."""
+    clip_start = 0.0 if start is None else float(start)
+    clip_end = float(duration) if end is None else float(end)
+    if clip_start < 0 or clip_start >= clip_end:
+        return {"error": f"Invalid range: start ({clip_start:.2f}s) must be before end ({clip_end:.2f}s)"}
+    if clip_end > duration:
+        return {"error": f"Range ends at {clip_end:.2f}s but the file is only {duration:.2f}s long"}
+    return {"start_secs": clip_start, "end_secs": clip_end, "duration_secs": float(duration)}
+
+
+def list_input_devices() -> dict:
+    """List audio devices that can be used for recording."""
+    if not _HAVE_SR:
+        return {
+            "devices": [],
+            "error": "speech_recognition library not installed. Run: pip install SpeechRecognition pyaudio"
+        }
+
+    try:
+        pyaudio = sr.Microphone.get_pyaudio()
+        audio = pyaudio.PyAudio()
+    except Exception as e:
+        _logger.error(f"Audio device enumeration failed: {e}")
+        return {"devices": [], "error": str(e)}
+
+    devices = []
+    try:
+        for index in range(audio.get_device_count()):
+            info = audio.get_device_info_by_index(index)
+            channels = int(info.get("maxInputChannels", 0))
+            if channels <= 0:
+                continue
+            devices.append({
+                "id": index,
+                "name": info.get("name", f"Device {index}"),
+                "channels": channels,
+                "default_sample_rate": int(info.get("defaultSampleRate", 0)) or None,
+            })
+    finally:
+        audio.terminate()
+    return {"devices": devices}
+
+
+def transcribe_from_microphone(
+    duration: int = 5,
+    language: str = "en-IN",
+    device_id: int | None = None,
+    sample_rate: int | None = None,
+    output: str | Path | None = None,
+) -> dict:
     """
     Record from microphone and transcribe in real-time.
     
     Args:
         duration: Maximum recording duration in seconds
         language: Language code (e.g., 'en-IN', 'ta-IN')
+        device_id: Input device index from list_input_devices (system default if omitted)
+        sample_rate: Recording sample rate in Hz (device default if omitted)
+        output: Optional WAV path where the captured audio is saved
     
     Returns:
         Dictionary with transcription result
@@ -103,12 +335,19 @@ def transcribe_from_microphone(duration: int = 5, language: str = "en-IN") -> di
     recognizer = sr.Recognizer()
     
     try:
-        with sr.Microphone() as source:
+        with sr.Microphone(device_index=device_id, sample_rate=sample_rate) as source:
             _logger.info("Adjusting for ambient noise...")
             recognizer.adjust_for_ambient_noise(source, duration=1)
             
             _logger.info(f"Recording for up to {duration} seconds...")
             audio_data = recognizer.listen(source, timeout=duration, phrase_time_limit=duration)
+
+        saved = {}
+        if output is not None:
+            output_path = Path(output)
+            output_path.parent.mkdir(parents=True, exist_ok=True)
+            output_path.write_bytes(audio_data.get_wav_data())
+            saved = {"audio_file": str(output_path)}
             
         # Transcribe using Google Speech Recognition
         try:
@@ -118,14 +357,16 @@ def transcribe_from_microphone(duration: int = 5, language: str = "en-IN") -> di
                 "text": text,
                 "success": True,
                 "confidence": 1.0,
-                "engine": "google"
+                "engine": "google",
+                **saved,
             }
         except sr.UnknownValueError:
             return {
                 "text": "",
                 "success": False,
                 "confidence": 0.0,
-                "error": "Could not understand audio. Please speak clearly."
+                "error": "Could not understand audio. Please speak clearly.",
+                **saved,
             }
         except sr.RequestError as e:
             _logger.error(f"Google Speech Recognition error: {e}")
@@ -133,7 +374,8 @@ def transcribe_from_microphone(duration: int = 5, language: str = "en-IN") -> di
                 "text": "",
                 "success": False,
                 "confidence": 0.0,
-                "error": f"Service error: {str(e)}"
+                "error": f"Service error: {str(e)}",
+                **saved,
             }
             
     except OSError as e:
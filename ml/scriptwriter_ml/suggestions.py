@@ -0,0 +1,91 @@
+"""Prefix suggestions for typing Tamil in Latin script, one keystroke at a time."""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from functools import lru_cache
+
+from .phonetics import phonetic_hint
+from .transliteration import _transduce
+
+DEFAULT_SUGGESTIONS = 5
+MAX_SUGGESTIONS = 10
+VIRAMA = "்"
+
+# Common words in everyday and screenplay Tamil, most frequent first. Position
+# in this tuple is the ranking signal, so keep new entries roughly in order.
+COMMON_WORDS: tuple[str, ...] = (
+    "ஒரு", "அது", "இது", "என்ன", "நான்", "இல்லை", "நீ", "அவன்", "அவள்", "அவர்",
+    "என்", "உன்", "சரி", "தான்", "வா", "போ", "இங்கே", "அங்கே", "எங்கே", "ஏன்",
+    "எப்படி", "யார்", "இப்போ", "இப்போது", "நாம்", "நாங்கள்", "நீங்கள்", "அவர்கள்", "என்னை", "உன்னை",
+    "வேண்டும்", "வேண்டாம்", "முடியும்", "முடியாது", "தெரியும்", "தெரியாது", "இருக்கு", "இருக்கிறது", "இருந்தது", "எல்லாம்",
+    "ரொம்ப", "கொஞ்சம்", "மட்டும்", "கூட", "இன்னும்", "பிறகு", "அப்போ", "எப்போது", "நல்ல", "நன்றி",
+    "வணக்கம்", "அம்மா", "அப்பா", "அண்ணா", "அக்கா", "தம்பி", "தங்கை", "வீடு", "ஊர்", "நேரம்",
+    "நாள்", "இரவு", "காலை", "மாலை", "பகல்", "வேலை", "பணம்", "தண்ணீர்", "சாப்பாடு", "காதல்",
+    "கண்", "மனசு", "மனம்", "உயிர்", "வாழ்க்கை", "உண்மை", "பொய்", "பயம்", "கோபம்", "சிரிப்பு",
+    "அழுகை", "வந்து", "போய்", "பார்", "சொல்", "கேள்", "செய்", "பேசு", "சொன்னான்", "சொன்னாள்",
+    "பார்த்தேன்", "உள்ளே", "வெளியே", "முன்னால்", "பின்னால்", "கதவு", "தெரு", "சாலை", "கிராமம்", "நகரம்",
+    "கோயில்", "கடவுள்", "பள்ளி", "மழை", "வெயில்", "கடல்", "இடம்", "காட்சி", "குழந்தை", "பையன்",
+    "பெண்", "ஆண்", "நண்பன்", "நண்பர்கள்", "மக்கள்", "கல்யாணம்", "தமிழ்", "போலீஸ்", "டாக்டர்", "ரயில்",
+)
+_RANK = {word: rank for rank, word in enumerate(COMMON_WORDS)}
+_ROMANIZED = {word: phonetic_hint(word).romanized.lower() for word in COMMON_WORDS}
+
+
+@dataclass(slots=True)
+class Suggestion:
+    text: str
+    source: str
+
+
+def _prefix_forms(candidate: str) -> tuple[str, ...]:
+    """A half-typed syllable may still gain a vowel, so also try it without its virama."""
+    if candidate.endswith(VIRAMA):
+        return (candidate, candidate[: -len(VIRAMA)])
+    return (candidate,)
+
+
+@lru_cache(maxsize=2048)
+def suggest(prefix: str, limit: int = DEFAULT_SUGGESTIONS) -> list[Suggestion]:
+    """Tamil suggestions for the Latin ``prefix`` of the word being typed.
+
+    What was typed comes first, as a known word when one spells it exactly,
+    so committing it never needs the arrow keys. Common words whose
+    romanization or Tamil spelling continues the prefix follow, most
+    frequent first.
+    """
+    cleaned = prefix.strip().lower()
+    limit = max(1, min(limit, MAX_SUGGESTIONS))
+    if not cleaned or not cleaned.isascii() or not cleaned.isalpha():
+        return []
+
+    candidates = [candidate for candidate in _transduce(cleaned) if candidate]
+    forms = {form for candidate in candidates for form in _prefix_forms(candidate)}
+
+    suggestions: list[Suggestion] = []
+    seen: set[str] = set()
+
+    def add(text: str, source: str) -> None:
+        if text not in seen and len(suggestions) < limit:
+            seen.add(text)
+            suggestions.append(Suggestion(text=text, source=source))
+
+    exact = [candidate for candidate in candidates if candidate in _RANK]
+    exact += [word for word, roman in _ROMANIZED.items() if roman == cleaned]
+    if exact:
+        add(min(exact, key=_RANK.__getitem__), "lexicon")
+    elif candidates:
+        add(candidates[0], "transliteration")
+    for word in sorted(
+        (
+            word
+            for word in COMMON_WORDS
+            if _ROMANIZED[word].startswith(cleaned)
+            or any(word.startswith(form) for form in forms)
+        ),
+        key=_RANK.__getitem__,
+    ):
+        add(word, "lexicon")
+    for candidate in candidates[1:]:
+        add(candidate, "transliteration")
+    return suggestions
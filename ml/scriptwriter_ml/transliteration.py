@@ -27,6 +27,15 @@ except Exception:  # pragma: no cover
 
 MAX_CANDIDATES = 8
 
+# Engine preference per mode. ITRANS output follows formal spelling, while the
+# Tanglish converter mirrors how colloquial Tamil is typed in Latin script.
+TRANSLITERATION_MODES: dict[str, tuple[str, ...]] = {
+    "phonetic": ("indic", "opentamil", "fallback"),
+    "formal": ("indic", "fallback", "opentamil"),
+    "colloquial": ("opentamil", "fallback", "indic"),
+}
+DEFAULT_MODE = "phonetic"
+
 CONSONANT_MAP: dict[str, list[str]] = {
     "ksh": ["க்ஷ"],
     "ng": ["ங"],
@@ -137,20 +146,30 @@ class TransliterationResult:
     notes: list[str]
 
 
-def transliterate_tamil(text: str, scheme: str = "itrans") -> TransliterationResult:
-    """Transliterate latin text into Tamil script and provide suggestions."""
+def transliterate_tamil(
+    text: str, scheme: str = "itrans", mode: str = DEFAULT_MODE
+) -> TransliterationResult:
+    """Transliterate latin text into Tamil script and provide suggestions.
+
+    ``mode`` picks which engine's suggestions are ranked first; see
+    ``TRANSLITERATION_MODES``.
+    """
 
     cleaned = text.strip()
     if not cleaned:
         return TransliterationResult(candidates=[], engine="noop", notes=[])
 
-    suggestions: list[str] = []
     notes: list[str] = []
+    if mode not in TRANSLITERATION_MODES:
+        notes.append(f"Unknown transliteration mode '{mode}'; using {DEFAULT_MODE}")
+        mode = DEFAULT_MODE
+
+    by_engine: dict[str, list[str]] = {"indic": [], "opentamil": [], "fallback": []}
 
     if _HAVE_INDIC:
         try:
             mapped = indic_transliterate(cleaned, scheme, sanscript.TAMIL)
-            suggestions.append(mapped)
+            by_engine["indic"].append(mapped)
         except Exception as exc:  # pragma: no cover - log and continue
             _logger.warning("indic-transliteration failed: %s", exc, exc_info=True)
             notes.append("indic-transliteration failed; using fallbacks")
@@ -160,13 +179,16 @@ def transliterate_tamil(text: str, scheme: str = "itrans") -> TransliterationRes
     if _HAVE_OPENTAMIL:
         try:
             tamil_text = tanglish_to_unicode(cleaned)
-            suggestions.append(tamil_text)
+            by_engine["opentamil"].append(tamil_text)
         except Exception as exc:  # pragma: no cover
             _logger.warning("open-tamil transliteration failed: %s", exc, exc_info=True)
             notes.append("open-tamil fallback failed")
 
-    fallback_candidates = _fallback_transliterate(cleaned)
-    suggestions.extend(fallback_candidates)
+    by_engine["fallback"].extend(_fallback_transliterate(cleaned))
+
+    suggestions: list[str] = []
+    for engine in TRANSLITERATION_MODES[mode]:
+        suggestions.extend(by_engine[engine])
 
     # Deduplicate while preserving order and limit to MAX_CANDIDATES
     seen = set()
@@ -2,7 +2,12 @@
 
 from __future__ import annotations
 
+import base64
 import logging
+import os
+import shutil
+import subprocess
+import tempfile
 from pathlib import Path
 from typing import Iterable
 
@@ -19,7 +24,13 @@ except ImportError:
     _logger.warning("pyttsx3 not installed. Install with: pip install pyttsx3")
 
 
-def synthesize_to_file(text: str, output_path: str, language: str = "en") -> dict:
+def synthesize_to_file(
+    text: str,
+    output_path: str,
+    language: str = "en",
+    voice: str | None = None,
+    model_id: str | None = None,
+) -> dict:
     """
     Synthesize text to speech and save to file.
     
@@ -27,10 +38,16 @@ def synthesize_to_file(text: str, output_path: str, language: str = "en") -> dic
         text: Text to synthesize
         output_path: Path to save audio file
         language: Language code
+        voice: Optional engine voice id to use instead of the default
+        model_id: Registry TTS model to synthesize with instead of pyttsx3
     
     Returns:
         Dictionary with 'success', 'path', and optional 'error' keys
     """
+    if model_id == "espeak-ng-tamil":
+        return _synthesize_espeak(text, output_path, language, voice)
+    if model_id is not None:
+        return {"success": False, "error": f"TTS model '{model_id}' has no synthesis engine yet"}
     if not _HAVE_PYTTSX3:
         return {
             "success": False,
@@ -41,6 +58,8 @@ def synthesize_to_file(text: str, output_path: str, language: str = "en") -> dic
         engine = pyttsx3.init()
         engine.setProperty('rate', 150)
         engine.setProperty('volume', 0.9)
+        if voice:
+            engine.setProperty('voice', voice)
         engine.save_to_file(text, output_path)
         engine.runAndWait()
         
@@ -58,6 +77,55 @@ def synthesize_to_file(text: str, output_path: str, language: str = "en") -> dic
         }
 
 
+def _synthesize_espeak(text: str, output_path: str, language: str, voice: str | None) -> dict:
+    """Render ``text`` to a WAV with the espeak-ng binary."""
+    binary = shutil.which("espeak-ng") or shutil.which("espeak")
+    if binary is None:
+        return {"success": False, "error": "espeak-ng not installed"}
+    try:
+        completed = subprocess.run(
+            [binary, "-v", voice or language, "-w", output_path, text],
+            capture_output=True,
+            text=True,
+            timeout=60,
+        )
+    except Exception as e:
+        _logger.error(f"eSpeak synthesis error: {e}")
+        return {"success": False, "error": str(e)}
+    if completed.returncode != 0:
+        return {"success": False, "error": completed.stderr.strip() or "espeak-ng failed"}
+    return {"success": True, "path": output_path, "engine": "espeak-ng"}
+
+
+def preview_speech(text: str, language: str = "en", voice: str | None = None) -> dict:
+    """
+    Synthesize a short sample and return it inline instead of keeping a file.
+
+    The audio is rendered to a temporary WAV that is removed before returning,
+    so voice auditions leave nothing behind.
+
+    Returns:
+        Dictionary with 'success', 'mime_type', base64 'audio' and optional 'error' keys
+    """
+    handle, temp_path = tempfile.mkstemp(prefix="scriptwriter-preview-", suffix=".wav")
+    os.close(handle)
+    try:
+        result = synthesize_to_file(text, temp_path, language=language, voice=voice)
+        if not result.get("success"):
+            return result
+        audio = Path(temp_path).read_bytes()
+        if not audio:
+            return {"success": False, "error": "TTS engine produced no audio"}
+        return {
+            "success": True,
+            "engine": result.get("engine"),
+            "mime_type": "audio/wav",
+            "audio": base64.b64encode(audio).decode("ascii"),
+        }
+    finally:
+        Path(temp_path).unlink(missing_ok=True)
+
+
 def speak_text(text: str, language: str = "en") -> dict:
     """
     Speak text immediately.
@@ -162,3 +230,79 @@ def synthesise(
             })
     
     return results
+
+
+# Built-in XTTS studio speakers; every one of them speaks all XTTS languages.
+XTTS_SPEAKERS: list[tuple[str, str]] = [
+    ("Claribel Dervla", "female"),
+    ("Daisy Studious", "female"),
+    ("Gracie Wise", "female"),
+    ("Ana Florence", "female"),
+    ("Andrew Chipper", "male"),
+    ("Craig Gutsy", "male"),
+    ("Damien Black", "male"),
+    ("Viktor Eka", "male"),
+]
+
+_ESPEAK_GENDERS = {"M": "male", "F": "female"}
+
+
+def _espeak_voices() -> list[dict]:
+    """Parse ``espeak-ng --voices`` output, keeping Tamil and English voices."""
+    binary = shutil.which("espeak-ng") or shutil.which("espeak")
+    if binary is None:
+        return [{
+            "id": "ta",
+            "name": "Tamil",
+            "language": "ta",
+            "gender": None,
+            "sample_available": False,
+        }]
+
+    output = subprocess.run([binary, "--voices"], capture_output=True, text=True, timeout=10).stdout
+    voices = []
+    for line in output.splitlines()[1:]:
+        # Pty Language Age/Gender VoiceName File Other Languages
+        parts = line.split()
+        if len(parts) < 5:
+            continue
+        language, age_gender, name, file = parts[1], parts[2], parts[3], parts[4]
+        if not (language.startswith("ta") or language.startswith("en")):
+            continue
+        voices.append({
+            "id": file,
+            "name": name.replace("_", " "),
+            "language": language,
+            "gender": _ESPEAK_GENDERS.get(age_gender[-1:]),
+            "sample_available": True,
+        })
+    return voices
+
+
+def list_voices(model_id: str, root: str | Path | None = None) -> dict:
+    """List the voices a TTS model offers.
+
+    ``sample_available`` tells the UI whether a preview can be played right
+    now, i.e. the model weights or synthesizer binary are installed.
+    """
+    if model_id == "coqui-xtts-dq":
+        downloaded = locate_model(model_id, root=root).exists()
+        voices = [
+            {
+                "id": name,
+                "name": name,
+                "language": "multilingual",
+                "gender": gender,
+                "sample_available": downloaded,
+            }
+            for name, gender in XTTS_SPEAKERS
+        ]
+    elif model_id == "espeak-ng-tamil":
+        try:
+            voices = _espeak_voices()
+        except (OSError, subprocess.SubprocessError) as e:
+            return {"model": model_id, "voices": [], "error": str(e)}
+    else:
+        return {"model": model_id, "voices": [], "error": f"Unknown TTS model '{model_id}'"}
+
+    return {"model": model_id, "voices": voices}
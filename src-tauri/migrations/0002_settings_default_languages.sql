@@ -0,0 +1 @@
+ALTER TABLE settings ADD COLUMN default_languages TEXT NOT NULL DEFAULT '["ta","en"]';
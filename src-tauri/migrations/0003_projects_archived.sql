@@ -0,0 +1 @@
+ALTER TABLE projects ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
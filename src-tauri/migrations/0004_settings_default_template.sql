@@ -0,0 +1 @@
+ALTER TABLE settings ADD COLUMN default_template_id TEXT NOT NULL DEFAULT 'feature-film';
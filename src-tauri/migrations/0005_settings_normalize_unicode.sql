@@ -0,0 +1 @@
+ALTER TABLE settings ADD COLUMN normalize_unicode INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+ALTER TABLE settings ADD COLUMN llm_fallback INTEGER NOT NULL DEFAULT 0;
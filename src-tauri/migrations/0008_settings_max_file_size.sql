@@ -0,0 +1 @@
+ALTER TABLE settings ADD COLUMN max_file_size_bytes INTEGER NOT NULL DEFAULT 5242880;
@@ -0,0 +1 @@
+ALTER TABLE settings ADD COLUMN active_project_id TEXT REFERENCES projects(id) ON DELETE SET NULL;
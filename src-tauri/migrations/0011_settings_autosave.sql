@@ -0,0 +1,2 @@
+ALTER TABLE settings ADD COLUMN autosave_interval_secs INTEGER NOT NULL DEFAULT 30;
+ALTER TABLE settings ADD COLUMN snapshot_retention INTEGER NOT NULL DEFAULT 20;
@@ -0,0 +1,2 @@
+ALTER TABLE settings ADD COLUMN reading_wpm_english INTEGER NOT NULL DEFAULT 200;
+ALTER TABLE settings ADD COLUMN reading_wpm_tamil INTEGER NOT NULL DEFAULT 140;
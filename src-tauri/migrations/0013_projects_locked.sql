@@ -0,0 +1 @@
+ALTER TABLE projects ADD COLUMN locked INTEGER NOT NULL DEFAULT 0;
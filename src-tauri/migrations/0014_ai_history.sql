@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS ai_history (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+  prompt TEXT NOT NULL,
+  context TEXT NOT NULL,
+  context_length INTEGER NOT NULL,
+  response TEXT NOT NULL,
+  model_id TEXT,
+  created_at TEXT DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_history_project ON ai_history(project_id);
@@ -0,0 +1 @@
+ALTER TABLE settings ADD COLUMN max_concurrent_ml_jobs INTEGER NOT NULL DEFAULT 2;
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS reminders (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  project_id TEXT REFERENCES projects(id) ON DELETE CASCADE,
+  message TEXT NOT NULL,
+  due_at TEXT NOT NULL,
+  fired_at TEXT,
+  created_at TEXT DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminders_due ON reminders(due_at) WHERE fired_at IS NULL;
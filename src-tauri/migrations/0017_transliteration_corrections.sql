@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS transliteration_corrections (
+  user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  input TEXT NOT NULL,
+  output TEXT NOT NULL,
+  updated_at TEXT DEFAULT (datetime('now')),
+  PRIMARY KEY (user_id, input)
+);
@@ -0,0 +1,536 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::error::{AppError, AppResult};
+use crate::filesystem::{sanitize_slug, write_atomic, FileKind, PROJECT_MARKER, TRASH_DIR};
+
+#[derive(Debug, Serialize)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub size: u64,
+    pub is_directory: bool,
+    pub unsafe_path: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArchiveInspection {
+    pub entries: Vec<ArchiveEntry>,
+    pub slug: Option<String>,
+    pub slug_taken: bool,
+    pub root_folder: Option<String>,
+    pub unsafe_paths: Vec<String>,
+    pub file_count: usize,
+    pub total_uncompressed_bytes: u64,
+}
+
+fn map_zip_error(err: zip::result::ZipError) -> AppError {
+    AppError::Message(format!("Unable to read archive: {err}"))
+}
+
+/// The folder every entry lives under, if the archive wraps the project in one.
+fn shared_root(names: &[&Path]) -> Option<String> {
+    let mut root: Option<&std::ffi::OsStr> = None;
+    for name in names {
+        let first = match name.components().next() {
+            Some(Component::Normal(first)) => first,
+            _ => return None,
+        };
+        if root.is_some_and(|root| root != first) {
+            return None;
+        }
+        root = Some(first);
+    }
+    let nested = names.iter().any(|name| name.components().count() > 1);
+    root.filter(|_| nested)
+        .map(|root| root.to_string_lossy().to_string())
+}
+
+/// Reads the archive's central directory without extracting anything.
+pub fn inspect_archive(path: &Path) -> AppResult<ArchiveInspection> {
+    let file = fs::File::open(path)?;
+    let mut archive = ZipArchive::new(file).map_err(map_zip_error)?;
+
+    let mut entries = Vec::with_capacity(archive.len());
+    let mut safe_names = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index).map_err(map_zip_error)?;
+        let enclosed = entry.enclosed_name().filter(|_| !entry.is_symlink());
+        if let Some(name) = &enclosed {
+            safe_names.push(name.clone());
+        }
+        entries.push(ArchiveEntry {
+            path: entry.name().to_string(),
+            size: entry.size(),
+            is_directory: entry.is_dir(),
+            unsafe_path: enclosed.is_none(),
+        });
+    }
+
+    let names: Vec<&Path> = safe_names.iter().map(|name| name.as_path()).collect();
+    let root_folder = shared_root(&names);
+    let slug = root_folder
+        .as_deref()
+        .or_else(|| path.file_stem().and_then(|stem| stem.to_str()))
+        .map(sanitize_slug)
+        .filter(|slug| !slug.is_empty());
+
+    Ok(ArchiveInspection {
+        unsafe_paths: entries
+            .iter()
+            .filter(|entry| entry.unsafe_path)
+            .map(|entry| entry.path.clone())
+            .collect(),
+        file_count: entries.iter().filter(|entry| !entry.is_directory).count(),
+        total_uncompressed_bytes: entries.iter().map(|entry| entry.size).sum(),
+        entries,
+        slug,
+        slug_taken: false,
+        root_folder,
+    })
+}
+
+/// Top-level project entries left out of exported archives.
+const ARCHIVE_EXCLUDED: &[&str] = &["exports", TRASH_DIR, PROJECT_MARKER];
+
+/// How much work an export risks losing: the partial archive is made whole
+/// again after this many source bytes or files, and a resume restarts from
+/// the last of these checkpoints.
+const CHECKPOINT_BYTES: u64 = 256 * 1024 * 1024;
+const CHECKPOINT_FILES: usize = 1000;
+
+fn map_zip_write_error(err: zip::result::ZipError) -> AppError {
+    AppError::Message(format!("Unable to write archive: {err}"))
+}
+
+/// A project file or folder as it was when it went into the archive.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct ArchivedEntry {
+    /// Relative path, with a trailing `/` for folders.
+    path: String,
+    size: u64,
+    modified: u64,
+}
+
+/// Written next to the partial archive at every checkpoint. The central
+/// directory bytes live in a sidecar so a torn write can be cut back to the
+/// checkpoint and closed again.
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveManifest {
+    checkpoint_len: u64,
+    central_directory_start: u64,
+    entries: Vec<ArchivedEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArchiveExport {
+    pub file_count: usize,
+    /// Entries carried over from an interrupted export.
+    pub resumed: usize,
+    pub total_bytes: u64,
+}
+
+struct ExportPaths {
+    part: PathBuf,
+    manifest: PathBuf,
+    central_directory: PathBuf,
+}
+
+impl ExportPaths {
+    fn new(target: &Path) -> AppResult<Self> {
+        let name = target
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| AppError::Message("Archive path has no file name".into()))?;
+        let sibling = |suffix: &str| target.with_file_name(format!(".{name}.{suffix}"));
+        Ok(Self {
+            part: sibling("part"),
+            manifest: sibling("part.json"),
+            central_directory: sibling("part.cd"),
+        })
+    }
+
+    fn discard(&self) {
+        for path in [&self.part, &self.manifest, &self.central_directory] {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn archive_entries(root: &Path) -> AppResult<Vec<(ArchivedEntry, PathBuf)>> {
+    let mut entries = Vec::new();
+    let walker = WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() != 1
+                || !ARCHIVE_EXCLUDED.contains(&entry.file_name().to_string_lossy().as_ref())
+        });
+    for entry in walker {
+        let entry = entry.map_err(|err| AppError::Anyhow(err.into()))?;
+        let file_type = entry.file_type();
+        if !file_type.is_file() && !file_type.is_dir() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .map_err(|err| AppError::Anyhow(err.into()))?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let metadata = entry
+            .metadata()
+            .map_err(|err| AppError::Anyhow(err.into()))?;
+        let archived = if file_type.is_dir() {
+            ArchivedEntry {
+                path: format!("{relative}/"),
+                size: 0,
+                modified: 0,
+            }
+        } else {
+            ArchivedEntry {
+                path: relative,
+                size: metadata.len(),
+                modified: metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |elapsed| elapsed.as_secs()),
+            }
+        };
+        entries.push((archived, entry.into_path()));
+    }
+    Ok(entries)
+}
+
+/// Brings the partial archive back to its last checkpoint, returning the
+/// manifest if every entry in it is still unchanged on disk.
+fn restore_checkpoint(
+    paths: &ExportPaths,
+    current: &[(ArchivedEntry, PathBuf)],
+) -> Option<ArchiveManifest> {
+    let manifest: ArchiveManifest =
+        serde_json::from_slice(&fs::read(&paths.manifest).ok()?).ok()?;
+    let central_directory = fs::read(&paths.central_directory).ok()?;
+    if manifest.central_directory_start + central_directory.len() as u64 != manifest.checkpoint_len
+    {
+        return None;
+    }
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&paths.part)
+        .ok()?;
+    if file.metadata().ok()?.len() != manifest.checkpoint_len || ZipArchive::new(&mut file).is_err()
+    {
+        file.set_len(manifest.central_directory_start).ok()?;
+        file.seek(SeekFrom::End(0)).ok()?;
+        file.write_all(&central_directory).ok()?;
+    }
+    let archive = ZipArchive::new(&mut file).ok()?;
+    if archive.len() != manifest.entries.len() {
+        return None;
+    }
+
+    let current: HashMap<&str, &ArchivedEntry> = current
+        .iter()
+        .map(|(entry, _)| (entry.path.as_str(), entry))
+        .collect();
+    let unchanged = manifest
+        .entries
+        .iter()
+        .all(|entry| current.get(entry.path.as_str()) == Some(&entry));
+    unchanged.then_some(manifest)
+}
+
+/// Closes the archive so the part file is whole, then records the
+/// checkpoint and reopens it for appending. Appending writes after the old
+/// central directory, so each checkpoint leaves a superseded copy of it in
+/// the file, a few dozen bytes per entry.
+fn checkpoint(
+    writer: ZipWriter<fs::File>,
+    paths: &ExportPaths,
+    entries: &[ArchivedEntry],
+) -> AppResult<ZipWriter<fs::File>> {
+    let mut file = writer.finish().map_err(map_zip_write_error)?;
+    file.sync_all()?;
+    let checkpoint_len = file.seek(SeekFrom::End(0))?;
+    let central_directory_start = ZipArchive::new(&mut file)
+        .map_err(map_zip_write_error)?
+        .central_directory_start();
+    let mut central_directory = Vec::new();
+    file.seek(SeekFrom::Start(central_directory_start))?;
+    file.read_to_end(&mut central_directory)?;
+
+    write_atomic(&paths.central_directory, &central_directory)?;
+    let manifest = ArchiveManifest {
+        checkpoint_len,
+        central_directory_start,
+        entries: entries.to_vec(),
+    };
+    write_atomic(&paths.manifest, &serde_json::to_vec(&manifest)?)?;
+
+    ZipWriter::new_append(file).map_err(map_zip_write_error)
+}
+
+/// Zips the project at `root` into `target` under a `{slug}/` folder, the
+/// layout [`inspect_archive`] expects. The archive is built in a hidden
+/// `.part` file beside the target and renamed over it once complete, so the
+/// target only ever holds a whole archive. With `resume`, entries saved by
+/// an interrupted export are kept as long as none of them changed since.
+///
+/// `on_progress` gets the bytes archived so far and the total; an error from
+/// it stops the export and leaves the partial archive for a later resume.
+pub fn export_archive(
+    root: &Path,
+    slug: &str,
+    target: &Path,
+    resume: bool,
+    on_progress: impl FnMut(u64, u64) -> AppResult<()>,
+) -> AppResult<ArchiveExport> {
+    write_archive(
+        root,
+        slug,
+        target,
+        resume,
+        (CHECKPOINT_BYTES, CHECKPOINT_FILES),
+        on_progress,
+    )
+}
+
+fn write_archive(
+    root: &Path,
+    slug: &str,
+    target: &Path,
+    resume: bool,
+    (checkpoint_bytes, checkpoint_files): (u64, usize),
+    mut on_progress: impl FnMut(u64, u64) -> AppResult<()>,
+) -> AppResult<ArchiveExport> {
+    let paths = ExportPaths::new(target)?;
+    let entries = archive_entries(root)?;
+    let total_bytes = entries.iter().map(|(entry, _)| entry.size).sum();
+
+    let restored = if resume {
+        restore_checkpoint(&paths, &entries)
+    } else {
+        None
+    };
+    let (mut writer, mut archived) = match restored {
+        Some(manifest) => {
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&paths.part)?;
+            let writer = ZipWriter::new_append(file).map_err(map_zip_write_error)?;
+            (writer, manifest.entries)
+        }
+        None => {
+            paths.discard();
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // Read access too: checkpoints read the central directory back.
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&paths.part)?;
+            (ZipWriter::new(file), Vec::new())
+        }
+    };
+    let resumed = archived.len();
+    let done: HashSet<String> = archived.iter().map(|entry| entry.path.clone()).collect();
+    let mut bytes_done: u64 = archived.iter().map(|entry| entry.size).sum();
+    let (mut pending_bytes, mut pending_files) = (0, 0);
+
+    for (entry, path) in &entries {
+        if done.contains(&entry.path) {
+            continue;
+        }
+        let name = format!("{slug}/{}", entry.path);
+        if entry.path.ends_with('/') {
+            writer
+                .add_directory(name, SimpleFileOptions::default())
+                .map_err(map_zip_write_error)?;
+        } else {
+            let compression = match FileKind::from_path(path) {
+                FileKind::Image | FileKind::Audio => CompressionMethod::Stored,
+                _ => CompressionMethod::Deflated,
+            };
+            let options = SimpleFileOptions::default()
+                .compression_method(compression)
+                .large_file(entry.size >= u64::from(u32::MAX));
+            writer
+                .start_file(name, options)
+                .map_err(map_zip_write_error)?;
+            io::copy(&mut fs::File::open(path)?, &mut writer)?;
+        }
+        archived.push(entry.clone());
+        bytes_done += entry.size;
+        pending_bytes += entry.size;
+        pending_files += 1;
+
+        if pending_bytes >= checkpoint_bytes || pending_files >= checkpoint_files {
+            writer = checkpoint(writer, &paths, &archived)?;
+            (pending_bytes, pending_files) = (0, 0);
+        }
+        on_progress(bytes_done, total_bytes)?;
+    }
+
+    let file = writer.finish().map_err(map_zip_write_error)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&paths.part, target)?;
+    paths.discard();
+
+    Ok(ArchiveExport {
+        file_count: archived
+            .iter()
+            .filter(|entry| !entry.path.ends_with('/'))
+            .count(),
+        resumed,
+        total_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn sample_project() -> PathBuf {
+        let root = std::env::temp_dir().join(format!("scriptwriter-test-{}", Uuid::new_v4()));
+        for (path, content) in [
+            ("drafts/scene1.md", "# Scene 1\n"),
+            ("drafts/scene2.md", "# Scene 2\n"),
+            ("notes/ideas.md", "Ideas\n"),
+            ("research/links.md", "https://example.com\n"),
+            ("exports/old.docx", "stale"),
+            (".trash/gone.md", "gone"),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        fs::create_dir_all(root.join("audio")).unwrap();
+        root
+    }
+
+    fn archived_names(path: &Path) -> Vec<String> {
+        let mut names: Vec<String> = inspect_archive(path)
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn export_archive_wraps_the_project_in_its_slug() {
+        let root = sample_project();
+        let target = root.join("exports/film.zip");
+        let export = export_archive(&root, "film", &target, false, |_, _| Ok(())).unwrap();
+        assert_eq!(export.file_count, 4);
+        assert_eq!(export.resumed, 0);
+
+        let inspection = inspect_archive(&target).unwrap();
+        assert_eq!(inspection.slug.as_deref(), Some("film"));
+        assert_eq!(
+            archived_names(&target),
+            vec![
+                "film/audio/",
+                "film/drafts/",
+                "film/drafts/scene1.md",
+                "film/drafts/scene2.md",
+                "film/notes/",
+                "film/notes/ideas.md",
+                "film/research/",
+                "film/research/links.md",
+            ]
+        );
+        assert!(!root.join("exports/.film.zip.part").exists());
+        assert!(!root.join("exports/.film.zip.part.json").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn interrupted_export_resumes_from_its_last_checkpoint() {
+        let root = sample_project();
+        let target = root.join("exports/film.zip");
+        let part = root.join("exports/.film.zip.part");
+
+        let mut calls = 0;
+        let stopped = write_archive(&root, "film", &target, false, (u64::MAX, 3), |_, _| {
+            calls += 1;
+            if calls == 5 {
+                Err(AppError::Message("cancelled".into()))
+            } else {
+                Ok(())
+            }
+        });
+        assert!(stopped.is_err());
+        assert!(!target.exists());
+        // A torn write past the checkpoint is cut away on resume.
+        OpenOptions::new()
+            .append(true)
+            .open(&part)
+            .unwrap()
+            .write_all(b"PK\x03\x04torn")
+            .unwrap();
+
+        let export =
+            write_archive(&root, "film", &target, true, (u64::MAX, 3), |_, _| Ok(())).unwrap();
+        assert_eq!(export.resumed, 3);
+        assert_eq!(export.file_count, 4);
+        assert_eq!(archived_names(&target).len(), 8);
+        let mut archive = ZipArchive::new(fs::File::open(&target).unwrap()).unwrap();
+        let mut scene = String::new();
+        archive
+            .by_name("film/drafts/scene2.md")
+            .unwrap()
+            .read_to_string(&mut scene)
+            .unwrap();
+        assert_eq!(scene, "# Scene 2\n");
+        assert!(!part.exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn changed_files_restart_an_interrupted_export() {
+        let root = sample_project();
+        let target = root.join("exports/film.zip");
+
+        // Entries go in name order, so the checkpoint after three of them
+        // covers audio/, drafts/ and drafts/scene1.md.
+        let stopped = write_archive(&root, "film", &target, false, (u64::MAX, 3), |done, _| {
+            if done > 0 {
+                Err(AppError::Message("cancelled".into()))
+            } else {
+                Ok(())
+            }
+        });
+        assert!(stopped.is_err());
+        fs::write(root.join("drafts/scene1.md"), "# Scene 1, revised\n").unwrap();
+
+        let export =
+            write_archive(&root, "film", &target, true, (u64::MAX, 3), |_, _| Ok(())).unwrap();
+        assert_eq!(export.resumed, 0);
+        assert_eq!(export.file_count, 4);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -2,32 +2,110 @@ use sanitize_filename::sanitize;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sqlx::Row;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
+use unicode_normalization::UnicodeNormalization;
 use uuid::Uuid;
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::{Component, Path, PathBuf};
 
+use crate::archive::{export_archive, inspect_archive, ArchiveInspection};
 use crate::auth::{hash_password, verify_password};
 use crate::error::{AppError, AppResult};
+use crate::export::{
+    breakdown_csv, collect_references, markdown_to_docx, playlist_m3u, references_bibtex,
+    references_markdown, table_read_playlist, Reference, ReferenceSource, RESEARCH_DIR, SITE_DIR,
+    TABLE_READS_DIR,
+};
 use crate::filesystem::{
-    assert_slug_unique, ensure_projects_root, ensure_template_scaffold, project_path,
-    sanitize_slug, write_markdown_placeholder,
+    assert_slug_unique, copy_dir_all, draft_markdown_files, ensure_free_space,
+    ensure_projects_root, ensure_template_scaffold, ensure_writable, file_snapshots, glob_match,
+    is_external_target, list_trash, measure_disk_usage, move_to_trash, project_markdown_files,
+    project_path, read_text_chunk, recent_markdown_files, reference_candidates, remove_project_dir,
+    rename_conflicts, rename_entry, restore_from_trash, sanitize_slug, scene_versions,
+    snapshot_file, trashed_original_path, verify_copy, verify_project_layout, write_atomic,
+    write_markdown_placeholder, write_project_marker, DiskUsage, FileChecksum, FileKind,
+    OrphanedAsset, ProjectIssue, SceneVersion, TrashedFile, SCRATCH_DIR, TRASH_DIR,
+};
+use crate::jobs::JobRecord;
+use crate::language::{validate_language_codes, LanguageDetection, SUPPORTED_LANGUAGES};
+use crate::markdown::{
+    build_outline, compare_headings, extract_headings, front_matter_value, link_references,
+    render_html, scene_breakdown, set_front_matter_value, strip_front_matter, CharacterEntry,
+    ColoredScript, HeadingComparison, OutlineNode, ReadingTime, RuntimeEstimate,
+    DEFAULT_RUNTIME_FORMAT, RUNTIME_FORMATS,
+};
+use crate::ml_bridge::{
+    DraftOutline, InlineCompletion, InputSuggestion, LlmCredentialCheck, LlmEstimate,
+    ModelBenchmark, OutlineBeat, PhoneticHint, SceneSummary, SpeechPreview, TransliterationStatus,
+    TtsVoice, DEFAULT_TRANSLITERATION_MODE, TRANSLITERATION_MODES,
 };
 use crate::models::{
-    default_models, default_templates, ProjectRecord, ProjectRow, ProjectTemplate, SettingsPayload,
-    SettingsRow, UserProfile, UserRow,
+    default_models, default_templates, merge_api_keys, merge_model_inventory, resolve_api_key,
+    AiHistoryEntry, AuditEntry, ModelAvailability, ModelType, Persona, ProjectRecord, ProjectRow,
+    ProjectTemplate, Reminder, SettingsPayload, SettingsRow, TransliterationCorrection,
+    UserProfile, UserRow, AUTOSAVE_INTERVAL_RANGE, CUSTOM_MODEL_PREFIX, MIN_MAX_FILE_SIZE_BYTES,
+    ML_JOB_LIMIT_RANGE, READING_WPM_RANGE, SNAPSHOT_RETENTION_RANGE,
+};
+use crate::screenplay::{
+    fountain_to_markdown, markdown_to_fountain, structure_plaintext, FountainExport,
+    FountainImport, FountainReport, StructureReport, StructuredScript, UnmappedElement,
 };
-use crate::state::AppState;
+use crate::state::{AppState, DATABASE_FILE, MIGRATOR};
+use crate::subtitles::{
+    parse_subtitles, subtitles_to_markdown, transcript_to_markdown, SubtitleFormat, TimestampStyle,
+};
+use crate::transform::{Transformation, TRANSFORMATIONS};
+
+const OPENROUTER_KEY: &str = "openrouter_key";
+const MAX_SCENE_VARIATIONS: u8 = 5;
+const README_PLACEHOLDER: &str = "Start drafting your story here. Use the bilingual editor to keep Tamil and English drafts in sync.";
 
 async fn require_session(state: &AppState) -> AppResult<UserProfile> {
     state.current_user().await.ok_or(AppError::Unauthorized)
 }
 
+/// Appends a mutating command to the audit log when the setting is on.
+/// `detail` must be metadata such as a relative path, never file contents or
+/// keys. Failures are logged so auditing can never block the command itself.
+async fn record_audit(
+    state: &AppState,
+    user: &UserProfile,
+    command: &str,
+    project_id: Option<&str>,
+    detail: Option<&str>,
+) {
+    let result = async {
+        let enabled: bool =
+            sqlx::query_scalar("SELECT audit_log_enabled FROM settings WHERE id = 1")
+                .fetch_optional(&state.pool)
+                .await?
+                .unwrap_or(false);
+        if enabled {
+            sqlx::query(
+                "INSERT INTO audit_log (user_id, project_id, command, detail) VALUES (?1, ?2, ?3, ?4)",
+            )
+            .bind(&user.id)
+            .bind(project_id)
+            .bind(command)
+            .bind(detail)
+            .execute(&state.pool)
+            .await?;
+        }
+        Ok::<_, sqlx::Error>(())
+    }
+    .await;
+    if let Err(err) = result {
+        log::warn!("Failed to record audit entry for {command}: {err}");
+    }
+}
+
 async fn fetch_project_row(state: &AppState, project_id: &str) -> AppResult<ProjectRow> {
     let row = sqlx::query_as::<_, ProjectRow>(
         r#"
-        SELECT id, name, slug, project_type, languages, description, template_id, base_path, created_at, updated_at
+        SELECT id, name, slug, project_type, languages, description, template_id, base_path, archived, locked, cover_path, created_at, updated_at
         FROM projects
         WHERE id = ?1
     "#,
@@ -39,6 +117,70 @@ async fn fetch_project_row(state: &AppState, project_id: &str) -> AppResult<Proj
     row.ok_or_else(|| AppError::Message("Project not found".into()))
 }
 
+/// Commands that change a project's files call this first; reading a locked
+/// project is still allowed.
+fn ensure_unlocked(project_row: &ProjectRow) -> AppResult<()> {
+    if project_row.locked {
+        return Err(AppError::Message("Project is locked".into()));
+    }
+    Ok(())
+}
+
+async fn fetch_settings(state: &AppState) -> AppResult<SettingsPayload> {
+    let query = r#"
+      SELECT preferred_theme, transliteration_mode, stt_model, tts_model, llm_model, api_keys, default_languages, default_template_id, normalize_unicode, llm_fallback, max_file_size_bytes, audit_log_enabled, autosave_interval_secs, snapshot_retention, reading_wpm_english, reading_wpm_tamil, max_concurrent_ml_jobs
+      FROM settings
+      WHERE id = 1
+    "#;
+    let settings_row = match sqlx::query_as::<_, SettingsRow>(query)
+        .fetch_optional(&state.pool)
+        .await?
+    {
+        Some(row) => row,
+        None => {
+            log::warn!("Settings row is missing; restoring defaults");
+            ensure_settings_row(state).await?;
+            sqlx::query_as::<_, SettingsRow>(query)
+                .fetch_one(&state.pool)
+                .await?
+        }
+    };
+
+    Ok(SettingsPayload::try_from(settings_row)?)
+}
+
+/// Seeds the single settings row from the column defaults, as the initial
+/// migration does, if it has gone missing.
+async fn ensure_settings_row(state: &AppState) -> AppResult<()> {
+    sqlx::query("INSERT INTO settings (id) VALUES (1) ON CONFLICT(id) DO NOTHING")
+        .execute(&state.pool)
+        .await?;
+    Ok(())
+}
+
+async fn load_project_file(
+    state: &AppState,
+    project_id: &str,
+    file_path: &str,
+) -> AppResult<String> {
+    let project_row = fetch_project_row(state, project_id).await?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let target_path = resolve_project_path(&base_path, file_path)?;
+    if matches!(
+        FileKind::from_path(&target_path),
+        FileKind::Image | FileKind::Audio
+    ) {
+        return Err(AppError::Message(format!(
+            "{file_path} is not a text file and cannot be opened in the editor"
+        )));
+    }
+    if target_path.exists() {
+        Ok(fs::read_to_string(&target_path)?)
+    } else {
+        Ok(String::new())
+    }
+}
+
 fn resolve_project_path(base: &Path, relative: &str) -> AppResult<PathBuf> {
     let trimmed = relative.trim();
     if trimmed.is_empty() {
@@ -76,15 +218,45 @@ pub struct ProjectFileEntry {
     #[serde(rename = "is_directory")]
     pub is_directory: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<FileKind>,
+    /// The front matter `language` tag of a markdown file, listed with kinds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub children: Option<Vec<ProjectFileEntry>>,
 }
 
 const MAX_TREE_DEPTH: usize = 8;
+const MAX_FRONT_MATTER_LINES: usize = 50;
+
+/// The `language` front matter tag, read without loading the whole file.
+fn file_language(path: &Path) -> Option<String> {
+    let file = fs::File::open(path).ok()?;
+    let mut head = String::new();
+    for (index, line) in BufReader::new(file)
+        .lines()
+        .take(MAX_FRONT_MATTER_LINES)
+        .enumerate()
+    {
+        let line = line.ok()?;
+        let delimiter = matches!(line.trim_end(), "---" | "...");
+        if index == 0 && !delimiter {
+            return None;
+        }
+        head.push_str(&line);
+        head.push('\n');
+        if index > 0 && delimiter {
+            break;
+        }
+    }
+    front_matter_value(&head, "language")
+}
 
 fn build_directory_entries(
     base: &Path,
     dir: &Path,
     depth: usize,
+    include_kinds: bool,
 ) -> AppResult<Vec<ProjectFileEntry>> {
     if depth > MAX_TREE_DEPTH {
         return Ok(Vec::new());
@@ -108,7 +280,7 @@ fn build_directory_entries(
         let relative = to_relative_string(base, &path)?;
 
         let children = if is_dir {
-            let nested = build_directory_entries(base, &path, depth + 1)?;
+            let nested = build_directory_entries(base, &path, depth + 1, include_kinds)?;
             if nested.is_empty() {
                 None
             } else {
@@ -118,10 +290,17 @@ fn build_directory_entries(
             None
         };
 
+        let kind = (include_kinds && !is_dir).then(|| FileKind::from_path(&path));
+        let language = matches!(kind, Some(FileKind::Markdown))
+            .then(|| file_language(&path))
+            .flatten();
+
         entries.push(ProjectFileEntry {
             name,
             path: relative,
             is_directory: is_dir,
+            kind,
+            language,
             children,
         });
     }
@@ -142,6 +321,7 @@ pub struct BootstrapPayload {
     pub model_options: serde_json::Value,
     pub settings: SettingsPayload,
     pub projects: Vec<ProjectRecord>,
+    pub active_project: Option<ProjectRecord>,
 }
 
 #[tauri::command]
@@ -154,22 +334,13 @@ async fn bootstrap_inner(state: State<'_, AppState>) -> AppResult<BootstrapPaylo
     let templates = default_templates();
     let models = default_models();
 
-    let settings_row = sqlx::query_as::<_, SettingsRow>(
-        r#"
-      SELECT preferred_theme, transliteration_mode, stt_model, tts_model, llm_model, api_keys
-      FROM settings
-      WHERE id = 1
-    "#,
-    )
-    .fetch_one(&state.pool)
-    .await?;
-
-    let settings = SettingsPayload::try_from(settings_row)?;
+    let settings = fetch_settings(&state).await?.redacted();
 
     let rows = sqlx::query_as::<_, ProjectRow>(
     r#"
-      SELECT id, name, slug, project_type, languages, description, template_id, base_path, created_at, updated_at
+      SELECT id, name, slug, project_type, languages, description, template_id, base_path, archived, locked, cover_path, created_at, updated_at
       FROM projects
+      WHERE archived = 0
       ORDER BY datetime(updated_at) DESC
     "#,
   )
@@ -202,9 +373,95 @@ async fn bootstrap_inner(state: State<'_, AppState>) -> AppResult<BootstrapPaylo
         }),
         settings,
         projects: project_records,
+        active_project: fetch_active_project(&state).await?,
     })
 }
 
+/// The project the UI last had open. An id left behind by a deleted project
+/// is cleared instead of returned.
+async fn fetch_active_project(state: &AppState) -> AppResult<Option<ProjectRecord>> {
+    let row = sqlx::query_as::<_, ProjectRow>(
+        r#"
+        SELECT projects.id, projects.name, projects.slug, projects.project_type, projects.languages, projects.description, projects.template_id, projects.base_path, projects.archived, projects.locked, projects.cover_path, projects.created_at, projects.updated_at
+        FROM settings
+        JOIN projects ON projects.id = settings.active_project_id
+        WHERE settings.id = 1
+    "#,
+    )
+    .fetch_optional(&state.pool)
+    .await?;
+
+    match row {
+        Some(row) => Ok(Some(ProjectRecord::try_from(row)?)),
+        None => {
+            sqlx::query(
+                "UPDATE settings SET active_project_id = NULL WHERE id = 1 AND active_project_id IS NOT NULL",
+            )
+            .execute(&state.pool)
+            .await?;
+            Ok(None)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetActiveProjectRequest {
+    /// `None` closes the current project.
+    pub project_id: Option<String>,
+}
+
+#[tauri::command]
+pub async fn set_active_project(
+    state: State<'_, AppState>,
+    payload: SetActiveProjectRequest,
+) -> Result<Option<ProjectRecord>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let project = match payload.project_id.as_deref() {
+            Some(project_id) => Some(ProjectRecord::try_from(
+                fetch_project_row(&state, project_id).await?,
+            )?),
+            None => None,
+        };
+
+        ensure_settings_row(&state).await?;
+        sqlx::query("UPDATE settings SET active_project_id = ?1 WHERE id = 1")
+            .bind(&payload.project_id)
+            .execute(&state.pool)
+            .await?;
+        Ok::<_, AppError>(project)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn list_project_templates(
+    state: State<'_, AppState>,
+) -> Result<Vec<ProjectTemplate>, String> {
+    if let Err(err) = require_session(&state).await {
+        return Err(err.to_string());
+    }
+    Ok(default_templates())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetTemplateRequest {
+    pub template_id: String,
+}
+
+#[tauri::command]
+pub async fn get_template(
+    state: State<'_, AppState>,
+    payload: GetTemplateRequest,
+) -> Result<ProjectTemplate, String> {
+    if let Err(err) = require_session(&state).await {
+        return Err(err.to_string());
+    }
+    project_template(Some(&payload.template_id))
+        .ok_or_else(|| AppError::Message("Template not found".into()).to_string())
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateProjectRequest {
     pub name: String,
@@ -224,10 +481,8 @@ pub async fn create_project(
     app: AppHandle,
     state: State<'_, AppState>,
     payload: CreateProjectRequest,
-) -> Result<CreateProjectResponse, String> {
-    create_project_inner(app, state, payload)
-        .await
-        .map_err(|err| err.to_string())
+) -> Result<CreateProjectResponse, AppError> {
+    create_project_inner(app, state, payload).await
 }
 
 async fn create_project_inner(
@@ -235,7 +490,11 @@ async fn create_project_inner(
     state: State<'_, AppState>,
     payload: CreateProjectRequest,
 ) -> AppResult<CreateProjectResponse> {
-    let _user = require_session(&state).await?;
+    let user = require_session(&state).await?;
+    // Resolve the template before any slug or filesystem work so a stale id
+    // fails fast without side effects.
+    let template = project_template(Some(&payload.template_id))
+        .ok_or_else(|| AppError::Message("Template not found".into()))?;
     let slug = sanitize_slug(&payload.name);
 
     let existing_slugs = sqlx::query("SELECT slug FROM projects")
@@ -245,33 +504,66 @@ async fn create_project_inner(
         .filter_map(|row| row.try_get::<String, _>("slug").ok())
         .collect::<Vec<_>>();
 
-    assert_slug_unique(&existing_slugs, &slug)?;
+    let mut errors = BTreeMap::new();
 
-    let template = default_templates()
-        .into_iter()
-        .find(|t| t.id == payload.template_id)
-        .ok_or_else(|| AppError::Message("Template not found".into()))?;
+    if payload.name.trim().is_empty() {
+        errors.insert("name", "Project name is required".to_string());
+    } else if slug.is_empty() {
+        errors.insert(
+            "name",
+            "Project name needs at least one letter or digit".to_string(),
+        );
+    } else if let Err(err) = assert_slug_unique(&existing_slugs, &slug) {
+        errors.insert("name", err.to_string());
+    }
+
+    if project_template(Some(&payload.project_type)).is_none() {
+        errors.insert(
+            "project_type",
+            format!("Unknown project type '{}'", payload.project_type),
+        );
+    }
+
+    let languages = if payload.languages.is_empty() {
+        let defaults = fetch_settings(&state).await?.default_languages;
+        if defaults.is_empty() {
+            errors.insert("languages", "Select at least one language".to_string());
+        }
+        defaults
+    } else {
+        match validate_language_codes(&payload.languages) {
+            Ok(languages) => languages,
+            Err(err) => {
+                errors.insert("languages", err.to_string());
+                Vec::new()
+            }
+        }
+    };
+
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
 
     let project_dir = project_path(&state.storage_root, &slug);
     ensure_projects_root(&state.storage_root)?;
     tokio::task::spawn_blocking({
-    let template = template.clone();
-    let project_dir = project_dir.clone();
-    move || -> AppResult<()> {
-      ensure_template_scaffold(&project_dir, &template)?;
-      write_markdown_placeholder(
-        &project_dir.join("README.md"),
-        &template.title,
-        "Start drafting your story here. Use the bilingual editor to keep Tamil and English drafts in sync.",
-      )?;
-      Ok(())
-    }
-  })
-  .await
-  .map_err(|err| AppError::Anyhow(err.into()))??;
+        let template = template.clone();
+        let project_dir = project_dir.clone();
+        move || -> AppResult<()> {
+            ensure_template_scaffold(&project_dir, &template)?;
+            write_markdown_placeholder(
+                &project_dir.join("README.md"),
+                &template.title,
+                README_PLACEHOLDER,
+            )?;
+            Ok(())
+        }
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
 
     let new_id = crate::models::ProjectRecord::new_id();
-    let languages_json = serde_json::to_string(&payload.languages)?;
+    let languages_json = serde_json::to_string(&languages)?;
 
     sqlx::query(
     r#"
@@ -292,7 +584,7 @@ async fn create_project_inner(
 
     let inserted_row = sqlx::query_as::<_, ProjectRow>(
     r#"
-      SELECT id, name, slug, project_type, languages, description, template_id, base_path, created_at, updated_at
+      SELECT id, name, slug, project_type, languages, description, template_id, base_path, archived, locked, cover_path, created_at, updated_at
       FROM projects
       WHERE id = ?1
     "#,
@@ -302,6 +594,14 @@ async fn create_project_inner(
   .await?;
 
     let project = ProjectRecord::try_from(inserted_row)?;
+    record_audit(
+        &state,
+        &user,
+        "create_project",
+        Some(&project.id),
+        Some(&slug),
+    )
+    .await;
 
     app.emit("project-created", &project)
         .map_err(|err| AppError::Anyhow(err.into()))?;
@@ -309,14 +609,55 @@ async fn create_project_inner(
     Ok(CreateProjectResponse { project })
 }
 
+#[derive(Debug, Deserialize)]
+pub struct QuickCreateProjectRequest {
+    pub name: String,
+}
+
+#[tauri::command]
+pub async fn quick_create_project(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: QuickCreateProjectRequest,
+) -> Result<ProjectRecord, AppError> {
+    let _user = require_session(&state).await?;
+    let settings = fetch_settings(&state).await?;
+    let template = project_template(Some(&settings.default_template_id))
+        .or_else(|| default_templates().into_iter().next())
+        .ok_or_else(|| AppError::Message("No project templates available".into()))?;
+
+    let request = CreateProjectRequest {
+        name: payload.name.trim().to_string(),
+        project_type: template.id.to_string(),
+        languages: settings.default_languages,
+        template_id: template.id.to_string(),
+        description: None,
+    };
+    let response = create_project_inner(app, state, request).await?;
+    Ok(response.project)
+}
+
+/// Every field is optional; omitted ones keep their stored value, so panels
+/// holding stale copies of other settings cannot overwrite them.
 #[derive(Debug, Deserialize)]
 pub struct UpdateSettingsRequest {
-    pub preferred_theme: String,
-    pub transliteration_mode: String,
-    pub stt_model: String,
-    pub tts_model: String,
-    pub llm_model: String,
-    pub api_keys: Value,
+    pub preferred_theme: Option<String>,
+    pub transliteration_mode: Option<String>,
+    pub stt_model: Option<String>,
+    pub tts_model: Option<String>,
+    pub llm_model: Option<String>,
+    pub api_keys: Option<Value>,
+    pub default_languages: Option<Vec<String>>,
+    pub default_template_id: Option<String>,
+    pub normalize_unicode: Option<bool>,
+    pub llm_fallback: Option<bool>,
+    pub max_file_size_bytes: Option<u64>,
+    pub audit_log_enabled: Option<bool>,
+    pub autosave_interval_secs: Option<u32>,
+    pub snapshot_retention: Option<u32>,
+    pub reading_wpm_english: Option<u32>,
+    pub reading_wpm_tamil: Option<u32>,
+    pub max_concurrent_ml_jobs: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -338,364 +679,6660 @@ async fn update_settings_inner(
     state: State<'_, AppState>,
     payload: UpdateSettingsRequest,
 ) -> AppResult<UpdateSettingsResponse> {
-    let _user = require_session(&state).await?;
+    let user = require_session(&state).await?;
+    let preferred_theme = match &payload.preferred_theme {
+        Some(theme) => {
+            let normalized = theme.trim().to_lowercase();
+            if !THEMES.contains(&normalized.as_str()) {
+                return Err(AppError::Message(format!(
+                    "Unknown theme '{theme}'; expected one of: {}",
+                    THEMES.join(", ")
+                )));
+            }
+            Some(normalized)
+        }
+        None => None,
+    };
+    let default_languages = payload
+        .default_languages
+        .as_deref()
+        .map(validate_language_codes)
+        .transpose()?
+        .map(|languages| serde_json::to_string(&languages))
+        .transpose()?;
+    if let Some(template_id) = &payload.default_template_id {
+        if project_template(Some(template_id)).is_none() {
+            return Err(AppError::Message(format!(
+                "Unknown template '{template_id}'"
+            )));
+        }
+    }
+    let model_ids = [
+        ("stt_model", &payload.stt_model, ModelType::SpeechToText),
+        ("tts_model", &payload.tts_model, ModelType::TextToSpeech),
+        ("llm_model", &payload.llm_model, ModelType::LanguageModel),
+    ]
+    .into_iter()
+    .filter_map(|(field, value, model_type)| Some((field, value.as_deref()?, model_type)))
+    .collect::<Vec<_>>();
+    if let Some((_, message)) = invalid_model_ids(&state, &model_ids).await.pop_first() {
+        return Err(AppError::Message(message));
+    }
+    let max_file_size_bytes = payload
+        .max_file_size_bytes
+        .map(validate_max_file_size)
+        .transpose()?;
+    let autosave_interval_secs = payload
+        .autosave_interval_secs
+        .map(validate_autosave_interval)
+        .transpose()?;
+    let snapshot_retention = payload
+        .snapshot_retention
+        .map(validate_snapshot_retention)
+        .transpose()?;
+    let reading_wpm_english = payload
+        .reading_wpm_english
+        .map(validate_reading_wpm)
+        .transpose()?;
+    let reading_wpm_tamil = payload
+        .reading_wpm_tamil
+        .map(validate_reading_wpm)
+        .transpose()?;
+    let max_concurrent_ml_jobs = payload
+        .max_concurrent_ml_jobs
+        .map(validate_ml_job_limit)
+        .transpose()?;
+    let api_keys = match payload.api_keys {
+        Some(incoming) => {
+            let stored_keys = fetch_settings(&state).await?.api_keys;
+            Some(merge_api_keys(&stored_keys, incoming).to_string())
+        }
+        None => None,
+    };
+
+    ensure_settings_row(&state).await?;
     sqlx::query(
         r#"
       UPDATE settings
-      SET preferred_theme = ?1,
-          transliteration_mode = ?2,
-          stt_model = ?3,
-          tts_model = ?4,
-          llm_model = ?5,
-          api_keys = ?6,
+      SET preferred_theme = COALESCE(?1, preferred_theme),
+          transliteration_mode = COALESCE(?2, transliteration_mode),
+          stt_model = COALESCE(?3, stt_model),
+          tts_model = COALESCE(?4, tts_model),
+          llm_model = COALESCE(?5, llm_model),
+          api_keys = COALESCE(?6, api_keys),
+          default_languages = COALESCE(?7, default_languages),
+          default_template_id = COALESCE(?8, default_template_id),
+          normalize_unicode = COALESCE(?9, normalize_unicode),
+          llm_fallback = COALESCE(?10, llm_fallback),
+          max_file_size_bytes = COALESCE(?11, max_file_size_bytes),
+          audit_log_enabled = COALESCE(?12, audit_log_enabled),
+          autosave_interval_secs = COALESCE(?13, autosave_interval_secs),
+          snapshot_retention = COALESCE(?14, snapshot_retention),
+          reading_wpm_english = COALESCE(?15, reading_wpm_english),
+          reading_wpm_tamil = COALESCE(?16, reading_wpm_tamil),
+          max_concurrent_ml_jobs = COALESCE(?17, max_concurrent_ml_jobs),
           id = 1
       WHERE id = 1
     "#,
     )
-    .bind(&payload.preferred_theme)
+    .bind(preferred_theme)
     .bind(&payload.transliteration_mode)
     .bind(&payload.stt_model)
     .bind(&payload.tts_model)
     .bind(&payload.llm_model)
-    .bind(payload.api_keys.to_string())
+    .bind(api_keys)
+    .bind(default_languages)
+    .bind(&payload.default_template_id)
+    .bind(payload.normalize_unicode)
+    .bind(payload.llm_fallback)
+    .bind(max_file_size_bytes)
+    .bind(payload.audit_log_enabled)
+    .bind(autosave_interval_secs)
+    .bind(snapshot_retention)
+    .bind(reading_wpm_english)
+    .bind(reading_wpm_tamil)
+    .bind(max_concurrent_ml_jobs)
     .execute(&state.pool)
     .await?;
+    if let Some(limit) = max_concurrent_ml_jobs {
+        state.set_ml_job_limit(limit).await;
+    }
+    record_audit(&state, &user, "update_settings", None, None).await;
 
-    let settings_row = sqlx::query_as::<_, SettingsRow>(
-        r#"
-      SELECT preferred_theme, transliteration_mode, stt_model, tts_model, llm_model, api_keys
-      FROM settings
-      WHERE id = 1
-    "#,
-    )
-    .fetch_one(&state.pool)
-    .await?;
-
-    let settings = SettingsPayload::try_from(settings_row)?;
+    let settings = fetch_settings(&state).await?.redacted();
 
     Ok(UpdateSettingsResponse { settings })
 }
 
 #[derive(Debug, Deserialize)]
-pub struct TransliterationRequest {
-    pub text: String,
+pub struct SetThemeRequest {
+    pub theme: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct TransliterationResponse {
-    pub candidates: Vec<String>,
+pub struct SetThemeResponse {
+    pub preferred_theme: String,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ListProjectFilesRequest {
-    pub project_id: String,
+/// Updates only `preferred_theme`, so flipping dark mode never resends (and
+/// possibly clobbers) the rest of the settings.
+#[tauri::command]
+pub async fn set_theme(
+    state: State<'_, AppState>,
+    payload: SetThemeRequest,
+) -> Result<SetThemeResponse, String> {
+    let result = async {
+        let user = require_session(&state).await?;
+        let theme = payload.theme.trim().to_lowercase();
+        if !THEMES.contains(&theme.as_str()) {
+            return Err(AppError::Message(format!(
+                "Unknown theme '{}'; expected one of: {}",
+                payload.theme,
+                THEMES.join(", ")
+            )));
+        }
+
+        ensure_settings_row(&state).await?;
+        sqlx::query("UPDATE settings SET preferred_theme = ?1 WHERE id = 1")
+            .bind(&theme)
+            .execute(&state.pool)
+            .await?;
+        record_audit(&state, &user, "set_theme", None, Some(&theme)).await;
+
+        Ok::<_, AppError>(SetThemeResponse {
+            preferred_theme: theme,
+        })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
 }
 
-#[derive(Debug, Serialize)]
-pub struct ProjectFilesResponse {
-    pub files: Vec<ProjectFileEntry>,
+/// Checks each `(field, model id, type)` against the catalogue, falling back to
+/// the downloaded local inventory only when an id is not catalogued. Ids with
+/// [`CUSTOM_MODEL_PREFIX`] are accepted as long as they name something.
+async fn invalid_model_ids(
+    state: &AppState,
+    model_ids: &[(&'static str, &str, ModelType)],
+) -> BTreeMap<&'static str, String> {
+    let catalogue = default_models();
+    let mut inventory: Option<Vec<Value>> = None;
+    let mut errors = BTreeMap::new();
+    for &(field, model_id, model_type) in model_ids {
+        if let Some(custom) = model_id.strip_prefix(CUSTOM_MODEL_PREFIX) {
+            if custom.trim().is_empty() {
+                errors.insert(
+                    field,
+                    format!("Custom model ids need a name after '{CUSTOM_MODEL_PREFIX}'"),
+                );
+            }
+            continue;
+        }
+        if catalogue
+            .iter()
+            .any(|model| model.id == model_id && model.model_type == model_type)
+        {
+            continue;
+        }
+
+        if inventory.is_none() {
+            let _permit = state.ml_permit().await.ok();
+            inventory = Some(
+                crate::ml_bridge::fetch_model_inventory(&state.models_root)
+                    .await
+                    .unwrap_or_default(),
+            );
+        }
+        let installed = inventory.iter().flatten().any(|item| {
+            item.get("identifier").and_then(Value::as_str) == Some(model_id)
+                && item.get("downloaded").and_then(Value::as_bool) == Some(true)
+                && item
+                    .get("model_type")
+                    .and_then(|value| serde_json::from_value::<ModelType>(value.clone()).ok())
+                    == Some(model_type)
+        });
+        if !installed {
+            let kind = match model_type {
+                ModelType::SpeechToText => "speech-to-text",
+                ModelType::TextToSpeech => "text-to-speech",
+                ModelType::LanguageModel => "language",
+            };
+            errors.insert(
+                field,
+                format!(
+                    "Unknown {kind} model '{model_id}'; choose one from the model list or use '{CUSTOM_MODEL_PREFIX}<name>' for your own model"
+                ),
+            );
+        }
+    }
+    errors
 }
 
-#[derive(Debug, Deserialize)]
-pub struct LoadMarkdownRequest {
-    pub project_id: String,
-    pub file_path: String,
+fn validate_max_file_size(bytes: u64) -> AppResult<i64> {
+    if bytes < MIN_MAX_FILE_SIZE_BYTES {
+        return Err(AppError::Message(format!(
+            "Maximum file size must be at least {}",
+            human_size(MIN_MAX_FILE_SIZE_BYTES)
+        )));
+    }
+    i64::try_from(bytes).map_err(|_| AppError::Message("Maximum file size is too large".into()))
 }
 
-#[derive(Debug, Serialize)]
-pub struct LoadMarkdownResponse {
-    pub content: String,
+fn validate_autosave_interval(secs: u32) -> AppResult<u32> {
+    if !AUTOSAVE_INTERVAL_RANGE.contains(&secs) {
+        return Err(AppError::Message(format!(
+            "Autosave interval must be between {} and {} seconds",
+            AUTOSAVE_INTERVAL_RANGE.start(),
+            AUTOSAVE_INTERVAL_RANGE.end()
+        )));
+    }
+    Ok(secs)
 }
 
-#[derive(Debug, Deserialize)]
-pub struct SaveMarkdownRequest {
-    pub project_id: String,
-    pub file_path: String,
-    pub content: String,
+fn validate_snapshot_retention(count: u32) -> AppResult<u32> {
+    if !SNAPSHOT_RETENTION_RANGE.contains(&count) {
+        return Err(AppError::Message(format!(
+            "Snapshot retention must be between {} and {}",
+            SNAPSHOT_RETENTION_RANGE.start(),
+            SNAPSHOT_RETENTION_RANGE.end()
+        )));
+    }
+    Ok(count)
 }
 
-#[derive(Debug, Serialize)]
-pub struct SaveMarkdownResponse {
-    pub path: String,
+fn validate_ml_job_limit(limit: u32) -> AppResult<u32> {
+    if !ML_JOB_LIMIT_RANGE.contains(&limit) {
+        return Err(AppError::Message(format!(
+            "Concurrent ML jobs must be between {} and {}",
+            ML_JOB_LIMIT_RANGE.start(),
+            ML_JOB_LIMIT_RANGE.end()
+        )));
+    }
+    Ok(limit)
 }
 
-#[derive(Debug, Deserialize)]
-pub struct CopyAssetRequest {
-    pub project_id: String,
-    pub source: String,
-    pub target_dir: Option<String>,
+fn validate_reading_wpm(wpm: u32) -> AppResult<u32> {
+    if !READING_WPM_RANGE.contains(&wpm) {
+        return Err(AppError::Message(format!(
+            "Reading pace must be between {} and {} words per minute",
+            READING_WPM_RANGE.start(),
+            READING_WPM_RANGE.end()
+        )));
+    }
+    Ok(wpm)
 }
 
-#[derive(Debug, Serialize)]
-pub struct CopyAssetResponse {
-    pub relative_path: String,
+fn human_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} KB", bytes.div_ceil(1024))
+    }
 }
 
-#[tauri::command]
-pub async fn transliterate_english_to_tamil(
-    state: State<'_, AppState>,
-    payload: TransliterationRequest,
-) -> Result<TransliterationResponse, String> {
-    let result = async {
-        let _user = require_session(&state).await?;
-        crate::ml_bridge::transliterate_english_to_tamil(&payload.text)
-            .await
-            .map(|candidates| TransliterationResponse { candidates })
+/// Refuses to write drafts larger than the `max_file_size_bytes` setting.
+fn ensure_within_size_limit(content: &str, settings: &SettingsPayload) -> AppResult<()> {
+    let size = content.len() as u64;
+    if size > settings.max_file_size_bytes {
+        return Err(AppError::Message(format!(
+            "Content is {}, over the {} limit. Raise the maximum file size in settings to save larger files.",
+            human_size(size),
+            human_size(settings.max_file_size_bytes)
+        )));
     }
-    .await;
+    Ok(())
+}
 
-    result.map_err(|err| err.to_string())
+/// Composes `content` to NFC when the `normalize_unicode` setting is on, so
+/// decomposed Tamil typed or pasted in is stored in one form.
+fn normalize_for_save(content: String, normalize_unicode: bool) -> String {
+    if normalize_unicode {
+        content.nfc().collect()
+    } else {
+        content
+    }
 }
 
-#[tauri::command]
-pub async fn transcribe_audio_file(
-    state: State<'_, AppState>,
-    audio_path: String,
-    language: Option<String>,
-) -> Result<Value, String> {
-    let lang = language.unwrap_or_else(|| "en-IN".to_string());
-    let result = async {
-        let _user = require_session(&state).await?;
-        crate::ml_bridge::transcribe_audio_file(&audio_path, &lang).await
-    }.await;
-    result.map_err(|err| err.to_string())
+const SETTINGS_EXPORT_VERSION: u32 = 1;
+const THEMES: &[&str] = &["system", "light", "dark"];
+
+/// Settings that can move between machines; API keys are never included,
+/// and neither are the audit log toggle and ML job limit, which belong to
+/// the machine.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PortableSettings {
+    pub version: u32,
+    pub preferred_theme: Option<String>,
+    pub transliteration_mode: Option<String>,
+    pub stt_model: Option<String>,
+    pub tts_model: Option<String>,
+    pub llm_model: Option<String>,
+    pub default_languages: Option<Vec<String>>,
+    pub default_template_id: Option<String>,
+    pub normalize_unicode: Option<bool>,
+    pub llm_fallback: Option<bool>,
+    pub max_file_size_bytes: Option<u64>,
+    pub autosave_interval_secs: Option<u32>,
+    pub snapshot_retention: Option<u32>,
+    pub reading_wpm_english: Option<u32>,
+    pub reading_wpm_tamil: Option<u32>,
 }
 
 #[tauri::command]
-pub async fn record_from_microphone(
-    state: State<'_, AppState>,
-    duration: Option<i32>,
-    language: Option<String>,
-) -> Result<Value, String> {
-    let dur = duration.unwrap_or(5);
-    let lang = language.unwrap_or_else(|| "en-IN".to_string());
+pub async fn export_settings(state: State<'_, AppState>) -> Result<PortableSettings, String> {
     let result = async {
         let _user = require_session(&state).await?;
-        crate::ml_bridge::record_and_transcribe(dur, &lang).await
-    }.await;
+        let settings = fetch_settings(&state).await?;
+        Ok::<_, AppError>(PortableSettings {
+            version: SETTINGS_EXPORT_VERSION,
+            preferred_theme: Some(settings.preferred_theme),
+            transliteration_mode: Some(settings.transliteration_mode),
+            stt_model: Some(settings.stt_model),
+            tts_model: Some(settings.tts_model),
+            llm_model: Some(settings.llm_model),
+            default_languages: Some(settings.default_languages),
+            default_template_id: Some(settings.default_template_id),
+            normalize_unicode: Some(settings.normalize_unicode),
+            llm_fallback: Some(settings.llm_fallback),
+            max_file_size_bytes: Some(settings.max_file_size_bytes),
+            autosave_interval_secs: Some(settings.autosave_interval_secs),
+            snapshot_retention: Some(settings.snapshot_retention),
+            reading_wpm_english: Some(settings.reading_wpm_english),
+            reading_wpm_tamil: Some(settings.reading_wpm_tamil),
+        })
+    }
+    .await;
     result.map_err(|err| err.to_string())
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ImportSettingsRequest {
+    pub settings: Value,
+}
+
 #[tauri::command]
-pub async fn synthesize_speech(
+pub async fn import_settings(
     state: State<'_, AppState>,
-    text: String,
-    language: Option<String>,
-) -> Result<Value, String> {
-    let _lang = language.unwrap_or_else(|| "en".to_string());
+    payload: ImportSettingsRequest,
+) -> Result<SettingsPayload, AppError> {
+    let user = require_session(&state).await?;
+
+    // Unknown keys, including any api_keys from a hand-edited file, are ignored.
+    let imported: PortableSettings = serde_json::from_value(payload.settings)
+        .map_err(|err| AppError::Message(format!("Invalid settings file: {err}")))?;
+    if imported.version > SETTINGS_EXPORT_VERSION {
+        return Err(AppError::Message(format!(
+            "Settings file version {} is newer than this app supports",
+            imported.version
+        )));
+    }
+
+    let mut errors = BTreeMap::new();
+    if let Some(theme) = &imported.preferred_theme {
+        if !THEMES.contains(&theme.as_str()) {
+            errors.insert("preferred_theme", format!("Unknown theme '{theme}'"));
+        }
+    }
+    if let Some(mode) = &imported.transliteration_mode {
+        if !TRANSLITERATION_MODES.contains(&mode.as_str()) {
+            errors.insert(
+                "transliteration_mode",
+                format!("Unknown transliteration mode '{mode}'"),
+            );
+        }
+    }
+    let model_ids = [
+        ("stt_model", &imported.stt_model, ModelType::SpeechToText),
+        ("tts_model", &imported.tts_model, ModelType::TextToSpeech),
+        ("llm_model", &imported.llm_model, ModelType::LanguageModel),
+    ]
+    .into_iter()
+    .filter_map(|(field, value, model_type)| Some((field, value.as_deref()?, model_type)))
+    .collect::<Vec<_>>();
+    errors.extend(invalid_model_ids(&state, &model_ids).await);
+    let default_languages = match imported
+        .default_languages
+        .as_deref()
+        .map(validate_language_codes)
+    {
+        Some(Ok(languages)) => Some(serde_json::to_string(&languages)?),
+        Some(Err(err)) => {
+            errors.insert("default_languages", err.to_string());
+            None
+        }
+        None => None,
+    };
+    if let Some(template_id) = &imported.default_template_id {
+        if project_template(Some(template_id)).is_none() {
+            errors.insert(
+                "default_template_id",
+                format!("Unknown template '{template_id}'"),
+            );
+        }
+    }
+    let max_file_size_bytes = match imported.max_file_size_bytes.map(validate_max_file_size) {
+        Some(Ok(bytes)) => Some(bytes),
+        Some(Err(err)) => {
+            errors.insert("max_file_size_bytes", err.to_string());
+            None
+        }
+        None => None,
+    };
+    if let Some(Err(err)) = imported
+        .autosave_interval_secs
+        .map(validate_autosave_interval)
+    {
+        errors.insert("autosave_interval_secs", err.to_string());
+    }
+    if let Some(Err(err)) = imported.snapshot_retention.map(validate_snapshot_retention) {
+        errors.insert("snapshot_retention", err.to_string());
+    }
+    if let Some(Err(err)) = imported.reading_wpm_english.map(validate_reading_wpm) {
+        errors.insert("reading_wpm_english", err.to_string());
+    }
+    if let Some(Err(err)) = imported.reading_wpm_tamil.map(validate_reading_wpm) {
+        errors.insert("reading_wpm_tamil", err.to_string());
+    }
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    ensure_settings_row(&state).await?;
+    sqlx::query(
+        r#"
+      UPDATE settings
+      SET preferred_theme = COALESCE(?1, preferred_theme),
+          transliteration_mode = COALESCE(?2, transliteration_mode),
+          stt_model = COALESCE(?3, stt_model),
+          tts_model = COALESCE(?4, tts_model),
+          llm_model = COALESCE(?5, llm_model),
+          default_languages = COALESCE(?6, default_languages),
+          default_template_id = COALESCE(?7, default_template_id),
+          normalize_unicode = COALESCE(?8, normalize_unicode),
+          llm_fallback = COALESCE(?9, llm_fallback),
+          max_file_size_bytes = COALESCE(?10, max_file_size_bytes),
+          autosave_interval_secs = COALESCE(?11, autosave_interval_secs),
+          snapshot_retention = COALESCE(?12, snapshot_retention),
+          reading_wpm_english = COALESCE(?13, reading_wpm_english),
+          reading_wpm_tamil = COALESCE(?14, reading_wpm_tamil)
+      WHERE id = 1
+    "#,
+    )
+    .bind(&imported.preferred_theme)
+    .bind(&imported.transliteration_mode)
+    .bind(&imported.stt_model)
+    .bind(&imported.tts_model)
+    .bind(&imported.llm_model)
+    .bind(default_languages)
+    .bind(&imported.default_template_id)
+    .bind(imported.normalize_unicode)
+    .bind(imported.llm_fallback)
+    .bind(max_file_size_bytes)
+    .bind(imported.autosave_interval_secs)
+    .bind(imported.snapshot_retention)
+    .bind(imported.reading_wpm_english)
+    .bind(imported.reading_wpm_tamil)
+    .execute(&state.pool)
+    .await?;
+    record_audit(&state, &user, "import_settings", None, None).await;
+
+    Ok(fetch_settings(&state).await?.redacted())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetAuditLogRequest {
+    pub project_id: Option<String>,
+    pub user_id: Option<String>,
+    pub limit: Option<u32>,
+}
+
+const DEFAULT_AUDIT_LOG_LIMIT: u32 = 100;
+const MAX_AUDIT_LOG_LIMIT: u32 = 1000;
+
+#[tauri::command]
+pub async fn get_audit_log(
+    state: State<'_, AppState>,
+    payload: GetAuditLogRequest,
+) -> Result<Vec<AuditEntry>, String> {
     let result = async {
         let _user = require_session(&state).await?;
-        crate::ml_bridge::synthesize_speech(&text, None).await
-    }.await;
+        let limit = payload
+            .limit
+            .unwrap_or(DEFAULT_AUDIT_LOG_LIMIT)
+            .clamp(1, MAX_AUDIT_LOG_LIMIT);
+
+        let entries = sqlx::query_as::<_, AuditEntry>(
+            r#"
+      SELECT audit_log.id, audit_log.user_id, users.email AS user_email, audit_log.project_id,
+             audit_log.command, audit_log.detail, audit_log.created_at
+      FROM audit_log
+      LEFT JOIN users ON users.id = audit_log.user_id
+      WHERE (?1 IS NULL OR audit_log.project_id = ?1)
+        AND (?2 IS NULL OR audit_log.user_id = ?2)
+      ORDER BY audit_log.id DESC
+      LIMIT ?3
+    "#,
+        )
+        .bind(&payload.project_id)
+        .bind(&payload.user_id)
+        .bind(limit)
+        .fetch_all(&state.pool)
+        .await?;
+        Ok::<_, AppError>(entries)
+    }
+    .await;
     result.map_err(|err| err.to_string())
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct VacuumDatabaseRequest {
+    /// Also fold the write-ahead log back into the database file.
+    #[serde(default)]
+    pub checkpoint: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VacuumDatabaseResponse {
+    /// Database plus write-ahead log, in bytes.
+    pub before_bytes: u64,
+    pub after_bytes: u64,
+    pub journal_mode: String,
+    pub checkpointed: bool,
+}
+
+fn database_size(database: &Path) -> u64 {
+    let wal = database.with_file_name(format!("{DATABASE_FILE}-wal"));
+    [database, wal.as_path()]
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Rebuilds the database to reclaim space left by deletions. Draft saves are
+/// held off for the duration; other database writers (audit entries,
+/// settings, AI history, reminders) wait on SQLite's lock for up to the
+/// pool's busy timeout. Everything runs on one connection so the checkpoint
+/// sees the vacuum's own pages.
 #[tauri::command]
-pub async fn generate_ai_scene(
+pub async fn vacuum_database(
     state: State<'_, AppState>,
-    prompt: String,
-    context: Option<String>,
-    api_key: Option<String>,
-) -> Result<Value, String> {
-    let ctx = context.unwrap_or_default();
+    payload: VacuumDatabaseRequest,
+) -> Result<VacuumDatabaseResponse, String> {
     let result = async {
-        let _user = require_session(&state).await?;
-        crate::ml_bridge::generate_scene_ai(&prompt, &ctx, api_key.as_deref()).await
-    }.await;
+        let user = require_session(&state).await?;
+        let database = state.storage_root.join(DATABASE_FILE);
+
+        let _write = state.pending_writes.write().await;
+        let before_bytes = database_size(&database);
+        let mut connection = state.pool.acquire().await?;
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&mut *connection)
+            .await?;
+        sqlx::query("VACUUM").execute(&mut *connection).await?;
+        // Outside WAL mode VACUUM already rewrote the file in place.
+        let checkpointed = payload.checkpoint && journal_mode.eq_ignore_ascii_case("wal");
+        if checkpointed {
+            let busy: i64 = sqlx::query_scalar("PRAGMA wal_checkpoint(TRUNCATE)")
+                .fetch_one(&mut *connection)
+                .await?;
+            if busy != 0 {
+                log::warn!("WAL checkpoint could not finish while readers were active");
+            }
+        }
+        drop(connection);
+        let after_bytes = database_size(&database);
+
+        let detail = format!("{before_bytes} -> {after_bytes} bytes");
+        record_audit(&state, &user, "vacuum_database", None, Some(&detail)).await;
+        Ok::<_, AppError>(VacuumDatabaseResponse {
+            before_bytes,
+            after_bytes,
+            journal_mode,
+            checkpointed,
+        })
+    }
+    .await;
     result.map_err(|err| err.to_string())
 }
 
+#[derive(Debug, Serialize)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub description: String,
+    pub checksum: String,
+    pub installed_on: String,
+    pub success: bool,
+    /// `None` when this build does not ship the migration, e.g. after a downgrade.
+    pub checksum_matches: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingMigration {
+    pub version: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppInfo {
+    pub app_version: String,
+    /// Latest migration applied to the database.
+    pub schema_version: Option<i64>,
+    /// Latest migration bundled with this build.
+    pub bundled_schema_version: Option<i64>,
+    pub python_version: Option<String>,
+    pub toolkit_version: Option<String>,
+    /// Why the Python toolkit could not be queried, when it could not.
+    pub toolkit_error: Option<String>,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub data_dir: String,
+}
+
+/// Version and environment details for bug reports. Callable before login,
+/// so it reads nothing from settings and carries no keys.
 #[tauri::command]
-pub async fn list_projects(state: State<'_, AppState>) -> Result<Vec<ProjectRecord>, String> {
-    if let Err(err) = require_session(&state).await {
-        return Err(err.to_string());
+pub async fn app_info(app: AppHandle, state: State<'_, AppState>) -> Result<AppInfo, String> {
+    let result = async {
+        let schema_version: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+                .fetch_one(&state.pool)
+                .await?;
+        let bundled_schema_version = MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| migration.version)
+            .max();
+        let _permit = state.ml_permit().await?;
+        let (python_version, toolkit_version, toolkit_error) =
+            match crate::ml_bridge::toolkit_version().await {
+                Ok(version) => (Some(version.python), Some(version.toolkit), None),
+                Err(err) => (None, None, Some(err.to_string())),
+            };
+
+        Ok::<_, AppError>(AppInfo {
+            app_version: app.package_info().version.to_string(),
+            schema_version,
+            bundled_schema_version,
+            python_version,
+            toolkit_version,
+            toolkit_error,
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            data_dir: state.storage_root.to_string_lossy().to_string(),
+        })
     }
-    let rows = sqlx::query_as::<_, ProjectRow>(
-    r#"
-      SELECT id, name, slug, project_type, languages, description, template_id, base_path, created_at, updated_at
-      FROM projects
-      ORDER BY datetime(updated_at) DESC
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Serialize)]
+pub struct MigrationStatus {
+    pub applied: Vec<AppliedMigration>,
+    pub pending: Vec<PendingMigration>,
+    pub up_to_date: bool,
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Which bundled migrations the database has run, for diagnosing schema
+/// mismatches after an update.
+#[tauri::command]
+pub async fn migration_status(state: State<'_, AppState>) -> Result<MigrationStatus, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let rows = sqlx::query(
+            r#"
+      SELECT version, description, CAST(installed_on AS TEXT) AS installed_on, success, checksum
+      FROM _sqlx_migrations
+      ORDER BY version
     "#,
-  )
-  .fetch_all(&state.pool)
-  .await
-  .map_err(|err| err.to_string())?;
+        )
+        .fetch_all(&state.pool)
+        .await?;
 
-    let mut projects = Vec::with_capacity(rows.len());
-    for row in rows {
-        match ProjectRecord::try_from(row) {
-            Ok(project) => projects.push(project),
-            Err(err) => return Err(err.to_string()),
+        let bundled = MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .collect::<Vec<_>>();
+        let mut applied = Vec::with_capacity(rows.len());
+        for row in rows {
+            let version: i64 = row.try_get("version")?;
+            let checksum: Vec<u8> = row.try_get("checksum")?;
+            applied.push(AppliedMigration {
+                version,
+                description: row.try_get("description")?,
+                checksum: hex_digest(&checksum),
+                installed_on: row.try_get("installed_on")?,
+                success: row.try_get("success")?,
+                checksum_matches: bundled
+                    .iter()
+                    .find(|migration| migration.version == version)
+                    .map(|migration| *migration.checksum == *checksum),
+            });
         }
+        let pending = bundled
+            .iter()
+            .filter(|migration| !applied.iter().any(|row| row.version == migration.version))
+            .map(|migration| PendingMigration {
+                version: migration.version,
+                description: migration.description.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let up_to_date = pending.is_empty()
+            && applied
+                .iter()
+                .all(|row| row.success && row.checksum_matches == Some(true));
+        Ok::<_, AppError>(MigrationStatus {
+            applied,
+            pending,
+            up_to_date,
+        })
     }
+    .await;
+    result.map_err(|err| err.to_string())
+}
 
-    Ok(projects)
+#[derive(Debug, Deserialize)]
+pub struct TransliterationRequest {
+    pub text: String,
+    pub mode: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ModelInventoryResponse {
-    pub models: Vec<Value>,
+pub struct TransliterationResponse {
+    pub candidates: Vec<String>,
+    pub status: TransliterationStatus,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListProjectFilesRequest {
+    pub project_id: String,
+    #[serde(default)]
+    pub include_kinds: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectFilesResponse {
+    pub files: Vec<ProjectFileEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoadMarkdownRequest {
+    pub project_id: String,
+    pub file_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoadMarkdownResponse {
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveMarkdownRequest {
+    pub project_id: String,
+    pub file_path: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SaveMarkdownResponse {
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CopyAssetRequest {
+    pub project_id: String,
+    pub source: String,
+    pub target_dir: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CopyAssetResponse {
+    pub relative_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevealApiKeyRequest {
+    pub key: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RevealApiKeyResponse {
+    pub key: String,
+    pub value: Option<String>,
 }
 
 #[tauri::command]
-pub async fn refresh_model_inventory(
+pub async fn reveal_api_key(
     state: State<'_, AppState>,
-) -> Result<ModelInventoryResponse, String> {
-    if let Err(err) = require_session(&state).await {
-        return Err(err.to_string());
+    payload: RevealApiKeyRequest,
+) -> Result<RevealApiKeyResponse, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let api_keys = fetch_settings(&state).await?.api_keys;
+        let value = api_keys
+            .get(&payload.key)
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        Ok::<_, AppError>(RevealApiKeyResponse {
+            key: payload.key,
+            value,
+        })
+    }
+    .await;
+
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TestLlmCredentialsRequest {
+    pub provider: String,
+    pub api_key: Option<String>,
+}
+
+#[tauri::command]
+pub async fn test_llm_credentials(
+    state: State<'_, AppState>,
+    payload: TestLlmCredentialsRequest,
+) -> Result<LlmCredentialCheck, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let api_keys = fetch_settings(&state).await?.api_keys;
+        let key_name = format!("{}_key", payload.provider);
+        let api_key = resolve_api_key(&api_keys, &key_name, payload.api_key)
+            .ok_or_else(|| AppError::Message("Enter an API key to test".into()))?;
+        let _permit = state.ml_permit().await?;
+        crate::ml_bridge::test_llm_credentials(&payload.provider, &api_key).await
+    }
+    .await;
+
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn transliterate_english_to_tamil(
+    state: State<'_, AppState>,
+    payload: TransliterationRequest,
+) -> Result<TransliterationResponse, String> {
+    let result = async {
+        let user = require_session(&state).await?;
+        let mode = match payload.mode {
+            Some(mode) if TRANSLITERATION_MODES.contains(&mode.as_str()) => mode,
+            Some(mode) => {
+                return Err(AppError::Message(format!(
+                    "Unknown transliteration mode '{mode}'"
+                )))
+            }
+            None => Some(fetch_settings(&state).await?.transliteration_mode)
+                .filter(|mode| TRANSLITERATION_MODES.contains(&mode.as_str()))
+                .unwrap_or_else(|| DEFAULT_TRANSLITERATION_MODE.to_string()),
+        };
+        let correction: Option<String> = sqlx::query_scalar(
+            "SELECT output FROM transliteration_corrections WHERE user_id = ?1 AND input = ?2",
+        )
+        .bind(&user.id)
+        .bind(correction_key(&payload.text))
+        .fetch_optional(&state.pool)
+        .await?;
+
+        let result = {
+            let _permit = state.ml_permit().await?;
+            crate::ml_bridge::transliterate_english_to_tamil(&payload.text, &mode).await?
+        };
+        let mut candidates = result.candidates;
+        let mut notes = result.notes;
+        if let Some(correction) = correction {
+            candidates.retain(|candidate| *candidate != correction);
+            candidates.insert(0, correction);
+            notes.push("Your saved correction is listed first".to_string());
+        }
+        Ok(TransliterationResponse {
+            candidates,
+            status: result.status,
+            notes,
+        })
+    }
+    .await;
+
+    result.map_err(|err| err.to_string())
+}
+
+const MAX_CORRECTION_CHARS: usize = 200;
+
+/// Corrections match the whole input, ignoring case and surrounding spaces.
+fn correction_key(input: &str) -> String {
+    input.trim().to_lowercase()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveTransliterationCorrectionRequest {
+    /// The Latin text as typed.
+    pub input: String,
+    /// The Tamil text the user prefers for it.
+    pub output: String,
+}
+
+/// Remembers the user's preferred transliteration so it leads the candidate
+/// list the next time they type the same input. Saving again replaces it.
+#[tauri::command]
+pub async fn save_transliteration_correction(
+    state: State<'_, AppState>,
+    payload: SaveTransliterationCorrectionRequest,
+) -> Result<TransliterationCorrection, String> {
+    let result = async {
+        let user = require_session(&state).await?;
+        let input = correction_key(&payload.input);
+        let output = payload.output.trim().nfc().collect::<String>();
+        if input.is_empty() || output.is_empty() {
+            return Err(AppError::Message(
+                "Both the input and the corrected output are required".into(),
+            ));
+        }
+        if !output.chars().any(crate::language::is_tamil) {
+            return Err(AppError::Message(
+                "Corrections must be written in Tamil script".into(),
+            ));
+        }
+        if input.chars().count() > MAX_CORRECTION_CHARS
+            || output.chars().count() > MAX_CORRECTION_CHARS
+        {
+            return Err(AppError::Message(format!(
+                "Corrections are limited to {MAX_CORRECTION_CHARS} characters"
+            )));
+        }
+
+        let correction = sqlx::query_as::<_, TransliterationCorrection>(
+            r#"
+      INSERT INTO transliteration_corrections (user_id, input, output)
+      VALUES (?1, ?2, ?3)
+      ON CONFLICT(user_id, input) DO UPDATE SET
+        output = excluded.output,
+        updated_at = datetime('now')
+      RETURNING input, output, updated_at
+    "#,
+        )
+        .bind(&user.id)
+        .bind(&input)
+        .bind(&output)
+        .fetch_one(&state.pool)
+        .await?;
+        Ok(correction)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+/// Language codes `stt_model` can transcribe, cached per model.
+async fn stt_model_languages(state: &AppState, stt_model: &str) -> AppResult<Vec<String>> {
+    if let Some(languages) = state.stt_languages.lock().await.get(stt_model) {
+        return Ok(languages.clone());
     }
-    crate::ml_bridge::fetch_model_inventory(&state.models_root)
+    let languages = {
+        let _permit = state.ml_permit().await?;
+        crate::ml_bridge::stt_languages(stt_model).await?
+    };
+    state
+        .stt_languages
+        .lock()
         .await
-        .map(|models| ModelInventoryResponse { models })
-        .map_err(|err| err.to_string())
+        .insert(stt_model.to_string(), languages.clone());
+    Ok(languages)
+}
+
+/// Rejects a transcription language the configured model cannot handle
+/// before any audio is processed. Custom models are taken on trust.
+async fn ensure_stt_language(state: &AppState, language: &str) -> AppResult<()> {
+    let stt_model = fetch_settings(state).await?.stt_model;
+    if stt_model.starts_with(CUSTOM_MODEL_PREFIX) {
+        return Ok(());
+    }
+    let languages = stt_model_languages(state, &stt_model).await?;
+    if languages
+        .iter()
+        .any(|supported| supported.eq_ignore_ascii_case(language))
+    {
+        return Ok(());
+    }
+    Err(AppError::Message(format!(
+        "{stt_model} does not support language '{language}'; supported: {}",
+        languages.join(", ")
+    )))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SupportedLanguagesRequest {
+    /// Defaults to the configured speech-to-text model.
+    pub stt_model: Option<String>,
+}
+
+#[tauri::command]
+pub async fn supported_languages(
+    state: State<'_, AppState>,
+    payload: SupportedLanguagesRequest,
+) -> Result<Vec<String>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let stt_model = match payload.stt_model {
+            Some(stt_model) => stt_model,
+            None => fetch_settings(&state).await?.stt_model,
+        };
+        if !default_models()
+            .iter()
+            .any(|model| model.id == stt_model && model.model_type == ModelType::SpeechToText)
+        {
+            return Err(AppError::Message(format!(
+                "Unknown STT model '{stt_model}'"
+            )));
+        }
+        stt_model_languages(&state, &stt_model).await
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn transcribe_audio_file(
+    state: State<'_, AppState>,
+    audio_path: String,
+    language: Option<String>,
+    start_secs: Option<f64>,
+    end_secs: Option<f64>,
+    diarize: Option<bool>,
+    save_to_project: Option<String>,
+) -> Result<Value, String> {
+    let lang = language.unwrap_or_else(|| "en-IN".to_string());
+    let result = async {
+        let user = require_session(&state).await?;
+        validate_time_range(start_secs, end_secs)?;
+        ensure_stt_language(&state, &lang).await?;
+        let mut transcript = {
+            let _permit = state.ml_permit().await?;
+            crate::ml_bridge::transcribe_audio_file(
+                &audio_path,
+                &lang,
+                start_secs,
+                end_secs,
+                diarize.unwrap_or(false),
+            )
+            .await?
+        };
+        let Some(project_id) = save_to_project else {
+            return Ok(transcript);
+        };
+        let heard = transcript
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if heard.trim().is_empty() {
+            return Ok(transcript);
+        }
+
+        let project_row = fetch_project_row(&state, &project_id).await?;
+        ensure_unlocked(&project_row)?;
+        let base_path = PathBuf::from(&project_row.base_path);
+        let stem = Path::new(&audio_path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let settings = fetch_settings(&state).await?;
+        let markdown = transcript_to_markdown(&stem, &transcript);
+        ensure_within_size_limit(&markdown, &settings)?;
+        let markdown = normalize_for_save(markdown, settings.normalize_unicode);
+
+        let drafts_dir = resolve_project_path(&base_path, "drafts")?;
+        fs::create_dir_all(&drafts_dir)?;
+        let name = sanitize(&stem);
+        let name = if name.is_empty() {
+            "transcript".to_string()
+        } else {
+            format!("{name}-transcript")
+        };
+        let _write = state.pending_writes.read().await;
+        let target = timestamped_path(&drafts_dir, &name, "md");
+        write_atomic(&target, markdown.as_bytes())?;
+        state.invalidate_disk_usage(&project_id).await;
+        sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+            .bind(&project_id)
+            .execute(&state.pool)
+            .await?;
+
+        let relative = to_relative_string(&base_path, &target)?;
+        record_audit(
+            &state,
+            &user,
+            "transcribe_audio_file",
+            Some(&project_id),
+            Some(&relative),
+        )
+        .await;
+        if let Some(object) = transcript.as_object_mut() {
+            object.insert("transcript_path".into(), Value::String(relative));
+        }
+        Ok::<_, AppError>(transcript)
+    }.await;
+    result.map_err(|err| err.to_string())
+}
+
+fn validate_time_range(start_secs: Option<f64>, end_secs: Option<f64>) -> AppResult<()> {
+    for value in [start_secs, end_secs].into_iter().flatten() {
+        if !value.is_finite() || value < 0.0 {
+            return Err(AppError::Message(
+                "Time range values must be non-negative seconds".into(),
+            ));
+        }
+    }
+    if let (Some(start), Some(end)) = (start_secs, end_secs) {
+        if start >= end {
+            return Err(AppError::Message(
+                "Start time must be before end time".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TranscribeAudioBatchRequest {
+    pub audio_paths: Vec<String>,
+    pub language: Option<String>,
+    pub diarize: Option<bool>,
+}
+
+#[tauri::command]
+pub async fn transcribe_audio_batch(
+    state: State<'_, AppState>,
+    payload: TranscribeAudioBatchRequest,
+) -> Result<JobStartedResponse, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        if payload.audio_paths.is_empty() {
+            return Err(AppError::Message("No audio files selected".into()));
+        }
+
+        let lang = payload.language.unwrap_or_else(|| "en-IN".to_string());
+        ensure_stt_language(&state, &lang).await?;
+        let audio_paths = payload.audio_paths;
+        let diarize = payload.diarize.unwrap_or(false);
+        let ml_jobs = state.ml_jobs.clone();
+        let job_id = state
+            .jobs
+            .enqueue("transcribe_audio_batch", move |job| async move {
+                let total = audio_paths.len();
+                let mut results = Vec::with_capacity(total);
+                for (index, audio_path) in audio_paths.into_iter().enumerate() {
+                    job.progress(index as f32 / total as f32, audio_path.clone())
+                        .await;
+                    // One permit per file so other ML work can interleave.
+                    let _permit = ml_jobs
+                        .clone()
+                        .acquire_owned()
+                        .await
+                        .map_err(|err| AppError::Anyhow(err.into()))?;
+                    let entry = match crate::ml_bridge::transcribe_audio_file(
+                        &audio_path,
+                        &lang,
+                        None,
+                        None,
+                        diarize,
+                    )
+                    .await
+                    {
+                        Ok(transcript) => json!({ "audio_path": audio_path, "result": transcript }),
+                        Err(err) => json!({ "audio_path": audio_path, "error": err.to_string() }),
+                    };
+                    results.push(entry);
+                }
+                Ok(json!({ "results": results }))
+            })
+            .await?;
+
+        Ok(JobStartedResponse { job_id })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn record_from_microphone(
+    state: State<'_, AppState>,
+    duration: Option<i32>,
+    language: Option<String>,
+    device_id: Option<u32>,
+    sample_rate: Option<u32>,
+    save_to_project: Option<String>,
+) -> Result<Value, String> {
+    let dur = duration.unwrap_or(5);
+    let lang = language.unwrap_or_else(|| "en-IN".to_string());
+    let result = async {
+        let _user = require_session(&state).await?;
+        ensure_stt_language(&state, &lang).await?;
+        let _permit = state.ml_permit().await?;
+        let Some(project_id) = save_to_project else {
+            return crate::ml_bridge::record_and_transcribe(
+                dur,
+                &lang,
+                device_id,
+                sample_rate,
+                None,
+            )
+            .await;
+        };
+
+        let project_row = fetch_project_row(&state, &project_id).await?;
+        ensure_unlocked(&project_row)?;
+        let base_path = PathBuf::from(&project_row.base_path);
+        let template = project_template(project_row.template_id.as_deref());
+        let audio_dir = resolve_project_path(&base_path, project_audio_dir(template.as_ref()))?;
+        fs::create_dir_all(&audio_dir)?;
+        let target = timestamped_path(&audio_dir, "recording", "wav");
+
+        let mut transcript = crate::ml_bridge::record_and_transcribe(
+            dur,
+            &lang,
+            device_id,
+            sample_rate,
+            Some(target.clone()),
+        )
+        .await?;
+        if !target.is_file() {
+            return Ok(transcript);
+        }
+
+        state.invalidate_disk_usage(&project_id).await;
+        sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+            .bind(&project_id)
+            .execute(&state.pool)
+            .await?;
+
+        let relative = to_relative_string(&base_path, &target)?;
+        if let Some(object) = transcript.as_object_mut() {
+            object.remove("audio_file");
+            object.insert("audio_path".into(), Value::String(relative));
+        }
+        Ok(transcript)
+    }.await;
+    result.map_err(|err| err.to_string())
+}
+
+fn project_audio_dir(template: Option<&ProjectTemplate>) -> &'static str {
+    template
+        .and_then(|template| {
+            template
+                .directory_structure
+                .iter()
+                .find(|dir| dir.starts_with("audio/"))
+                .copied()
+        })
+        .unwrap_or("audio")
+}
+
+/// A fresh `<prefix>-<unix secs>.<extension>` path in `dir`.
+fn timestamped_path(dir: &Path, prefix: &str, extension: &str) -> PathBuf {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let mut candidate = dir.join(format!("{prefix}-{stamp}.{extension}"));
+    let mut counter = 1usize;
+    while candidate.exists() {
+        candidate = dir.join(format!("{prefix}-{stamp}-{counter}.{extension}"));
+        counter += 1;
+    }
+    candidate
+}
+
+#[tauri::command]
+pub async fn list_audio_devices(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::ml_bridge::AudioDevice>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let _permit = state.ml_permit().await?;
+        crate::ml_bridge::list_audio_devices().await
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn synthesize_speech(
+    state: State<'_, AppState>,
+    text: String,
+    language: Option<String>,
+) -> Result<Value, String> {
+    let _lang = language.unwrap_or_else(|| "en".to_string());
+    let result = async {
+        let _user = require_session(&state).await?;
+        let _permit = state.ml_permit().await?;
+        crate::ml_bridge::synthesize_speech(&text, None).await
+    }.await;
+    result.map_err(|err| err.to_string())
+}
+
+/// Previews are for auditioning voices, not reading scenes aloud.
+const SPEECH_PREVIEW_MAX_CHARS: usize = 300;
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewSpeechRequest {
+    pub text: String,
+    pub language: Option<String>,
+    pub voice: Option<String>,
+}
+
+#[tauri::command]
+pub async fn preview_speech(
+    state: State<'_, AppState>,
+    payload: PreviewSpeechRequest,
+) -> Result<SpeechPreview, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let text = payload.text.trim();
+        if text.is_empty() {
+            return Err(AppError::Message("Enter some text to preview".into()));
+        }
+        let length = text.chars().count();
+        if length > SPEECH_PREVIEW_MAX_CHARS {
+            return Err(AppError::Message(format!(
+                "Previews are limited to {SPEECH_PREVIEW_MAX_CHARS} characters ({length} given)"
+            )));
+        }
+        let language = payload.language.as_deref().unwrap_or("en");
+        let _permit = state.ml_permit().await?;
+        crate::ml_bridge::preview_speech(text, language, payload.voice.as_deref()).await
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListTtsVoicesRequest {
+    pub tts_model: String,
+}
+
+#[tauri::command]
+pub async fn list_tts_voices(
+    state: State<'_, AppState>,
+    payload: ListTtsVoicesRequest,
+) -> Result<Vec<TtsVoice>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        if !default_models().iter().any(|model| {
+            model.id == payload.tts_model && model.model_type == ModelType::TextToSpeech
+        }) {
+            return Err(AppError::Message(format!(
+                "Unknown TTS model '{}'",
+                payload.tts_model
+            )));
+        }
+
+        if let Some(voices) = state.tts_voices.lock().await.get(&payload.tts_model) {
+            return Ok(voices.clone());
+        }
+        let voices = {
+            let _permit = state.ml_permit().await?;
+            crate::ml_bridge::list_tts_voices(&payload.tts_model, &state.models_root).await?
+        };
+        state
+            .tts_voices
+            .lock()
+            .await
+            .insert(payload.tts_model, voices.clone());
+        Ok(voices)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_ai_scene(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    prompt: String,
+    context: Option<String>,
+    api_key: Option<String>,
+    variations: Option<u8>,
+    project_id: Option<String>,
+    persona_id: Option<i64>,
+) -> Result<Value, String> {
+    let ctx = context.unwrap_or_default();
+    let result = async {
+        let _user = require_session(&state).await?;
+        if let Some(project_id) = &project_id {
+            fetch_project_row(&state, project_id).await?;
+        }
+        let system_prompt = match persona_id {
+            Some(persona_id) => Some(fetch_persona(&state, persona_id).await?.system_prompt),
+            None => None,
+        };
+        let variations = variations.unwrap_or(1);
+        if !(1..=MAX_SCENE_VARIATIONS).contains(&variations) {
+            return Err(AppError::Message(format!(
+                "Variations must be between 1 and {MAX_SCENE_VARIATIONS}"
+            )));
+        }
+        let settings = fetch_settings(&state).await?;
+        let api_key = resolve_api_key(&settings.api_keys, OPENROUTER_KEY, api_key);
+        let _permit = state.ml_permit().await?;
+        let inventory = crate::ml_bridge::fetch_model_inventory(&state.models_root)
+            .await
+            .unwrap_or_else(|err| {
+                log::warn!("Model inventory unavailable for generation: {err}");
+                Vec::new()
+            });
+        let local_models = downloaded_language_models(&inventory);
+        let mut active = local_models
+            .iter()
+            .find(|model| model.id == settings.llm_model);
+        let mut fallback_from = None;
+
+        let mut output = loop {
+            let variation_app = app.clone();
+            let output = crate::ml_bridge::generate_scene_ai(
+                &prompt,
+                &ctx,
+                api_key.as_deref(),
+                variations,
+                active.map(|model| model.path.as_str()),
+                system_prompt.as_deref(),
+                move |event| {
+                    if let Err(err) = variation_app.emit("ai-scene-variation", event) {
+                        log::warn!("Failed to emit scene variation: {err}");
+                    }
+                },
+            )
+            .await?;
+
+            let out_of_memory = output
+                .get("out_of_memory")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            let lighter = active.and_then(|current| {
+                local_models
+                    .iter()
+                    .filter(|model| model.size_mb < current.size_mb)
+                    .max_by_key(|model| model.size_mb)
+            });
+            match (active, lighter) {
+                (Some(current), Some(lighter)) if out_of_memory && settings.llm_fallback => {
+                    let event = json!({
+                        "from": current.id,
+                        "to": lighter.id,
+                        "reason": output.get("error"),
+                    });
+                    if let Err(err) = app.emit("llm-fallback", event) {
+                        log::warn!("Failed to emit LLM fallback: {err}");
+                    }
+                    fallback_from.get_or_insert_with(|| current.id.clone());
+                    active = Some(lighter);
+                }
+                _ => break output,
+            }
+        };
+
+        // Report the registry id rather than the .gguf file name when a local
+        // model answered.
+        if let (Some(model), Value::Object(fields)) = (active, &mut output) {
+            let file_name = Path::new(&model.path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string());
+            if fields.get("model_id").and_then(Value::as_str) == file_name.as_deref() {
+                fields.insert("model_id".into(), json!(model.id));
+            }
+            if let Some(Value::Array(variations)) = fields.get_mut("variations") {
+                for variation in variations.iter_mut().filter_map(Value::as_object_mut) {
+                    if variation.get("model_id").and_then(Value::as_str) == file_name.as_deref() {
+                        variation.insert("model_id".into(), json!(model.id));
+                    }
+                }
+            }
+            fields.insert("fallback_from".into(), json!(fallback_from));
+        }
+        if let Some(project_id) = &project_id {
+            record_ai_history(&state, project_id, &prompt, &ctx, &output).await;
+        }
+        Ok(output)
+    }.await;
+    result.map_err(|err| err.to_string())
+}
+
+const MAX_PERSONA_NAME_CHARS: usize = 60;
+const MAX_PERSONA_PROMPT_CHARS: usize = 4000;
+
+async fn fetch_persona(state: &AppState, persona_id: i64) -> AppResult<Persona> {
+    sqlx::query_as::<_, Persona>(
+        "SELECT id, name, system_prompt, created_at, updated_at FROM personas WHERE id = ?1",
+    )
+    .bind(persona_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::Message("Persona not found".into()))
+}
+
+#[tauri::command]
+pub async fn list_personas(state: State<'_, AppState>) -> Result<Vec<Persona>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let personas = sqlx::query_as::<_, Persona>(
+            "SELECT id, name, system_prompt, created_at, updated_at FROM personas ORDER BY name COLLATE NOCASE",
+        )
+        .fetch_all(&state.pool)
+        .await?;
+        Ok::<_, AppError>(personas)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SavePersonaRequest {
+    /// Updates this persona; omit to create a new one.
+    pub persona_id: Option<i64>,
+    pub name: String,
+    pub system_prompt: String,
+}
+
+/// Creates or updates a persona whose system prompt `generate_ai_scene` can
+/// use in place of the default instructions.
+#[tauri::command]
+pub async fn save_persona(
+    state: State<'_, AppState>,
+    payload: SavePersonaRequest,
+) -> Result<Persona, String> {
+    let result = async {
+        let user = require_session(&state).await?;
+        let name = payload.name.trim();
+        let system_prompt = payload.system_prompt.trim();
+        let mut errors = BTreeMap::new();
+        if name.is_empty() {
+            errors.insert("name", "Persona name is required".to_string());
+        } else if name.chars().count() > MAX_PERSONA_NAME_CHARS {
+            errors.insert(
+                "name",
+                format!("Persona names are limited to {MAX_PERSONA_NAME_CHARS} characters"),
+            );
+        }
+        if system_prompt.is_empty() {
+            errors.insert("system_prompt", "System prompt is required".to_string());
+        } else if system_prompt.chars().count() > MAX_PERSONA_PROMPT_CHARS {
+            errors.insert(
+                "system_prompt",
+                format!("System prompts are limited to {MAX_PERSONA_PROMPT_CHARS} characters"),
+            );
+        }
+        if !errors.is_empty() {
+            return Err(AppError::Validation(errors));
+        }
+
+        let taken: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM personas WHERE name = ?1 COLLATE NOCASE AND id IS NOT ?2",
+        )
+        .bind(name)
+        .bind(payload.persona_id)
+        .fetch_optional(&state.pool)
+        .await?;
+        if taken.is_some() {
+            return Err(AppError::Message(format!(
+                "A persona named '{name}' already exists"
+            )));
+        }
+
+        let persona = match payload.persona_id {
+            Some(persona_id) => sqlx::query_as::<_, Persona>(
+                r#"
+      UPDATE personas
+      SET name = ?1, system_prompt = ?2, updated_at = datetime('now')
+      WHERE id = ?3
+      RETURNING id, name, system_prompt, created_at, updated_at
+    "#,
+            )
+            .bind(name)
+            .bind(system_prompt)
+            .bind(persona_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or_else(|| AppError::Message("Persona not found".into()))?,
+            None => {
+                sqlx::query_as::<_, Persona>(
+                    r#"
+      INSERT INTO personas (name, system_prompt)
+      VALUES (?1, ?2)
+      RETURNING id, name, system_prompt, created_at, updated_at
+    "#,
+                )
+                .bind(name)
+                .bind(system_prompt)
+                .fetch_one(&state.pool)
+                .await?
+            }
+        };
+        record_audit(&state, &user, "save_persona", None, Some(&persona.name)).await;
+        Ok(persona)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeletePersonaRequest {
+    pub persona_id: i64,
+}
+
+#[tauri::command]
+pub async fn delete_persona(
+    state: State<'_, AppState>,
+    payload: DeletePersonaRequest,
+) -> Result<(), String> {
+    let result = async {
+        let user = require_session(&state).await?;
+        let persona = fetch_persona(&state, payload.persona_id).await?;
+        sqlx::query("DELETE FROM personas WHERE id = ?1")
+            .bind(persona.id)
+            .execute(&state.pool)
+            .await?;
+        record_audit(&state, &user, "delete_persona", None, Some(&persona.name)).await;
+        Ok::<_, AppError>(())
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+/// Characters of context kept per history entry, counted from the end since
+/// the text nearest the cursor shapes the generation most.
+const AI_HISTORY_CONTEXT_CHARS: usize = 2000;
+const DEFAULT_AI_HISTORY_LIMIT: u32 = 50;
+const MAX_AI_HISTORY_LIMIT: u32 = 500;
+
+/// Stores every variation that generated successfully, one entry each, so
+/// any of the drafts the writer saw can be re-inserted later. Failures are
+/// logged rather than returned so history never costs the writer a draft.
+async fn record_ai_history(
+    state: &AppState,
+    project_id: &str,
+    prompt: &str,
+    context: &str,
+    output: &Value,
+) {
+    fn generated(result: &Value) -> Option<(&str, Option<&str>)> {
+        let failed = result.get("error").is_some_and(|error| !error.is_null());
+        let response = result
+            .get("response")
+            .and_then(Value::as_str)
+            .filter(|response| !failed && !response.trim().is_empty())?;
+        Some((response, result.get("model_id").and_then(Value::as_str)))
+    }
+
+    let responses = match output.get("variations").and_then(Value::as_array) {
+        Some(variations) => variations.iter().filter_map(generated).collect::<Vec<_>>(),
+        None => generated(output).into_iter().collect(),
+    };
+    if responses.is_empty() {
+        return;
+    }
+
+    let context_length = context.chars().count();
+    let stored_context = context
+        .chars()
+        .skip(context_length.saturating_sub(AI_HISTORY_CONTEXT_CHARS))
+        .collect::<String>();
+    for (response, model_id) in responses {
+        let result = sqlx::query(
+            r#"
+      INSERT INTO ai_history (project_id, prompt, context, context_length, response, model_id)
+      VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+    "#,
+        )
+        .bind(project_id)
+        .bind(prompt)
+        .bind(&stored_context)
+        .bind(context_length as i64)
+        .bind(response)
+        .bind(model_id)
+        .execute(&state.pool)
+        .await;
+        if let Err(err) = result {
+            log::warn!("Failed to record AI history: {err}");
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListAiHistoryRequest {
+    pub project_id: String,
+    pub limit: Option<u32>,
+}
+
+#[tauri::command]
+pub async fn list_ai_history(
+    state: State<'_, AppState>,
+    payload: ListAiHistoryRequest,
+) -> Result<Vec<AiHistoryEntry>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        fetch_project_row(&state, &payload.project_id).await?;
+        let limit = payload
+            .limit
+            .unwrap_or(DEFAULT_AI_HISTORY_LIMIT)
+            .clamp(1, MAX_AI_HISTORY_LIMIT);
+
+        let entries = sqlx::query_as::<_, AiHistoryEntry>(
+            r#"
+      SELECT id, project_id, prompt, context, context_length, response, model_id, created_at
+      FROM ai_history
+      WHERE project_id = ?1
+      ORDER BY id DESC
+      LIMIT ?2
+    "#,
+        )
+        .bind(&payload.project_id)
+        .bind(limit)
+        .fetch_all(&state.pool)
+        .await?;
+        Ok::<_, AppError>(entries)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClearAiHistoryRequest {
+    pub project_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClearAiHistoryResponse {
+    pub removed: u64,
+}
+
+#[tauri::command]
+pub async fn clear_ai_history(
+    state: State<'_, AppState>,
+    payload: ClearAiHistoryRequest,
+) -> Result<ClearAiHistoryResponse, String> {
+    let result = async {
+        let user = require_session(&state).await?;
+        fetch_project_row(&state, &payload.project_id).await?;
+
+        let removed = sqlx::query("DELETE FROM ai_history WHERE project_id = ?1")
+            .bind(&payload.project_id)
+            .execute(&state.pool)
+            .await?
+            .rows_affected();
+        record_audit(
+            &state,
+            &user,
+            "clear_ai_history",
+            Some(&payload.project_id),
+            Some(&format!("{removed} entries")),
+        )
+        .await;
+        Ok::<_, AppError>(ClearAiHistoryResponse { removed })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InsertAiHistoryRequest {
+    pub project_id: String,
+    pub entry_id: i64,
+    pub file_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InsertAiHistoryResponse {
+    pub path: String,
+    pub snapshot: Option<String>,
+}
+
+/// Appends a past response to the end of a file, creating it if needed.
+#[tauri::command]
+pub async fn insert_ai_history_entry(
+    state: State<'_, AppState>,
+    payload: InsertAiHistoryRequest,
+) -> Result<InsertAiHistoryResponse, String> {
+    insert_ai_history_entry_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn insert_ai_history_entry_inner(
+    state: State<'_, AppState>,
+    payload: InsertAiHistoryRequest,
+) -> AppResult<InsertAiHistoryResponse> {
+    let user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let response: String =
+        sqlx::query_scalar("SELECT response FROM ai_history WHERE id = ?1 AND project_id = ?2")
+            .bind(payload.entry_id)
+            .bind(&payload.project_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or_else(|| AppError::Message("History entry not found".into()))?;
+
+    let base_path = PathBuf::from(project_row.base_path);
+    let target_path = resolve_project_path(&base_path, &payload.file_path)?;
+    if target_path.is_dir() {
+        return Err(AppError::Message(format!(
+            "'{}' is a folder",
+            payload.file_path
+        )));
+    }
+    let existing = if target_path.is_file() {
+        fs::read_to_string(&target_path)?
+    } else {
+        String::new()
+    };
+    let mut content = existing.trim_end().to_string();
+    if !content.is_empty() {
+        content.push_str("\n\n");
+    }
+    content.push_str(response.trim());
+    content.push('\n');
+
+    let settings = fetch_settings(&state).await?;
+    ensure_within_size_limit(&content, &settings)?;
+    let content = normalize_for_save(content, settings.normalize_unicode);
+
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let _write = state.pending_writes.read().await;
+    let snapshot = snapshot_file(
+        &base_path,
+        &target_path,
+        settings.snapshot_retention as usize,
+    )?;
+    write_atomic(&target_path, content.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    let relative = to_relative_string(&base_path, &target_path)?;
+    record_audit(
+        &state,
+        &user,
+        "insert_ai_history_entry",
+        Some(&payload.project_id),
+        Some(&relative),
+    )
+    .await;
+
+    Ok(InsertAiHistoryResponse {
+        path: relative,
+        snapshot: snapshot
+            .map(|snapshot| to_relative_string(&base_path, &snapshot))
+            .transpose()?,
+    })
+}
+
+struct LocalLanguageModel {
+    id: String,
+    path: String,
+    size_mb: u32,
+}
+
+/// Downloaded language models from the registry, with their on-disk paths.
+fn downloaded_language_models(inventory: &[Value]) -> Vec<LocalLanguageModel> {
+    default_models()
+        .into_iter()
+        .filter(|model| model.model_type == ModelType::LanguageModel)
+        .filter_map(|model| {
+            let entry = inventory
+                .iter()
+                .find(|entry| entry.get("identifier").and_then(Value::as_str) == Some(model.id))?;
+            let path = entry.get("path").and_then(Value::as_str)?;
+            Some(LocalLanguageModel {
+                id: model.id.to_string(),
+                path: path.to_string(),
+                size_mb: model.size_mb,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GenerateSceneInlineRequest {
+    pub before_cursor: String,
+    #[serde(default)]
+    pub after_cursor: String,
+    pub instruction: Option<String>,
+    pub api_key: Option<String>,
+}
+
+#[tauri::command]
+pub async fn generate_scene_inline(
+    state: State<'_, AppState>,
+    payload: GenerateSceneInlineRequest,
+) -> Result<InlineCompletion, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        if payload.before_cursor.trim().is_empty() && payload.after_cursor.trim().is_empty() {
+            return Err(AppError::Message(
+                "Write some text around the cursor before asking for a continuation".into(),
+            ));
+        }
+        let api_keys = fetch_settings(&state).await?.api_keys;
+        let api_key = resolve_api_key(&api_keys, OPENROUTER_KEY, payload.api_key);
+        let _permit = state.ml_permit().await?;
+        crate::ml_bridge::generate_scene_inline(
+            &payload.before_cursor,
+            &payload.after_cursor,
+            payload.instruction.as_deref().unwrap_or_default(),
+            api_key.as_deref(),
+        )
+        .await
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SummarizeSceneRequest {
+    pub project_id: String,
+    pub file_path: String,
+    pub api_key: Option<String>,
+    /// Only use the local model, even when an API key is configured.
+    #[serde(default)]
+    pub offline: bool,
+}
+
+#[tauri::command]
+pub async fn summarize_scene(
+    state: State<'_, AppState>,
+    payload: SummarizeSceneRequest,
+) -> Result<SceneSummary, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let content = load_project_file(&state, &payload.project_id, &payload.file_path).await?;
+        let body = strip_front_matter(&content);
+        let scene = body.trim();
+        if scene.is_empty() {
+            return Err(AppError::Message(
+                "This draft is empty; nothing to summarize".into(),
+            ));
+        }
+        let settings = fetch_settings(&state).await?;
+        let api_key = if payload.offline {
+            None
+        } else {
+            resolve_api_key(&settings.api_keys, OPENROUTER_KEY, payload.api_key)
+        };
+        let _permit = state.ml_permit().await?;
+        let inventory = crate::ml_bridge::fetch_model_inventory(&state.models_root)
+            .await
+            .unwrap_or_else(|err| {
+                log::warn!("Model inventory unavailable for summary: {err}");
+                Vec::new()
+            });
+        let local_model = downloaded_language_models(&inventory)
+            .into_iter()
+            .find(|model| model.id == settings.llm_model);
+        crate::ml_bridge::summarize_scene(
+            scene,
+            api_key.as_deref(),
+            local_model.as_ref().map(|model| model.path.as_str()),
+            payload.offline,
+        )
+        .await
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+const OUTLINE_FILE: &str = "outline.md";
+
+#[derive(Debug, Deserialize)]
+pub struct ExtractOutlineRequest {
+    pub project_id: String,
+    pub file_path: String,
+    pub api_key: Option<String>,
+    /// Only use the local model, even when an API key is configured.
+    #[serde(default)]
+    pub offline: bool,
+    /// Also write the outline to `outline.md` at the project root.
+    #[serde(default)]
+    pub write_file: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExtractOutlineResponse {
+    #[serde(flatten)]
+    pub outline: DraftOutline,
+    pub path: Option<String>,
+    pub snapshot: Option<String>,
+}
+
+/// Beats grouped under a heading per section, in the order the model gave.
+fn beats_markdown(source: &str, beats: &[OutlineBeat]) -> String {
+    let mut markdown = format!("# Outline\n\n_Extracted from `{source}`._\n");
+    let mut section = None;
+    for beat in beats {
+        if section != Some(beat.section.as_str()) {
+            section = Some(beat.section.as_str());
+            if !beat.section.is_empty() {
+                markdown.push_str(&format!("\n## {}\n", beat.section));
+            }
+            markdown.push('\n');
+        }
+        match (beat.beat.is_empty(), beat.summary.is_empty()) {
+            (false, false) => {
+                markdown.push_str(&format!("- **{}** — {}\n", beat.beat, beat.summary))
+            }
+            (false, true) => markdown.push_str(&format!("- **{}**\n", beat.beat)),
+            _ => markdown.push_str(&format!("- {}\n", beat.summary)),
+        }
+    }
+    markdown
+}
+
+#[tauri::command]
+pub async fn extract_outline_ai(
+    state: State<'_, AppState>,
+    payload: ExtractOutlineRequest,
+) -> Result<ExtractOutlineResponse, String> {
+    extract_outline_ai_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn extract_outline_ai_inner(
+    state: State<'_, AppState>,
+    payload: ExtractOutlineRequest,
+) -> AppResult<ExtractOutlineResponse> {
+    let user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    if payload.write_file {
+        ensure_unlocked(&project_row)?;
+    }
+    let content = load_project_file(&state, &payload.project_id, &payload.file_path).await?;
+    let body = strip_front_matter(&content);
+    let draft = body.trim();
+    if draft.is_empty() {
+        return Err(AppError::Message(
+            "This draft is empty; nothing to outline".into(),
+        ));
+    }
+
+    let structure = project_template(project_row.template_id.as_deref())
+        .map(|template| template.structure())
+        .unwrap_or_default();
+    let settings = fetch_settings(&state).await?;
+    let api_key = if payload.offline {
+        None
+    } else {
+        resolve_api_key(&settings.api_keys, OPENROUTER_KEY, payload.api_key)
+    };
+    let permit = state.ml_permit().await?;
+    let outline =
+        crate::ml_bridge::extract_outline(draft, &structure, api_key.as_deref(), payload.offline)
+            .await?;
+    drop(permit);
+
+    if !payload.write_file {
+        return Ok(ExtractOutlineResponse {
+            outline,
+            path: None,
+            snapshot: None,
+        });
+    }
+
+    let base_path = PathBuf::from(&project_row.base_path);
+    let target = resolve_project_path(&base_path, OUTLINE_FILE)?;
+    let markdown = beats_markdown(&payload.file_path, &outline.beats);
+    let _write = state.pending_writes.read().await;
+    let snapshot = snapshot_file(&base_path, &target, settings.snapshot_retention as usize)?;
+    write_atomic(&target, markdown.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+    record_audit(
+        &state,
+        &user,
+        "extract_outline_ai",
+        Some(&payload.project_id),
+        Some(OUTLINE_FILE),
+    )
+    .await;
+
+    Ok(ExtractOutlineResponse {
+        outline,
+        path: Some(to_relative_string(&base_path, &target)?),
+        snapshot: snapshot
+            .map(|snapshot| to_relative_string(&base_path, &snapshot))
+            .transpose()?,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EstimateLlmRequest {
+    pub prompt: String,
+    pub context: Option<String>,
+    pub model: Option<String>,
+    pub variations: Option<u8>,
+}
+
+#[tauri::command]
+pub async fn estimate_llm_request(
+    state: State<'_, AppState>,
+    payload: EstimateLlmRequest,
+) -> Result<LlmEstimate, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let variations = payload.variations.unwrap_or(1);
+        if !(1..=MAX_SCENE_VARIATIONS).contains(&variations) {
+            return Err(AppError::Message(format!(
+                "Variations must be between 1 and {MAX_SCENE_VARIATIONS}"
+            )));
+        }
+        let _permit = state.ml_permit().await?;
+        crate::ml_bridge::estimate_llm_request(
+            &payload.prompt,
+            payload.context.as_deref().unwrap_or_default(),
+            payload.model.as_deref(),
+            variations,
+        )
+        .await
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PhoneticHintRequest {
+    pub word: String,
+}
+
+#[tauri::command]
+pub async fn phonetic_hint(
+    state: State<'_, AppState>,
+    payload: PhoneticHintRequest,
+) -> Result<PhoneticHint, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let word = payload.word.trim();
+        if !word.chars().any(crate::language::is_tamil) {
+            return Err(AppError::Message(
+                "Phonetic hints require Tamil text".into(),
+            ));
+        }
+        let _permit = state.ml_permit().await?;
+        crate::ml_bridge::phonetic_hint(word).await
+    }
+    .await;
+
+    result.map_err(|err| err.to_string())
+}
+
+/// Candidates are single words or short phrases.
+const SPOKEN_CANDIDATE_MAX_CHARS: usize = 64;
+const SPOKEN_CANDIDATE_CACHE_SIZE: usize = 64;
+
+#[derive(Debug, Deserialize)]
+pub struct SpeakTransliterationRequest {
+    pub text: String,
+    pub voice: Option<String>,
+}
+
+/// Reads a Tamil transliteration candidate aloud so learners can hear it.
+#[tauri::command]
+pub async fn speak_transliteration(
+    state: State<'_, AppState>,
+    payload: SpeakTransliterationRequest,
+) -> Result<SpeechPreview, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let text = payload.text.trim().nfc().collect::<String>();
+        if !text.chars().any(crate::language::is_tamil) {
+            return Err(AppError::Message(
+                "Only Tamil candidates can be read aloud".into(),
+            ));
+        }
+        if text.chars().count() > SPOKEN_CANDIDATE_MAX_CHARS {
+            return Err(AppError::Message(format!(
+                "Candidates to read aloud are limited to {SPOKEN_CANDIDATE_MAX_CHARS} characters"
+            )));
+        }
+
+        let key = format!("{}:{text}", payload.voice.as_deref().unwrap_or_default());
+        if let Some(speech) = state.spoken_candidates.lock().await.get(&key) {
+            return Ok(speech.clone());
+        }
+        let _permit = state.ml_permit().await?;
+        let speech =
+            crate::ml_bridge::preview_speech(&text, "ta", payload.voice.as_deref()).await?;
+        let mut cache = state.spoken_candidates.lock().await;
+        if cache.len() >= SPOKEN_CANDIDATE_CACHE_SIZE {
+            cache.clear();
+        }
+        cache.insert(key, speech.clone());
+        Ok(speech)
+    }
+    .await;
+
+    result.map_err(|err| err.to_string())
+}
+
+const INPUT_SUGGESTION_MAX_PREFIX: usize = 32;
+const INPUT_SUGGESTION_MAX_LIMIT: u8 = 10;
+const INPUT_SUGGESTION_DEFAULT_LIMIT: u8 = 5;
+const INPUT_SUGGESTION_CACHE_SIZE: usize = 2048;
+
+#[derive(Debug, Deserialize)]
+pub struct InputSuggestionsRequest {
+    pub prefix: String,
+    pub limit: Option<u8>,
+}
+
+#[tauri::command]
+pub async fn input_suggestions(
+    state: State<'_, AppState>,
+    payload: InputSuggestionsRequest,
+) -> Result<Vec<InputSuggestion>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let prefix = payload.prefix.trim().to_lowercase();
+        if prefix.is_empty() {
+            return Ok(Vec::new());
+        }
+        if prefix.len() > INPUT_SUGGESTION_MAX_PREFIX {
+            return Err(AppError::Message(format!(
+                "Suggestion prefix is limited to {INPUT_SUGGESTION_MAX_PREFIX} letters"
+            )));
+        }
+        if !prefix.chars().all(|ch| ch.is_ascii_alphabetic()) {
+            return Err(AppError::Message(
+                "Suggestion prefix must contain only Latin letters".into(),
+            ));
+        }
+        let limit = payload
+            .limit
+            .unwrap_or(INPUT_SUGGESTION_DEFAULT_LIMIT)
+            .clamp(1, INPUT_SUGGESTION_MAX_LIMIT);
+
+        let key = format!("{limit}:{prefix}");
+        if let Some(suggestions) = state.input_suggestions.lock().await.get(&key) {
+            return Ok(suggestions.clone());
+        }
+        let suggestions = {
+            let _permit = state.ml_permit().await?;
+            crate::ml_bridge::input_suggestions(&prefix, limit).await?
+        };
+        let mut cache = state.input_suggestions.lock().await;
+        if cache.len() >= INPUT_SUGGESTION_CACHE_SIZE {
+            cache.clear();
+        }
+        cache.insert(key, suggestions.clone());
+        Ok(suggestions)
+    }
+    .await;
+
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DetectLanguageRequest {
+    pub text: String,
+}
+
+#[tauri::command]
+pub async fn detect_language(
+    state: State<'_, AppState>,
+    payload: DetectLanguageRequest,
+) -> Result<LanguageDetection, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        Ok::<_, AppError>(crate::language::detect_language(&payload.text))
+    }
+    .await;
+
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetFileLanguageRequest {
+    pub project_id: String,
+    pub file_path: String,
+    /// One of the project's languages; `None` removes the tag.
+    pub language: Option<String>,
+    /// Tag the file with the language detected from its body instead.
+    #[serde(default)]
+    pub detect: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetFileLanguageResponse {
+    pub path: String,
+    pub language: Option<String>,
+}
+
+/// Stores a `language` tag in a markdown file's front matter so the UI can
+/// pick fonts and TTS voices per file.
+#[tauri::command]
+pub async fn set_file_language(
+    state: State<'_, AppState>,
+    payload: SetFileLanguageRequest,
+) -> Result<SetFileLanguageResponse, String> {
+    set_file_language_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn set_file_language_inner(
+    state: State<'_, AppState>,
+    payload: SetFileLanguageRequest,
+) -> AppResult<SetFileLanguageResponse> {
+    let user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let project_languages: Vec<String> = serde_json::from_str(&project_row.languages)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let target_path = resolve_project_path(&base_path, &payload.file_path)?;
+    if !target_path.is_file() {
+        return Err(AppError::Message("File not found".into()));
+    }
+    if FileKind::from_path(&target_path) != FileKind::Markdown {
+        return Err(AppError::Message(
+            "Language tags can only be set on markdown files".into(),
+        ));
+    }
+    let content = fs::read_to_string(&target_path)?;
+
+    let language = if payload.detect {
+        let detection = crate::language::detect_language(&strip_front_matter(&content));
+        Some(detection.primary.ok_or_else(|| {
+            AppError::Message("Not enough text to detect the file's language".into())
+        })?)
+    } else {
+        payload
+            .language
+            .map(|language| language.trim().to_lowercase())
+            .filter(|language| !language.is_empty())
+    };
+    if let Some(language) = &language {
+        if !project_languages.contains(language) {
+            return Err(AppError::Message(format!(
+                "'{language}' is not one of this project's languages ({})",
+                project_languages.join(", ")
+            )));
+        }
+    }
+
+    let updated = set_front_matter_value(&content, "language", language.as_deref());
+    let relative = to_relative_string(&base_path, &target_path)?;
+    if updated != content {
+        let settings = fetch_settings(&state).await?;
+        let _write = state.pending_writes.read().await;
+        snapshot_file(
+            &base_path,
+            &target_path,
+            settings.snapshot_retention as usize,
+        )?;
+        write_atomic(&target_path, updated.as_bytes())?;
+        state.invalidate_disk_usage(&payload.project_id).await;
+
+        sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+            .bind(&payload.project_id)
+            .execute(&state.pool)
+            .await?;
+
+        let detail = format!("{relative}: {}", language.as_deref().unwrap_or("none"));
+        record_audit(
+            &state,
+            &user,
+            "set_file_language",
+            Some(&payload.project_id),
+            Some(&detail),
+        )
+        .await;
+    }
+
+    Ok(SetFileLanguageResponse {
+        path: relative,
+        language,
+    })
+}
+
+const MAX_STATUS_CHARS: usize = 32;
+
+#[derive(Debug, Deserialize)]
+pub struct TagFilesInDirectoryRequest {
+    pub project_id: String,
+    /// Relative folder to tag; empty for the whole project.
+    #[serde(default)]
+    pub directory: String,
+    /// Language to set; an empty string removes the tag.
+    pub language: Option<String>,
+    /// Status such as `draft` or `final`; an empty string removes the tag.
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagFilesInDirectoryResponse {
+    /// Files whose front matter changed.
+    pub tagged: usize,
+    /// Files that already carried the tags.
+    pub unchanged: usize,
+}
+
+/// Sets the `language` and/or `status` front matter tags on every markdown
+/// file under a folder, skipping exports.
+#[tauri::command]
+pub async fn tag_files_in_directory(
+    state: State<'_, AppState>,
+    payload: TagFilesInDirectoryRequest,
+) -> Result<TagFilesInDirectoryResponse, String> {
+    tag_files_in_directory_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn tag_files_in_directory_inner(
+    state: State<'_, AppState>,
+    payload: TagFilesInDirectoryRequest,
+) -> AppResult<TagFilesInDirectoryResponse> {
+    let user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let project_languages: Vec<String> = serde_json::from_str(&project_row.languages)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let directory = match payload.directory.trim() {
+        "" | "." | "/" => base_path.clone(),
+        directory => resolve_project_path(&base_path, directory)?,
+    };
+    if !directory.is_dir() {
+        return Err(AppError::Message("Folder not found".into()));
+    }
+    // The walk only skips hidden folders below the one it starts in, so a
+    // target such as `.snapshots` or `.trash` would be retagged wholesale.
+    let hidden = directory.strip_prefix(&base_path).is_ok_and(|relative| {
+        relative
+            .components()
+            .any(|component| component.as_os_str().to_string_lossy().starts_with('.'))
+    });
+    if hidden {
+        return Err(AppError::Message("Hidden folders cannot be tagged".into()));
+    }
+
+    let language = match payload.language.as_deref().map(str::trim) {
+        None => None,
+        Some("") => Some(None),
+        Some(language) => {
+            let code = crate::language::normalize_language_code(language)
+                .filter(|code| project_languages.iter().any(|known| known == code))
+                .ok_or_else(|| {
+                    AppError::Message(format!(
+                        "'{language}' is not one of this project's languages ({})",
+                        project_languages.join(", ")
+                    ))
+                })?;
+            Some(Some(code.to_string()))
+        }
+    };
+    let status = match payload.status.as_deref().map(str::trim) {
+        None => None,
+        Some("") => Some(None),
+        Some(status) => {
+            if status.chars().count() > MAX_STATUS_CHARS
+                || !status
+                    .chars()
+                    .all(|ch| ch.is_alphanumeric() || matches!(ch, ' ' | '-' | '_'))
+            {
+                return Err(AppError::Message(format!(
+                    "Statuses are up to {MAX_STATUS_CHARS} letters, digits, spaces, hyphens or underscores"
+                )));
+            }
+            Some(Some(status.to_lowercase()))
+        }
+    };
+    let tags = [("language", language), ("status", status)]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+        .collect::<Vec<_>>();
+    if tags.is_empty() {
+        return Err(AppError::Message(
+            "Choose a language or status to tag".into(),
+        ));
+    }
+
+    let exports = base_path.join("exports");
+    let files = project_markdown_files(&directory)
+        .into_iter()
+        .filter(|path| !path.starts_with(&exports))
+        .collect::<Vec<_>>();
+
+    let settings = fetch_settings(&state).await?;
+    let mut tagged = 0;
+    {
+        let _write = state.pending_writes.read().await;
+        for path in &files {
+            let content = fs::read_to_string(path)?;
+            let updated = tags.iter().fold(content.clone(), |content, (key, value)| {
+                set_front_matter_value(&content, key, value.as_deref())
+            });
+            if updated != content {
+                snapshot_file(&base_path, path, settings.snapshot_retention as usize)?;
+                write_atomic(path, updated.as_bytes())?;
+                tagged += 1;
+            }
+        }
+    }
+
+    if tagged > 0 {
+        state.invalidate_disk_usage(&payload.project_id).await;
+        sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+            .bind(&payload.project_id)
+            .execute(&state.pool)
+            .await?;
+
+        let folder = match to_relative_string(&base_path, &directory)? {
+            folder if folder.is_empty() => ".".to_string(),
+            folder => folder,
+        };
+        let detail = format!(
+            "{folder}: {} ({tagged} files)",
+            tags.iter()
+                .map(|(key, value)| format!("{key}={}", value.as_deref().unwrap_or("none")))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        record_audit(
+            &state,
+            &user,
+            "tag_files_in_directory",
+            Some(&payload.project_id),
+            Some(&detail),
+        )
+        .await;
+    }
+
+    Ok(TagFilesInDirectoryResponse {
+        tagged,
+        unchanged: files.len() - tagged,
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListProjectsRequest {
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+#[tauri::command]
+pub async fn list_projects(
+    state: State<'_, AppState>,
+    payload: Option<ListProjectsRequest>,
+) -> Result<Vec<ProjectRecord>, String> {
+    if let Err(err) = require_session(&state).await {
+        return Err(err.to_string());
+    }
+    let include_archived = payload.unwrap_or_default().include_archived;
+    let rows = sqlx::query_as::<_, ProjectRow>(
+    r#"
+      SELECT id, name, slug, project_type, languages, description, template_id, base_path, archived, locked, cover_path, created_at, updated_at
+      FROM projects
+      WHERE ?1 OR archived = 0
+      ORDER BY datetime(updated_at) DESC
+    "#,
+  )
+  .bind(include_archived)
+  .fetch_all(&state.pool)
+  .await
+  .map_err(|err| err.to_string())?;
+
+    let mut projects = Vec::with_capacity(rows.len());
+    for row in rows {
+        match ProjectRecord::try_from(row) {
+            Ok(project) => projects.push(project),
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    Ok(projects)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchProjectsRequest {
+    pub query: String,
+    #[serde(default)]
+    pub include_archived: bool,
+    pub limit: Option<usize>,
+}
+
+#[tauri::command]
+pub async fn search_projects(
+    state: State<'_, AppState>,
+    payload: SearchProjectsRequest,
+) -> Result<Vec<ProjectRecord>, String> {
+    search_projects_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn search_projects_inner(
+    state: State<'_, AppState>,
+    payload: SearchProjectsRequest,
+) -> AppResult<Vec<ProjectRecord>> {
+    let _user = require_session(&state).await?;
+
+    let query = payload.query.trim().to_lowercase();
+    let compact: String = query.chars().filter(|ch| !ch.is_whitespace()).collect();
+    if compact.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Every fuzzy match is also a subsequence match, so LIKE '%a%b%c%' narrows
+    // the rows before scoring.
+    let mut pattern = String::from("%");
+    for ch in compact.chars() {
+        if matches!(ch, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(ch);
+        pattern.push('%');
+    }
+
+    let rows = sqlx::query_as::<_, ProjectRow>(
+        r#"
+      SELECT id, name, slug, project_type, languages, description, template_id, base_path, archived, locked, cover_path, created_at, updated_at
+      FROM projects
+      WHERE (?1 OR archived = 0)
+        AND (replace(name, ' ', '') LIKE ?2 ESCAPE '\' OR slug LIKE ?2 ESCAPE '\')
+      ORDER BY datetime(updated_at) DESC
+    "#,
+    )
+    .bind(payload.include_archived)
+    .bind(&pattern)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut scored = Vec::with_capacity(rows.len());
+    for row in rows {
+        let score = [row.name.to_lowercase(), row.slug.clone()]
+            .iter()
+            .filter_map(|candidate| fuzzy_score(&query, &compact, candidate))
+            .max();
+        if let Some(score) = score {
+            scored.push((score, ProjectRecord::try_from(row)?));
+        }
+    }
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    let limit = payload.limit.unwrap_or(usize::MAX);
+    Ok(scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, project)| project)
+        .collect())
+}
+
+/// Ranks exact, prefix and substring matches above scattered subsequence
+/// matches; tighter subsequences score higher than spread-out ones. Lengths
+/// and positions count characters, so Tamil names rank like Latin ones.
+fn fuzzy_score(query: &str, compact: &str, candidate: &str) -> Option<i64> {
+    let candidate = candidate.chars().collect::<Vec<_>>();
+    let query = query.chars().collect::<Vec<_>>();
+    let compact = compact.chars().collect::<Vec<_>>();
+    if candidate == query {
+        return Some(4000);
+    }
+    if candidate.starts_with(&query) {
+        return Some(3000 - candidate.len() as i64);
+    }
+    if let Some(position) =
+        (1..candidate.len()).find(|start| candidate[*start..].starts_with(&query))
+    {
+        return Some(2000 - position as i64);
+    }
+
+    let mut wanted = compact.iter().peekable();
+    let mut first = None;
+    let mut last = 0;
+    for (index, ch) in candidate.iter().enumerate() {
+        if wanted.peek() == Some(&ch) {
+            wanted.next();
+            first.get_or_insert(index);
+            last = index;
+        }
+    }
+    if wanted.peek().is_some() {
+        return None;
+    }
+    let span = (last - first.unwrap_or(0) + 1) as i64;
+    Some(1000 - (span - compact.len() as i64))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ArchiveProjectRequest {
+    pub project_id: String,
+}
+
+#[tauri::command]
+pub async fn archive_project(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: ArchiveProjectRequest,
+) -> Result<ProjectRecord, String> {
+    set_project_archived(app, state, payload.project_id, true)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn unarchive_project(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: ArchiveProjectRequest,
+) -> Result<ProjectRecord, String> {
+    set_project_archived(app, state, payload.project_id, false)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn set_project_archived(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    project_id: String,
+    archived: bool,
+) -> AppResult<ProjectRecord> {
+    let user = require_session(&state).await?;
+
+    let result = sqlx::query(
+        "UPDATE projects SET archived = ?1, updated_at = datetime('now') WHERE id = ?2",
+    )
+    .bind(archived)
+    .bind(&project_id)
+    .execute(&state.pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::Message("Project not found".into()));
+    }
+
+    let project = ProjectRecord::try_from(fetch_project_row(&state, &project_id).await?)?;
+
+    let event = if archived {
+        "project-archived"
+    } else {
+        "project-unarchived"
+    };
+    let command = if archived {
+        "archive_project"
+    } else {
+        "unarchive_project"
+    };
+    record_audit(&state, &user, command, Some(&project_id), None).await;
+    app.emit(event, &project)
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+
+    Ok(project)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetProjectLockRequest {
+    pub project_id: String,
+    pub locked: bool,
+}
+
+#[tauri::command]
+pub async fn set_project_lock(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: SetProjectLockRequest,
+) -> Result<ProjectRecord, String> {
+    set_project_lock_inner(app, state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn set_project_lock_inner(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: SetProjectLockRequest,
+) -> AppResult<ProjectRecord> {
+    let user = require_session(&state).await?;
+
+    let result =
+        sqlx::query("UPDATE projects SET locked = ?1, updated_at = datetime('now') WHERE id = ?2")
+            .bind(payload.locked)
+            .bind(&payload.project_id)
+            .execute(&state.pool)
+            .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::Message("Project not found".into()));
+    }
+
+    let project = ProjectRecord::try_from(fetch_project_row(&state, &payload.project_id).await?)?;
+    let (event, command) = if payload.locked {
+        ("project-locked", "lock_project")
+    } else {
+        ("project-unlocked", "unlock_project")
+    };
+    record_audit(&state, &user, command, Some(&payload.project_id), None).await;
+    app.emit(event, &project)
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+
+    Ok(project)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProjectMetadataRequest {
+    pub project_id: String,
+    pub name: Option<String>,
+    /// An empty description clears it.
+    pub description: Option<String>,
+    /// Codes or names such as `Tamil`; stored as ISO 639-1 codes.
+    pub languages: Option<Vec<String>>,
+}
+
+/// Edits a project's display details. The slug and folder keep their
+/// original name.
+#[tauri::command]
+pub async fn update_project_metadata(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: UpdateProjectMetadataRequest,
+) -> Result<ProjectRecord, AppError> {
+    update_project_metadata_inner(app, state, payload).await
+}
+
+async fn update_project_metadata_inner(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: UpdateProjectMetadataRequest,
+) -> AppResult<ProjectRecord> {
+    let user = require_session(&state).await?;
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+
+    let mut errors = BTreeMap::new();
+    let name = payload.name.as_deref().map(str::trim);
+    if name.is_some_and(str::is_empty) {
+        errors.insert("name", "Project name is required".to_string());
+    }
+    let languages = match payload.languages.as_deref().map(validate_language_codes) {
+        Some(Ok(languages)) => Some(serde_json::to_string(&languages)?),
+        Some(Err(err)) => {
+            errors.insert("languages", err.to_string());
+            None
+        }
+        None => None,
+    };
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+    let description = payload
+        .description
+        .map(|description| description.trim().to_string());
+
+    sqlx::query(
+        r#"
+      UPDATE projects
+      SET name = COALESCE(?1, name),
+          description = CASE WHEN ?2 IS NULL THEN description ELSE NULLIF(?2, '') END,
+          languages = COALESCE(?3, languages),
+          updated_at = datetime('now')
+      WHERE id = ?4
+    "#,
+    )
+    .bind(name)
+    .bind(&description)
+    .bind(&languages)
+    .bind(&payload.project_id)
+    .execute(&state.pool)
+    .await?;
+
+    let project = ProjectRecord::try_from(fetch_project_row(&state, &payload.project_id).await?)?;
+    let changed = [
+        name.map(|_| "name"),
+        description.as_ref().map(|_| "description"),
+        languages.as_ref().map(|_| "languages"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(", ");
+    record_audit(
+        &state,
+        &user,
+        "update_project_metadata",
+        Some(&payload.project_id),
+        Some(&changed),
+    )
+    .await;
+    app.emit("project-updated", &project)
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+
+    Ok(project)
+}
+
+#[derive(Debug, Serialize)]
+pub struct LanguageOption {
+    pub code: &'static str,
+    pub name: &'static str,
+}
+
+/// Languages a project can be written in, for the language picker. Distinct
+/// from `supported_languages`, which lists what a speech model can hear.
+#[tauri::command]
+pub async fn supported_project_languages(
+    state: State<'_, AppState>,
+) -> Result<Vec<LanguageOption>, String> {
+    require_session(&state)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(SUPPORTED_LANGUAGES
+        .iter()
+        .map(|(code, name)| LanguageOption { code, name })
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteProjectsRequest {
+    pub project_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeleteProjectResult {
+    pub project_id: String,
+    pub deleted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeleteProjectsResponse {
+    pub results: Vec<DeleteProjectResult>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ProjectsDeletedEvent {
+    pub project_ids: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn delete_projects(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: DeleteProjectsRequest,
+) -> Result<DeleteProjectsResponse, String> {
+    delete_projects_inner(app, state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn delete_projects_inner(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: DeleteProjectsRequest,
+) -> AppResult<DeleteProjectsResponse> {
+    let user = require_session(&state).await?;
+
+    let mut results = Vec::with_capacity(payload.project_ids.len());
+    let mut deleted_ids = Vec::new();
+    for project_id in payload.project_ids {
+        match delete_project_by_id(&state, &project_id).await {
+            Ok(()) => {
+                record_audit(&state, &user, "delete_project", Some(&project_id), None).await;
+                deleted_ids.push(project_id.clone());
+                results.push(DeleteProjectResult {
+                    project_id,
+                    deleted: true,
+                    error: None,
+                });
+            }
+            Err(err) => {
+                log::warn!("Failed to delete project {project_id}: {err}");
+                results.push(DeleteProjectResult {
+                    project_id,
+                    deleted: false,
+                    error: Some(err.to_string()),
+                });
+            }
+        }
+    }
+
+    if !deleted_ids.is_empty() {
+        app.emit(
+            "projects-deleted",
+            ProjectsDeletedEvent {
+                project_ids: deleted_ids,
+            },
+        )
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+    }
+
+    Ok(DeleteProjectsResponse { results })
+}
+
+async fn delete_project_by_id(state: &AppState, project_id: &str) -> AppResult<()> {
+    let project_row = fetch_project_row(state, project_id).await?;
+    let base_path = PathBuf::from(project_row.base_path);
+
+    state.watchers.lock().await.remove(project_id);
+
+    let storage_root = state.storage_root.clone();
+    let owner = project_id.to_string();
+    tokio::task::spawn_blocking(move || remove_project_dir(&storage_root, &base_path, &owner))
+        .await
+        .map_err(|err| AppError::Anyhow(err.into()))??;
+
+    sqlx::query("DELETE FROM projects WHERE id = ?1")
+        .bind(project_id)
+        .execute(&state.pool)
+        .await?;
+
+    state.invalidate_disk_usage(project_id).await;
+    Ok(())
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PruneOrphanedProjectsRequest {
+    /// Remove the orphaned rows; without it the command only reports them.
+    #[serde(default)]
+    pub delete: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrphanedProject {
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+    pub base_path: String,
+    pub archived: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PruneOrphanedProjectsResponse {
+    pub orphans: Vec<OrphanedProject>,
+    /// Rows deleted, with their scripts, AI history and reminders.
+    pub removed: usize,
+}
+
+/// Finds projects whose folder was deleted or moved outside the app. Folders
+/// that cannot be checked, such as on a permission error, are not counted.
+/// A folder on an unplugged drive looks deleted, so deleting is opt-in.
+#[tauri::command]
+pub async fn prune_orphaned_projects(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: Option<PruneOrphanedProjectsRequest>,
+) -> Result<PruneOrphanedProjectsResponse, String> {
+    prune_orphaned_projects_inner(app, state, payload.unwrap_or_default())
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn prune_orphaned_projects_inner(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: PruneOrphanedProjectsRequest,
+) -> AppResult<PruneOrphanedProjectsResponse> {
+    let user = require_session(&state).await?;
+
+    let rows = sqlx::query_as::<_, ProjectRow>(
+        r#"
+      SELECT id, name, slug, project_type, languages, description, template_id, base_path, archived, locked, cover_path, created_at, updated_at
+      FROM projects
+      ORDER BY name
+    "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+    let orphans = rows
+        .into_iter()
+        .filter(|row| matches!(Path::new(&row.base_path).try_exists(), Ok(false)))
+        .map(|row| OrphanedProject {
+            id: row.id,
+            name: row.name,
+            slug: row.slug,
+            base_path: row.base_path,
+            archived: row.archived,
+        })
+        .collect::<Vec<_>>();
+
+    let mut removed_ids = Vec::new();
+    if payload.delete {
+        for orphan in &orphans {
+            // The folder may have come back since the scan.
+            if Path::new(&orphan.base_path).exists() {
+                continue;
+            }
+            state.watchers.lock().await.remove(&orphan.id);
+            sqlx::query("DELETE FROM projects WHERE id = ?1")
+                .bind(&orphan.id)
+                .execute(&state.pool)
+                .await?;
+            state.invalidate_disk_usage(&orphan.id).await;
+            record_audit(
+                &state,
+                &user,
+                "prune_orphaned_project",
+                Some(&orphan.id),
+                Some(&orphan.base_path),
+            )
+            .await;
+            removed_ids.push(orphan.id.clone());
+        }
+    }
+
+    let removed = removed_ids.len();
+    if !removed_ids.is_empty() {
+        app.emit(
+            "projects-deleted",
+            ProjectsDeletedEvent {
+                project_ids: removed_ids,
+            },
+        )
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+    }
+
+    Ok(PruneOrphanedProjectsResponse { orphans, removed })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RelocateProjectRequest {
+    pub project_id: String,
+    pub target_dir: String,
+}
+
+#[tauri::command]
+pub async fn relocate_project(
+    state: State<'_, AppState>,
+    payload: RelocateProjectRequest,
+) -> Result<ProjectRecord, String> {
+    relocate_project_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn relocate_project_inner(
+    state: State<'_, AppState>,
+    payload: RelocateProjectRequest,
+) -> AppResult<ProjectRecord> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let source = PathBuf::from(&project_row.base_path);
+    let target_dir = PathBuf::from(&payload.target_dir);
+    if !source.is_dir() {
+        return Err(AppError::Message("Project folder does not exist".into()));
+    }
+    if !target_dir.is_dir() {
+        return Err(AppError::Message("Target folder does not exist".into()));
+    }
+    let target = target_dir.canonicalize()?.join(&project_row.slug);
+    if target.starts_with(source.canonicalize()?) {
+        return Err(AppError::Message(
+            "Cannot move a project into itself".into(),
+        ));
+    }
+    if target.exists() {
+        return Err(AppError::Message(format!(
+            "{} already exists",
+            target.display()
+        )));
+    }
+
+    state.watchers.lock().await.remove(&payload.project_id);
+
+    tokio::task::spawn_blocking({
+        let source = source.clone();
+        let target = target.clone();
+        let project_id = payload.project_id.clone();
+        move || -> AppResult<()> {
+            ensure_writable(&target_dir)?;
+            let required = measure_disk_usage(&source)?.total_bytes;
+            ensure_free_space(&target_dir, required)?;
+
+            let copied = copy_dir_all(&source, &target)
+                .and_then(|_| verify_copy(&source, &target))
+                .and_then(|_| write_project_marker(&target, &project_id));
+            if copied.is_err() {
+                let _ = fs::remove_dir_all(&target);
+            }
+            copied
+        }
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
+
+    let updated = sqlx::query(
+        "UPDATE projects SET base_path = ?1, updated_at = datetime('now') WHERE id = ?2",
+    )
+    .bind(target.to_string_lossy().to_string())
+    .bind(&payload.project_id)
+    .execute(&state.pool)
+    .await;
+    if let Err(err) = updated {
+        let _ = fs::remove_dir_all(&target);
+        return Err(err.into());
+    }
+
+    if let Err(err) = fs::remove_dir_all(&source) {
+        log::warn!(
+            "Project moved but the old folder {} could not be removed: {err}",
+            source.display()
+        );
+    }
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    Ok(ProjectRecord::try_from(
+        fetch_project_row(&state, &payload.project_id).await?,
+    )?)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyProjectRequest {
+    pub project_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectVerification {
+    pub project_id: String,
+    pub healthy: bool,
+    pub issues: Vec<ProjectIssue>,
+}
+
+#[tauri::command]
+pub async fn verify_project(
+    state: State<'_, AppState>,
+    payload: VerifyProjectRequest,
+) -> Result<ProjectVerification, String> {
+    verify_project_inner(state, payload.project_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn verify_project_inner(
+    state: State<'_, AppState>,
+    project_id: String,
+) -> AppResult<ProjectVerification> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &project_id).await?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let template = project_template(project_row.template_id.as_deref());
+
+    let issues =
+        tokio::task::spawn_blocking(move || verify_project_layout(&base_path, template.as_ref()))
+            .await
+            .map_err(|err| AppError::Anyhow(err.into()))?;
+
+    Ok(ProjectVerification {
+        project_id,
+        healthy: issues.is_empty(),
+        issues,
+    })
+}
+
+#[tauri::command]
+pub async fn repair_project(
+    state: State<'_, AppState>,
+    payload: VerifyProjectRequest,
+) -> Result<ProjectVerification, String> {
+    repair_project_inner(state, payload.project_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn repair_project_inner(
+    state: State<'_, AppState>,
+    project_id: String,
+) -> AppResult<ProjectVerification> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let template = project_template(project_row.template_id.as_deref());
+
+    let issues = tokio::task::spawn_blocking(move || -> AppResult<Vec<ProjectIssue>> {
+        if base_path.exists() && !base_path.is_dir() {
+            return Err(AppError::Message(
+                "Project path is a file; move it aside before repairing".into(),
+            ));
+        }
+        fs::create_dir_all(&base_path)?;
+        let title = match &template {
+            Some(template) => {
+                ensure_template_scaffold(&base_path, template)?;
+                template.title
+            }
+            None => "Project",
+        };
+        write_markdown_placeholder(&base_path.join("README.md"), title, README_PLACEHOLDER)?;
+        Ok(verify_project_layout(&base_path, template.as_ref()))
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
+
+    state.invalidate_disk_usage(&project_id).await;
+
+    Ok(ProjectVerification {
+        project_id,
+        healthy: issues.is_empty(),
+        issues,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegenerateReadmeRequest {
+    pub project_id: String,
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RegenerateReadmeResponse {
+    pub path: String,
+    pub snapshot: Option<String>,
+}
+
+#[tauri::command]
+pub async fn regenerate_readme(
+    state: State<'_, AppState>,
+    payload: RegenerateReadmeRequest,
+) -> Result<RegenerateReadmeResponse, String> {
+    regenerate_readme_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn regenerate_readme_inner(
+    state: State<'_, AppState>,
+    payload: RegenerateReadmeRequest,
+) -> AppResult<RegenerateReadmeResponse> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(&project_row.base_path);
+    let template = project_template(project_row.template_id.as_deref())
+        .ok_or_else(|| AppError::Message("Project has no known template".into()))?;
+    let readme_path = base_path.join("README.md");
+
+    let content = format!(
+        "# {}\n\n{README_PLACEHOLDER}\n\n{}",
+        template.title,
+        template.readme_body()
+    );
+    // Content written by create_project is not a user edit.
+    let untouched = [
+        String::new(),
+        format!("# {}\n\n{README_PLACEHOLDER}\n", template.title),
+        content.clone(),
+    ];
+
+    let existing = match fs::read_to_string(&readme_path) {
+        Ok(existing) => Some(existing),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+    let edited = existing
+        .as_ref()
+        .is_some_and(|existing| !untouched.contains(existing));
+    if edited && !payload.force {
+        return Err(AppError::Message(
+            "README.md has been edited; regenerate with force to replace it".into(),
+        ));
+    }
+
+    let retention = fetch_settings(&state).await?.snapshot_retention as usize;
+    let snapshot = if edited {
+        snapshot_file(&base_path, &readme_path, retention)?
+    } else {
+        None
+    };
+    fs::create_dir_all(&base_path)?;
+    write_atomic(&readme_path, content.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    Ok(RegenerateReadmeResponse {
+        path: to_relative_string(&base_path, &readme_path)?,
+        snapshot: snapshot
+            .map(|snapshot| to_relative_string(&base_path, &snapshot))
+            .transpose()?,
+    })
+}
+
+#[tauri::command]
+pub async fn list_transformations(
+    state: State<'_, AppState>,
+) -> Result<&'static [Transformation], String> {
+    require_session(&state)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(TRANSFORMATIONS)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApplyTransformationRequest {
+    pub project_id: String,
+    pub transformation: String,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileTransformation {
+    pub path: String,
+    pub changes: usize,
+    pub snapshot: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApplyTransformationResponse {
+    pub transformation: String,
+    pub dry_run: bool,
+    /// Only files the transformation changed.
+    pub files: Vec<FileTransformation>,
+    pub total_changes: usize,
+}
+
+/// Runs an allowlisted transformation over every markdown file in the
+/// project except generated exports, snapshotting each file it rewrites.
+#[tauri::command]
+pub async fn apply_transformation(
+    state: State<'_, AppState>,
+    payload: ApplyTransformationRequest,
+) -> Result<ApplyTransformationResponse, String> {
+    apply_transformation_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn apply_transformation_inner(
+    state: State<'_, AppState>,
+    payload: ApplyTransformationRequest,
+) -> AppResult<ApplyTransformationResponse> {
+    let user = require_session(&state).await?;
+    let name = payload.transformation.trim().to_string();
+    if !TRANSFORMATIONS
+        .iter()
+        .any(|transformation| transformation.name == name)
+    {
+        return Err(AppError::Message(format!(
+            "Unknown transformation '{name}'"
+        )));
+    }
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    if !payload.dry_run {
+        ensure_unlocked(&project_row)?;
+    }
+    let base_path = PathBuf::from(&project_row.base_path);
+    let retention = fetch_settings(&state).await?.snapshot_retention as usize;
+    let dry_run = payload.dry_run;
+
+    let _write = state.pending_writes.read().await;
+    let files = tokio::task::spawn_blocking({
+        let base_path = base_path.clone();
+        let name = name.clone();
+        move || -> AppResult<Vec<FileTransformation>> {
+            let mut files = Vec::new();
+            for path in project_markdown_files(&base_path) {
+                let relative = to_relative_string(&base_path, &path)?;
+                if relative.starts_with("exports/") {
+                    continue;
+                }
+                let content = fs::read_to_string(&path)?;
+                let Some((updated, changes)) =
+                    crate::transform::apply_transformation(&name, &content)
+                else {
+                    continue;
+                };
+                if changes == 0 || updated == content {
+                    continue;
+                }
+                let snapshot = if dry_run {
+                    None
+                } else {
+                    let snapshot = snapshot_file(&base_path, &path, retention)?;
+                    write_atomic(&path, updated.as_bytes())?;
+                    snapshot
+                        .map(|snapshot| to_relative_string(&base_path, &snapshot))
+                        .transpose()?
+                };
+                files.push(FileTransformation {
+                    path: relative,
+                    changes,
+                    snapshot,
+                });
+            }
+            Ok(files)
+        }
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
+
+    let total_changes = files.iter().map(|file| file.changes).sum();
+    if !dry_run && !files.is_empty() {
+        state.invalidate_disk_usage(&payload.project_id).await;
+        sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+            .bind(&payload.project_id)
+            .execute(&state.pool)
+            .await?;
+        let detail = format!("{name}: {total_changes} changes in {} files", files.len());
+        record_audit(
+            &state,
+            &user,
+            "apply_transformation",
+            Some(&payload.project_id),
+            Some(&detail),
+        )
+        .await;
+    }
+
+    Ok(ApplyTransformationResponse {
+        transformation: name,
+        dry_run,
+        files,
+        total_changes,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DraftVsTemplateRequest {
+    pub project_id: String,
+    pub file_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DraftVsTemplateResponse {
+    pub template_id: String,
+    pub template_title: String,
+    #[serde(flatten)]
+    pub comparison: HeadingComparison,
+    /// Share of template sections the draft has, from 0 to 1.
+    pub coverage: f32,
+}
+
+/// Compares a draft's headings with the sections of the project template's
+/// starting markdown.
+#[tauri::command]
+pub async fn draft_vs_template(
+    state: State<'_, AppState>,
+    payload: DraftVsTemplateRequest,
+) -> Result<DraftVsTemplateResponse, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        let template = project_template(project_row.template_id.as_deref())
+            .ok_or_else(|| AppError::Message("Project has no known template".into()))?;
+        let default_markdown = template
+            .metadata
+            .get("default_markdown")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                AppError::Message(format!("{} has no starting draft", template.title))
+            })?;
+
+        let content = load_project_file(&state, &payload.project_id, &payload.file_path).await?;
+        let comparison = compare_headings(
+            &extract_headings(default_markdown),
+            &extract_headings(&strip_front_matter(&content)),
+        );
+        let expected = comparison.present.len() + comparison.missing.len();
+        let coverage = if expected == 0 {
+            1.0
+        } else {
+            (comparison.present.len() as f32 / expected as f32 * 100.0).round() / 100.0
+        };
+        Ok::<_, AppError>(DraftVsTemplateResponse {
+            template_id: template.id.to_string(),
+            template_title: template.title.to_string(),
+            comparison,
+            coverage,
+        })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+fn project_template(template_id: Option<&str>) -> Option<ProjectTemplate> {
+    let template_id = template_id?;
+    default_templates()
+        .into_iter()
+        .find(|template| template.id == template_id)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModelInventoryResponse {
+    pub models: Vec<Value>,
+    pub options: Vec<ModelAvailability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inventory_error: Option<String>,
+}
+
+#[tauri::command]
+pub async fn refresh_model_inventory(
+    state: State<'_, AppState>,
+) -> Result<ModelInventoryResponse, String> {
+    if let Err(err) = require_session(&state).await {
+        return Err(err.to_string());
+    }
+    let _permit = match state.ml_permit().await {
+        Ok(permit) => permit,
+        Err(err) => return Err(err.to_string()),
+    };
+    let (models, inventory_error) =
+        match crate::ml_bridge::fetch_model_inventory(&state.models_root).await {
+            Ok(models) => (models, None),
+            Err(err) => {
+                log::warn!("Model inventory unavailable: {err}");
+                (Vec::new(), Some(err.to_string()))
+            }
+        };
+    let options = merge_model_inventory(&models);
+
+    Ok(ModelInventoryResponse {
+        models,
+        options,
+        inventory_error,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DownloadModelRequest {
+    pub model_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadModelResponse {
+    pub model_id: String,
+    pub path: Option<String>,
+    pub options: Vec<ModelAvailability>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ModelDownloadProgress {
+    pub model_id: String,
+    pub bytes_downloaded: u64,
+    pub total_bytes: Option<u64>,
+}
+
+#[tauri::command]
+pub async fn download_model(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: DownloadModelRequest,
+) -> Result<DownloadModelResponse, String> {
+    download_model_inner(app, state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn download_model_inner(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: DownloadModelRequest,
+) -> AppResult<DownloadModelResponse> {
+    let _user = require_session(&state).await?;
+    if !default_models()
+        .iter()
+        .any(|model| model.id == payload.model_id)
+    {
+        return Err(AppError::Message(format!(
+            "Unknown model '{}'",
+            payload.model_id
+        )));
+    }
+
+    let _permit = state.ml_permit().await?;
+    let progress_app = app.clone();
+    let model_id = payload.model_id.clone();
+    let result =
+        crate::ml_bridge::download_model(&state.models_root, &payload.model_id, move |event| {
+            let progress = ModelDownloadProgress {
+                model_id: model_id.clone(),
+                bytes_downloaded: event.get("downloaded").and_then(Value::as_u64).unwrap_or(0),
+                total_bytes: event
+                    .get("total")
+                    .and_then(Value::as_u64)
+                    .filter(|total| *total > 0),
+            };
+            if let Err(err) = progress_app.emit("model-download-progress", progress) {
+                log::warn!("Failed to emit download progress: {err}");
+            }
+        })
+        .await?;
+    state.tts_voices.lock().await.remove(&payload.model_id);
+
+    let inventory = crate::ml_bridge::fetch_model_inventory(&state.models_root)
+        .await
+        .unwrap_or_else(|err| {
+            log::warn!("Model inventory unavailable after download: {err}");
+            Vec::new()
+        });
+
+    Ok(DownloadModelResponse {
+        model_id: payload.model_id,
+        path: result
+            .get("path")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        options: merge_model_inventory(&inventory),
+    })
+}
+
+const DEFAULT_BENCHMARK_RUNS: u8 = 3;
+const MAX_BENCHMARK_RUNS: u8 = 10;
+
+#[derive(Debug, Deserialize)]
+pub struct BenchmarkModelRequest {
+    pub model_id: String,
+    pub runs: Option<u8>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ModelBenchmarkProgress {
+    pub model_id: String,
+    pub run: u32,
+    pub total: u32,
+    pub latency_ms: f64,
+}
+
+/// Times a fixed sample through a catalogued model so users can compare
+/// models on their own hardware. Emits `model-benchmark-progress` per run.
+#[tauri::command]
+pub async fn benchmark_model(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: BenchmarkModelRequest,
+) -> Result<ModelBenchmark, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        if !default_models()
+            .iter()
+            .any(|model| model.id == payload.model_id)
+        {
+            return Err(AppError::Message(format!(
+                "Unknown model '{}'",
+                payload.model_id
+            )));
+        }
+        let runs = payload.runs.unwrap_or(DEFAULT_BENCHMARK_RUNS);
+        if !(1..=MAX_BENCHMARK_RUNS).contains(&runs) {
+            return Err(AppError::Message(format!(
+                "Runs must be between 1 and {MAX_BENCHMARK_RUNS}"
+            )));
+        }
+
+        let model_id = payload.model_id.clone();
+        let _permit = state.ml_permit().await?;
+        crate::ml_bridge::benchmark_model(
+            &state.models_root,
+            &payload.model_id,
+            runs,
+            move |event| {
+                let progress = ModelBenchmarkProgress {
+                    model_id: model_id.clone(),
+                    run: event.get("run").and_then(Value::as_u64).unwrap_or(0) as u32,
+                    total: event.get("total").and_then(Value::as_u64).unwrap_or(0) as u32,
+                    latency_ms: event
+                        .get("latency_ms")
+                        .and_then(Value::as_f64)
+                        .unwrap_or(0.0),
+                };
+                if let Err(err) = app.emit("model-benchmark-progress", progress) {
+                    log::warn!("Failed to emit benchmark progress: {err}");
+                }
+            },
+        )
+        .await
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Serialize)]
+pub struct CancelModelDownloadResponse {
+    pub cancelled: bool,
+}
+
+#[tauri::command]
+pub async fn cancel_model_download(
+    state: State<'_, AppState>,
+    payload: DownloadModelRequest,
+) -> Result<CancelModelDownloadResponse, String> {
+    if let Err(err) = require_session(&state).await {
+        return Err(err.to_string());
+    }
+    let key = crate::ml_bridge::download_key(&payload.model_id);
+    Ok(CancelModelDownloadResponse {
+        cancelled: crate::ml_bridge::cancel_tracked_process(&key),
+    })
+}
+
+#[tauri::command]
+pub async fn list_project_files(
+    state: State<'_, AppState>,
+    payload: ListProjectFilesRequest,
+) -> Result<ProjectFilesResponse, String> {
+    if let Err(err) = require_session(&state).await {
+        return Err(err.to_string());
+    }
+
+    let project_row = fetch_project_row(&state, &payload.project_id)
+        .await
+        .map_err(|err| err.to_string())?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let entries = build_directory_entries(&base_path, &base_path, 0, payload.include_kinds)
+        .map_err(|err| err.to_string())?;
+    Ok(ProjectFilesResponse { files: entries })
+}
+
+const DEFAULT_FILE_QUERY_LIMIT: usize = 50;
+const MAX_FILE_QUERY_LIMIT: usize = 500;
+const MAX_FILE_QUERY_CHARS: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct QueryProjectFilesRequest {
+    pub project_id: String,
+    /// A glob (`*`, `**`, `?`) or, without wildcards, a substring of the path.
+    /// Globs without a `/` are matched against the file name alone.
+    pub query: Option<String>,
+    /// Extensions to keep, with or without the leading dot.
+    pub extensions: Option<Vec<String>>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QueryProjectFilesResponse {
+    pub paths: Vec<String>,
+    /// Matches before pagination.
+    pub total: usize,
+}
+
+/// Paths matching `query`, in path order for globs. Substring matches rank
+/// names starting with the query first, then names containing it, then any
+/// other path containing it.
+fn filter_file_paths(paths: Vec<String>, query: &str) -> Vec<String> {
+    if query.contains(['*', '?']) {
+        return paths
+            .into_iter()
+            .filter(|path| {
+                let subject = if query.contains('/') {
+                    path.as_str()
+                } else {
+                    path.rsplit('/').next().unwrap_or(path)
+                };
+                glob_match(query, subject)
+            })
+            .collect();
+    }
+
+    let needle = query.to_lowercase();
+    let mut ranked = paths
+        .into_iter()
+        .filter_map(|path| {
+            let lower = path.to_lowercase();
+            let name = lower.rsplit('/').next().unwrap_or(&lower);
+            let rank = if name.starts_with(&needle) {
+                0
+            } else if name.contains(&needle) {
+                1
+            } else if lower.contains(&needle) {
+                2
+            } else {
+                return None;
+            };
+            Some((rank, path))
+        })
+        .collect::<Vec<_>>();
+    ranked.sort();
+    ranked.into_iter().map(|(_, path)| path).collect()
+}
+
+#[tauri::command]
+pub async fn query_project_files(
+    state: State<'_, AppState>,
+    payload: QueryProjectFilesRequest,
+) -> Result<QueryProjectFilesResponse, String> {
+    query_project_files_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn query_project_files_inner(
+    state: State<'_, AppState>,
+    payload: QueryProjectFilesRequest,
+) -> AppResult<QueryProjectFilesResponse> {
+    let _user = require_session(&state).await?;
+
+    let query = payload
+        .query
+        .map(|query| query.trim().replace('\\', "/"))
+        .filter(|query| !query.is_empty());
+    if query
+        .as_ref()
+        .is_some_and(|query| query.chars().count() > MAX_FILE_QUERY_CHARS)
+    {
+        return Err(AppError::Message(format!(
+            "File queries are limited to {MAX_FILE_QUERY_CHARS} characters"
+        )));
+    }
+    let extensions = payload.extensions.map(|extensions| {
+        extensions
+            .iter()
+            .map(|extension| extension.trim().trim_start_matches('.').to_lowercase())
+            .filter(|extension| !extension.is_empty())
+            .collect::<Vec<_>>()
+    });
+    let offset = payload.offset.unwrap_or(0);
+    let limit = payload
+        .limit
+        .unwrap_or(DEFAULT_FILE_QUERY_LIMIT)
+        .clamp(1, MAX_FILE_QUERY_LIMIT);
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let matched = tokio::task::spawn_blocking(move || -> AppResult<Vec<String>> {
+        let mut paths =
+            flatten_file_paths(&build_directory_entries(&base_path, &base_path, 0, false)?)
+                .into_iter()
+                .collect::<Vec<_>>();
+        if let Some(extensions) = &extensions {
+            paths.retain(|path| {
+                Path::new(path)
+                    .extension()
+                    .map(|extension| extension.to_string_lossy().to_lowercase())
+                    .is_some_and(|extension| extensions.contains(&extension))
+            });
+        }
+        Ok(match query {
+            Some(query) => filter_file_paths(paths, &query),
+            None => paths,
+        })
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
+
+    let total = matched.len();
+    Ok(QueryProjectFilesResponse {
+        paths: matched.into_iter().skip(offset).take(limit).collect(),
+        total,
+    })
+}
+
+#[tauri::command]
+pub async fn load_markdown_file(
+    state: State<'_, AppState>,
+    payload: LoadMarkdownRequest,
+) -> Result<LoadMarkdownResponse, String> {
+    if let Err(err) = require_session(&state).await {
+        return Err(err.to_string());
+    }
+
+    let content = load_project_file(&state, &payload.project_id, &payload.file_path)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(LoadMarkdownResponse { content })
+}
+
+const DEFAULT_CHUNK_BYTES: u64 = 256 * 1024;
+const MAX_CHUNK_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct LoadFileChunkRequest {
+    pub project_id: String,
+    pub file_path: String,
+    #[serde(default)]
+    pub offset: u64,
+    pub length: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoadFileChunkResponse {
+    pub content: String,
+    /// Where the chunk actually starts, after moving off a split character.
+    pub offset: u64,
+    /// Offset to request next.
+    pub next_offset: u64,
+    pub total_bytes: u64,
+    pub has_more: bool,
+}
+
+#[tauri::command]
+pub async fn load_file_chunk(
+    state: State<'_, AppState>,
+    payload: LoadFileChunkRequest,
+) -> Result<LoadFileChunkResponse, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        let base_path = PathBuf::from(project_row.base_path);
+        let target_path = resolve_project_path(&base_path, &payload.file_path)?;
+        if matches!(
+            FileKind::from_path(&target_path),
+            FileKind::Image | FileKind::Audio
+        ) {
+            return Err(AppError::Message(format!(
+                "{} is not a text file and cannot be opened in the editor",
+                payload.file_path
+            )));
+        }
+        if !target_path.is_file() {
+            return Err(AppError::Message(format!(
+                "{} does not exist",
+                payload.file_path
+            )));
+        }
+        let length = payload
+            .length
+            .unwrap_or(DEFAULT_CHUNK_BYTES)
+            .clamp(1, MAX_CHUNK_BYTES);
+
+        let chunk = tokio::task::spawn_blocking(move || {
+            read_text_chunk(&target_path, payload.offset, length)
+        })
+        .await
+        .map_err(|err| AppError::Anyhow(err.into()))??;
+        Ok::<_, AppError>(LoadFileChunkResponse {
+            content: chunk.content,
+            offset: chunk.start,
+            next_offset: chunk.end,
+            total_bytes: chunk.total_bytes,
+            has_more: chunk.end < chunk.total_bytes,
+        })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn save_markdown_file(
+    state: State<'_, AppState>,
+    payload: SaveMarkdownRequest,
+) -> Result<SaveMarkdownResponse, String> {
+    let user = require_session(&state)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id)
+        .await
+        .map_err(|err| err.to_string())?;
+    ensure_unlocked(&project_row).map_err(|err| err.to_string())?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let target_path =
+        resolve_project_path(&base_path, &payload.file_path).map_err(|err| err.to_string())?;
+
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+
+    let settings = fetch_settings(&state)
+        .await
+        .map_err(|err| err.to_string())?;
+    ensure_within_size_limit(&payload.content, &settings).map_err(|err| err.to_string())?;
+    let content = normalize_for_save(payload.content, settings.normalize_unicode);
+
+    let _write = state.pending_writes.read().await;
+    write_atomic(&target_path, content.as_bytes()).map_err(|err| err.to_string())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let relative = to_relative_string(&base_path, &target_path).map_err(|err| err.to_string())?;
+    record_audit(
+        &state,
+        &user,
+        "save_markdown_file",
+        Some(&payload.project_id),
+        Some(&relative),
+    )
+    .await;
+
+    Ok(SaveMarkdownResponse { path: relative })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateScratchFileRequest {
+    pub project_id: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateScratchFileResponse {
+    pub path: String,
+}
+
+#[tauri::command]
+pub async fn create_scratch_file(
+    state: State<'_, AppState>,
+    payload: CreateScratchFileRequest,
+) -> Result<CreateScratchFileResponse, String> {
+    create_scratch_file_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn create_scratch_file_inner(
+    state: State<'_, AppState>,
+    payload: CreateScratchFileRequest,
+) -> AppResult<CreateScratchFileResponse> {
+    let user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let scratch_dir = resolve_project_path(&base_path, SCRATCH_DIR)?;
+    fs::create_dir_all(&scratch_dir)?;
+
+    let settings = fetch_settings(&state).await?;
+    ensure_within_size_limit(&payload.content, &settings)?;
+    let content = normalize_for_save(payload.content, settings.normalize_unicode);
+
+    let _write = state.pending_writes.read().await;
+    let target = timestamped_path(&scratch_dir, "scratch", "md");
+    write_atomic(&target, content.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    let relative = to_relative_string(&base_path, &target)?;
+    record_audit(
+        &state,
+        &user,
+        "create_scratch_file",
+        Some(&payload.project_id),
+        Some(&relative),
+    )
+    .await;
+
+    Ok(CreateScratchFileResponse { path: relative })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClearMarkdownRequest {
+    pub project_id: String,
+    pub file_path: String,
+    #[serde(default)]
+    pub keep_heading: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClearMarkdownResponse {
+    pub path: String,
+    pub snapshot: Option<String>,
+}
+
+#[tauri::command]
+pub async fn clear_markdown_file(
+    state: State<'_, AppState>,
+    payload: ClearMarkdownRequest,
+) -> Result<ClearMarkdownResponse, String> {
+    clear_markdown_file_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn clear_markdown_file_inner(
+    state: State<'_, AppState>,
+    payload: ClearMarkdownRequest,
+) -> AppResult<ClearMarkdownResponse> {
+    let user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let target_path = resolve_project_path(&base_path, &payload.file_path)?;
+    if !target_path.is_file() {
+        return Err(AppError::Message("Draft not found".into()));
+    }
+
+    let content = if payload.keep_heading {
+        let existing = fs::read_to_string(&target_path)?;
+        extract_headings(&existing)
+            .first()
+            .map(|heading| format!("{} {}\n", "#".repeat(heading.level as usize), heading.text))
+            .unwrap_or_default()
+    } else {
+        String::new()
+    };
+
+    let retention = fetch_settings(&state).await?.snapshot_retention as usize;
+    let _write = state.pending_writes.read().await;
+    let snapshot = snapshot_file(&base_path, &target_path, retention)?;
+    write_atomic(&target_path, content.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    record_audit(
+        &state,
+        &user,
+        "clear_markdown_file",
+        Some(&payload.project_id),
+        Some(&payload.file_path),
+    )
+    .await;
+
+    Ok(ClearMarkdownResponse {
+        path: to_relative_string(&base_path, &target_path)?,
+        snapshot: snapshot
+            .map(|snapshot| to_relative_string(&base_path, &snapshot))
+            .transpose()?,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListSceneVersionsRequest {
+    pub project_id: String,
+    /// The canonical scene, e.g. `drafts/scene1.md`.
+    pub scene_path: String,
+}
+
+#[tauri::command]
+pub async fn list_scene_versions(
+    state: State<'_, AppState>,
+    payload: ListSceneVersionsRequest,
+) -> Result<Vec<SceneVersion>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        let base_path = PathBuf::from(project_row.base_path);
+        let scene_path = resolve_project_path(&base_path, &payload.scene_path)?;
+        scene_versions(&base_path, &scene_path)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PromoteSceneVersionRequest {
+    pub project_id: String,
+    pub scene_path: String,
+    /// One of the non-canonical paths returned by `list_scene_versions`.
+    pub version_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PromoteSceneVersionResponse {
+    pub path: String,
+    pub snapshot: Option<String>,
+}
+
+#[tauri::command]
+pub async fn promote_scene_version(
+    state: State<'_, AppState>,
+    payload: PromoteSceneVersionRequest,
+) -> Result<PromoteSceneVersionResponse, String> {
+    promote_scene_version_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn promote_scene_version_inner(
+    state: State<'_, AppState>,
+    payload: PromoteSceneVersionRequest,
+) -> AppResult<PromoteSceneVersionResponse> {
+    let user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let scene_path = resolve_project_path(&base_path, &payload.scene_path)?;
+    let version_path = resolve_project_path(&base_path, &payload.version_path)?;
+    let version_relative = to_relative_string(&base_path, &version_path)?;
+    if !scene_versions(&base_path, &scene_path)?
+        .iter()
+        .any(|version| !version.canonical && version.path == version_relative)
+    {
+        return Err(AppError::Message(format!(
+            "{version_relative} is not a version of {}",
+            payload.scene_path
+        )));
+    }
+
+    // The version file is left in place so promoting is never destructive.
+    let content = fs::read(&version_path)?;
+    let retention = fetch_settings(&state).await?.snapshot_retention as usize;
+    let _write = state.pending_writes.read().await;
+    let snapshot = snapshot_file(&base_path, &scene_path, retention)?;
+    write_atomic(&scene_path, &content)?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    let path = to_relative_string(&base_path, &scene_path)?;
+    let detail = format!("{version_relative} -> {path}");
+    record_audit(
+        &state,
+        &user,
+        "promote_scene_version",
+        Some(&payload.project_id),
+        Some(&detail),
+    )
+    .await;
+
+    Ok(PromoteSceneVersionResponse {
+        path,
+        snapshot: snapshot
+            .map(|snapshot| to_relative_string(&base_path, &snapshot))
+            .transpose()?,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteProjectFileRequest {
+    pub project_id: String,
+    pub file_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeleteProjectFileResponse {
+    pub trash_id: String,
+}
+
+#[tauri::command]
+pub async fn delete_project_file(
+    state: State<'_, AppState>,
+    payload: DeleteProjectFileRequest,
+) -> Result<DeleteProjectFileResponse, String> {
+    let result = async {
+        let user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        ensure_unlocked(&project_row)?;
+        let base_path = PathBuf::from(project_row.base_path);
+        let target_path = resolve_project_path(&base_path, &payload.file_path)?;
+        if !target_path.is_file() {
+            return Err(AppError::Message("File not found".into()));
+        }
+        let relative = to_relative_string(&base_path, &target_path)?;
+        if relative.starts_with(&format!("{TRASH_DIR}/")) {
+            return Err(AppError::Message("File is already in the trash".into()));
+        }
+
+        let _write = state.pending_writes.read().await;
+        let trash_id = move_to_trash(&base_path, &target_path)?;
+        state.invalidate_disk_usage(&payload.project_id).await;
+
+        sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+            .bind(&payload.project_id)
+            .execute(&state.pool)
+            .await?;
+        record_audit(
+            &state,
+            &user,
+            "delete_project_file",
+            Some(&payload.project_id),
+            Some(&relative),
+        )
+        .await;
+
+        Ok(DeleteProjectFileResponse { trash_id })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrashRequest {
+    pub project_id: String,
+}
+
+#[tauri::command]
+pub async fn list_trashed_files(
+    state: State<'_, AppState>,
+    payload: TrashRequest,
+) -> Result<Vec<TrashedFile>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        list_trash(Path::new(&project_row.base_path))
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreTrashedFileRequest {
+    pub project_id: String,
+    pub trash_id: String,
+    /// Restore somewhere else, e.g. when the original path is taken again.
+    pub target_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestoreTrashedFileResponse {
+    pub path: String,
+}
+
+#[tauri::command]
+pub async fn restore_trashed_file(
+    state: State<'_, AppState>,
+    payload: RestoreTrashedFileRequest,
+) -> Result<RestoreTrashedFileResponse, String> {
+    let result = async {
+        let user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        ensure_unlocked(&project_row)?;
+        let base_path = PathBuf::from(project_row.base_path);
+        let relative = match payload.target_path {
+            Some(target_path) => target_path,
+            None => trashed_original_path(&base_path, &payload.trash_id)?,
+        };
+        let target_path = resolve_project_path(&base_path, &relative)?;
+        let relative = to_relative_string(&base_path, &target_path)?;
+        if relative.starts_with(&format!("{TRASH_DIR}/")) {
+            return Err(AppError::Message("Cannot restore into the trash".into()));
+        }
+
+        let _write = state.pending_writes.read().await;
+        restore_from_trash(&base_path, &payload.trash_id, &target_path)?;
+        state.invalidate_disk_usage(&payload.project_id).await;
+
+        sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+            .bind(&payload.project_id)
+            .execute(&state.pool)
+            .await?;
+        record_audit(
+            &state,
+            &user,
+            "restore_trashed_file",
+            Some(&payload.project_id),
+            Some(&relative),
+        )
+        .await;
+
+        Ok::<_, AppError>(RestoreTrashedFileResponse { path: relative })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmptyTrashResponse {
+    pub removed: usize,
+    pub freed_bytes: u64,
+}
+
+#[tauri::command]
+pub async fn empty_trash(
+    state: State<'_, AppState>,
+    payload: TrashRequest,
+) -> Result<EmptyTrashResponse, String> {
+    let result = async {
+        let user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        ensure_unlocked(&project_row)?;
+        let base_path = PathBuf::from(project_row.base_path);
+
+        let _write = state.pending_writes.read().await;
+        let (removed, freed_bytes) =
+            tokio::task::spawn_blocking(move || crate::filesystem::empty_trash(&base_path))
+                .await
+                .map_err(|err| AppError::Anyhow(err.into()))??;
+        state.invalidate_disk_usage(&payload.project_id).await;
+        if removed > 0 {
+            sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+                .bind(&payload.project_id)
+                .execute(&state.pool)
+                .await?;
+        }
+        record_audit(
+            &state,
+            &user,
+            "empty_trash",
+            Some(&payload.project_id),
+            None,
+        )
+        .await;
+
+        Ok::<_, AppError>(EmptyTrashResponse {
+            removed,
+            freed_bytes,
+        })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+fn copy_asset_into(base_path: &Path, source_path: &Path, target_dir: &str) -> AppResult<PathBuf> {
+    if !source_path.exists() {
+        return Err(AppError::Message("Selected file does not exist".into()));
+    }
+
+    let target_dir = resolve_project_path(base_path, target_dir)?;
+    fs::create_dir_all(&target_dir)?;
+
+    let original_name = source_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::Message("Invalid source file".into()))?;
+    let mut sanitized = sanitize(&original_name);
+    if sanitized.is_empty() {
+        sanitized = "asset".into();
+    }
+
+    let mut candidate = target_dir.join(&sanitized);
+    if candidate.exists() {
+        let mut counter = 1usize;
+        let stem = candidate
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "asset".into());
+        let extension = candidate
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_string());
+
+        loop {
+            let new_name = if let Some(ext) = &extension {
+                format!("{}-{}.{}", stem, counter, ext)
+            } else {
+                format!("{}-{}", stem, counter)
+            };
+            candidate = target_dir.join(&new_name);
+            if !candidate.exists() {
+                break;
+            }
+            counter += 1;
+        }
+    }
+
+    fs::copy(source_path, &candidate)?;
+    Ok(candidate)
+}
+
+const MAX_COVER_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct SetProjectCoverRequest {
+    pub project_id: String,
+    pub source: Option<String>,
+}
+
+#[tauri::command]
+pub async fn set_project_cover(
+    state: State<'_, AppState>,
+    payload: SetProjectCoverRequest,
+) -> Result<ProjectRecord, String> {
+    set_project_cover_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn set_project_cover_inner(
+    state: State<'_, AppState>,
+    payload: SetProjectCoverRequest,
+) -> AppResult<ProjectRecord> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let cover_path = match &payload.source {
+        Some(source) => {
+            let source_path = PathBuf::from(source);
+            if FileKind::from_path(&source_path) != FileKind::Image {
+                return Err(AppError::Message(
+                    "Cover must be an image (PNG, JPEG, GIF, WebP, SVG or BMP)".into(),
+                ));
+            }
+            let size = fs::metadata(&source_path)
+                .map_err(|_| AppError::Message("Selected file does not exist".into()))?
+                .len();
+            if size > MAX_COVER_BYTES {
+                return Err(AppError::Message(format!(
+                    "Cover image is {} MB; the limit is {} MB",
+                    size.div_ceil(1024 * 1024),
+                    MAX_COVER_BYTES / (1024 * 1024)
+                )));
+            }
+
+            let base_path = PathBuf::from(&project_row.base_path);
+            let copied = copy_asset_into(&base_path, &source_path, "assets/cover")?;
+            state.invalidate_disk_usage(&payload.project_id).await;
+            Some(to_relative_string(&base_path, &copied)?)
+        }
+        None => None,
+    };
+
+    sqlx::query("UPDATE projects SET cover_path = ?1, updated_at = datetime('now') WHERE id = ?2")
+        .bind(&cover_path)
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    // The old cover was copied in by this command, so nothing else refers to it.
+    if let Some(previous) = project_row
+        .cover_path
+        .as_deref()
+        .filter(|previous| Some(*previous) != cover_path.as_deref())
+    {
+        let base_path = PathBuf::from(&project_row.base_path);
+        let removed = resolve_project_path(&base_path, previous)
+            .and_then(|path| fs::remove_file(path).map_err(AppError::from));
+        match removed {
+            Ok(()) => state.invalidate_disk_usage(&payload.project_id).await,
+            Err(AppError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => log::warn!("Failed to remove old cover {previous}: {err}"),
+        }
+    }
+
+    Ok(ProjectRecord::try_from(
+        fetch_project_row(&state, &payload.project_id).await?,
+    )?)
+}
+
+#[tauri::command]
+pub async fn copy_project_asset(
+    state: State<'_, AppState>,
+    payload: CopyAssetRequest,
+) -> Result<CopyAssetResponse, String> {
+    if let Err(err) = require_session(&state).await {
+        return Err(err.to_string());
+    }
+
+    let project_row = fetch_project_row(&state, &payload.project_id)
+        .await
+        .map_err(|err| err.to_string())?;
+    ensure_unlocked(&project_row).map_err(|err| err.to_string())?;
+    let base_path = PathBuf::from(project_row.base_path);
+
+    let source_path = PathBuf::from(&payload.source);
+    let target_dir_relative = payload.target_dir.unwrap_or_else(|| "assets/images".into());
+    let candidate = copy_asset_into(&base_path, &source_path, &target_dir_relative)
+        .map_err(|err| err.to_string())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    let relative = to_relative_string(&base_path, &candidate).map_err(|err| err.to_string())?;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(CopyAssetResponse {
+        relative_path: relative,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportSubtitlesRequest {
+    pub project_id: String,
+    /// Absolute path to an `.srt` or `.vtt` file outside the project.
+    pub source_path: String,
+    /// Project-relative markdown path; `drafts/<subtitle name>.md` when omitted.
+    pub target_path: Option<String>,
+    #[serde(default)]
+    pub timestamps: TimestampStyle,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportSubtitlesResponse {
+    pub path: String,
+    pub cues: usize,
+}
+
+#[tauri::command]
+pub async fn import_subtitles(
+    state: State<'_, AppState>,
+    payload: ImportSubtitlesRequest,
+) -> Result<ImportSubtitlesResponse, String> {
+    import_subtitles_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn import_subtitles_inner(
+    state: State<'_, AppState>,
+    payload: ImportSubtitlesRequest,
+) -> AppResult<ImportSubtitlesResponse> {
+    let user = require_session(&state).await?;
+
+    let source = PathBuf::from(&payload.source_path);
+    let format = SubtitleFormat::from_path(&source)
+        .ok_or_else(|| AppError::Message("Subtitles must be an .srt or .vtt file".into()))?;
+    if !source.is_file() {
+        return Err(AppError::Message("Selected file does not exist".into()));
+    }
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let target_relative = match payload.target_path {
+        Some(path) => path,
+        None => {
+            let name = sanitize(&stem);
+            let name = if name.is_empty() {
+                "transcript".into()
+            } else {
+                name
+            };
+            format!("drafts/{name}.md")
+        }
+    };
+    let target = resolve_project_path(&base_path, &target_relative)?;
+    if FileKind::from_path(&target) != FileKind::Markdown {
+        return Err(AppError::Message(
+            "Transcripts must be saved as markdown".into(),
+        ));
+    }
+    if target.exists() {
+        return Err(AppError::Message(format!(
+            "A file already exists at {target_relative}"
+        )));
+    }
+
+    let content = fs::read(&source)?;
+    let content = String::from_utf8(content)
+        .map_err(|_| AppError::Message("Subtitle file is not valid UTF-8 text".into()))?;
+    let cues = parse_subtitles(&content, format)?;
+    if cues.is_empty() {
+        return Err(AppError::Message("Subtitle file has no cues".into()));
+    }
+
+    let settings = fetch_settings(&state).await?;
+    let markdown = subtitles_to_markdown(&stem, &cues, payload.timestamps);
+    ensure_within_size_limit(&markdown, &settings)?;
+    let markdown = normalize_for_save(markdown, settings.normalize_unicode);
+
+    let _write = state.pending_writes.read().await;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&target, markdown.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    let relative = to_relative_string(&base_path, &target)?;
+    record_audit(
+        &state,
+        &user,
+        "import_subtitles",
+        Some(&payload.project_id),
+        Some(&relative),
+    )
+    .await;
+
+    Ok(ImportSubtitlesResponse {
+        path: relative,
+        cues: cues.len(),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportPlaintextScriptRequest {
+    pub project_id: String,
+    pub content: String,
+    pub title: Option<String>,
+    /// Project-relative markdown path; `drafts/<title>.md` when omitted.
+    pub target_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportPlaintextScriptResponse {
+    pub path: String,
+    pub content: String,
+    pub report: StructureReport,
+}
+
+#[tauri::command]
+pub async fn import_plaintext_script(
+    state: State<'_, AppState>,
+    payload: ImportPlaintextScriptRequest,
+) -> Result<ImportPlaintextScriptResponse, String> {
+    import_plaintext_script_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn import_plaintext_script_inner(
+    state: State<'_, AppState>,
+    payload: ImportPlaintextScriptRequest,
+) -> AppResult<ImportPlaintextScriptResponse> {
+    let user = require_session(&state).await?;
+    if payload.content.trim().is_empty() {
+        return Err(AppError::Message("Paste a script to import".into()));
+    }
+    let title = payload
+        .title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| "Imported script".into());
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let target_relative = match payload.target_path {
+        Some(path) => path,
+        None => {
+            let name = sanitize(&title);
+            let name = if name.is_empty() {
+                "imported-script".into()
+            } else {
+                name
+            };
+            format!("drafts/{name}.md")
+        }
+    };
+    let target = resolve_project_path(&base_path, &target_relative)?;
+    if FileKind::from_path(&target) != FileKind::Markdown {
+        return Err(AppError::Message(
+            "Imported scripts must be saved as markdown".into(),
+        ));
+    }
+    if target.exists() {
+        return Err(AppError::Message(format!(
+            "A file already exists at {target_relative}"
+        )));
+    }
+
+    let settings = fetch_settings(&state).await?;
+    let StructuredScript { markdown, report } = structure_plaintext(&title, &payload.content);
+    ensure_within_size_limit(&markdown, &settings)?;
+    let markdown = normalize_for_save(markdown, settings.normalize_unicode);
+
+    let _write = state.pending_writes.read().await;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&target, markdown.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    let relative = to_relative_string(&base_path, &target)?;
+    record_audit(
+        &state,
+        &user,
+        "import_plaintext_script",
+        Some(&payload.project_id),
+        Some(&relative),
+    )
+    .await;
+
+    Ok(ImportPlaintextScriptResponse {
+        path: relative,
+        content: markdown,
+        report,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportFountainRequest {
+    pub project_id: String,
+    /// Absolute path to a `.fountain` file outside the project.
+    pub source_path: String,
+    /// Project-relative markdown path; `drafts/<file name>.md` when omitted.
+    pub target_path: Option<String>,
+    /// Replace an existing draft, e.g. with a collaborator's edits. The old
+    /// content is snapshotted first.
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportFountainResponse {
+    pub path: String,
+    pub content: String,
+    pub report: FountainReport,
+    pub snapshot: Option<String>,
+}
+
+#[tauri::command]
+pub async fn import_fountain(
+    state: State<'_, AppState>,
+    payload: ImportFountainRequest,
+) -> Result<ImportFountainResponse, String> {
+    import_fountain_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn import_fountain_inner(
+    state: State<'_, AppState>,
+    payload: ImportFountainRequest,
+) -> AppResult<ImportFountainResponse> {
+    let user = require_session(&state).await?;
+
+    let source = PathBuf::from(&payload.source_path);
+    let is_fountain = source
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("fountain"));
+    if !is_fountain {
+        return Err(AppError::Message("Select a .fountain file".into()));
+    }
+    if !source.is_file() {
+        return Err(AppError::Message("Selected file does not exist".into()));
+    }
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let target_relative = match payload.target_path {
+        Some(path) => path,
+        None => {
+            let name = sanitize(&stem);
+            let name = if name.is_empty() {
+                "imported-script".into()
+            } else {
+                name
+            };
+            format!("drafts/{name}.md")
+        }
+    };
+    let target = resolve_project_path(&base_path, &target_relative)?;
+    if FileKind::from_path(&target) != FileKind::Markdown {
+        return Err(AppError::Message(
+            "Imported scripts must be saved as markdown".into(),
+        ));
+    }
+    if target.exists() && !payload.overwrite {
+        return Err(AppError::Message(format!(
+            "A file already exists at {target_relative}"
+        )));
+    }
+
+    let content = String::from_utf8(fs::read(&source)?)
+        .map_err(|_| AppError::Message("Fountain file is not valid UTF-8 text".into()))?;
+    if content.trim().is_empty() {
+        return Err(AppError::Message("Fountain file is empty".into()));
+    }
+
+    let settings = fetch_settings(&state).await?;
+    let FountainImport { markdown, report } = fountain_to_markdown(&stem, &content);
+    ensure_within_size_limit(&markdown, &settings)?;
+    let markdown = normalize_for_save(markdown, settings.normalize_unicode);
+
+    let _write = state.pending_writes.read().await;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let snapshot = snapshot_file(&base_path, &target, settings.snapshot_retention as usize)?
+        .map(|path| to_relative_string(&base_path, &path))
+        .transpose()?;
+    write_atomic(&target, markdown.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    let relative = to_relative_string(&base_path, &target)?;
+    record_audit(
+        &state,
+        &user,
+        "import_fountain",
+        Some(&payload.project_id),
+        Some(&relative),
+    )
+    .await;
+
+    Ok(ImportFountainResponse {
+        path: relative,
+        content: markdown,
+        report,
+        snapshot,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectDiskUsage {
+    pub project_id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub usage: DiskUsage,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FindOrphanedAssetsRequest {
+    pub project_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FindOrphanedAssetsResponse {
+    pub orphans: Vec<OrphanedAsset>,
+    pub total_bytes: u64,
+}
+
+#[tauri::command]
+pub async fn find_orphaned_assets(
+    state: State<'_, AppState>,
+    payload: FindOrphanedAssetsRequest,
+) -> Result<FindOrphanedAssetsResponse, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        let base_path = PathBuf::from(project_row.base_path);
+        let cover_path = project_row.cover_path;
+
+        let orphans = tokio::task::spawn_blocking(move || {
+            let also_referenced: Vec<&str> = cover_path.as_deref().into_iter().collect();
+            crate::filesystem::find_orphaned_assets(&base_path, &also_referenced)
+        })
+        .await
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+
+        Ok::<_, AppError>(FindOrphanedAssetsResponse {
+            total_bytes: orphans.iter().map(|orphan| orphan.size).sum(),
+            orphans,
+        })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ValidateReferencesRequest {
+    pub project_id: String,
+    /// Limit the scan to one file; the whole project is scanned when absent.
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrokenReference {
+    pub file: String,
+    pub line: usize,
+    pub target: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidateReferencesResponse {
+    pub broken: Vec<BrokenReference>,
+    pub files_checked: usize,
+    pub references_checked: usize,
+}
+
+#[tauri::command]
+pub async fn validate_references(
+    state: State<'_, AppState>,
+    payload: ValidateReferencesRequest,
+) -> Result<ValidateReferencesResponse, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        let base_path = PathBuf::from(project_row.base_path);
+
+        let files = match payload.file_path.as_deref() {
+            Some(file_path) => {
+                let target_path = resolve_project_path(&base_path, file_path)?;
+                if !target_path.is_file() {
+                    return Err(AppError::Message(format!("{file_path} does not exist")));
+                }
+                vec![target_path]
+            }
+            None => project_markdown_files(&base_path),
+        };
+
+        tokio::task::spawn_blocking(move || {
+            let mut broken = Vec::new();
+            let mut references_checked = 0;
+            for file in &files {
+                let Ok(content) = fs::read_to_string(file) else {
+                    continue;
+                };
+                let relative_file = to_relative_string(&base_path, file)?;
+                let folder = Path::new(&relative_file)
+                    .parent()
+                    .unwrap_or(Path::new(""))
+                    .to_path_buf();
+                for (line, target) in link_references(&content) {
+                    if is_external_target(&target) {
+                        continue;
+                    }
+                    references_checked += 1;
+                    let exists = reference_candidates(&folder, &target)
+                        .iter()
+                        .filter_map(|candidate| {
+                            resolve_project_path(&base_path, &candidate.to_string_lossy()).ok()
+                        })
+                        .any(|path| path.exists());
+                    if !exists {
+                        broken.push(BrokenReference {
+                            file: relative_file.clone(),
+                            line,
+                            target,
+                        });
+                    }
+                }
+            }
+            Ok::<_, AppError>(ValidateReferencesResponse {
+                broken,
+                files_checked: files.len(),
+                references_checked,
+            })
+        })
+        .await
+        .map_err(|err| AppError::Anyhow(err.into()))?
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectChecksumRequest {
+    pub project_id: String,
+    #[serde(default)]
+    pub include_files: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectChecksumResponse {
+    pub algorithm: &'static str,
+    pub checksum: String,
+    pub file_count: usize,
+    pub total_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<FileChecksum>>,
+}
+
+#[tauri::command]
+pub async fn project_checksum(
+    state: State<'_, AppState>,
+    payload: ProjectChecksumRequest,
+) -> Result<ProjectChecksumResponse, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        let base_path = PathBuf::from(project_row.base_path);
+
+        let (checksum, files) =
+            tokio::task::spawn_blocking(move || crate::filesystem::project_checksum(&base_path))
+                .await
+                .map_err(|err| AppError::Anyhow(err.into()))??;
+
+        Ok::<_, AppError>(ProjectChecksumResponse {
+            algorithm: "sha256",
+            checksum,
+            file_count: files.len(),
+            total_bytes: files.iter().map(|file| file.size).sum(),
+            files: payload.include_files.then_some(files),
+        })
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn project_disk_usage(
+    state: State<'_, AppState>,
+) -> Result<Vec<ProjectDiskUsage>, String> {
+    project_disk_usage_inner(state)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn project_disk_usage_inner(state: State<'_, AppState>) -> AppResult<Vec<ProjectDiskUsage>> {
+    let _user = require_session(&state).await?;
+
+    let rows = sqlx::query("SELECT id, name, base_path FROM projects ORDER BY name")
+        .fetch_all(&state.pool)
+        .await?;
+
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        let project_id: String = row.try_get("id")?;
+        let name: String = row.try_get("name")?;
+        let base_path = PathBuf::from(row.try_get::<String, _>("base_path")?);
+
+        let cached = state.disk_usage.lock().await.get(&project_id).cloned();
+        let usage = match cached {
+            Some(usage) => usage,
+            None => {
+                let usage = tokio::task::spawn_blocking(move || measure_disk_usage(&base_path))
+                    .await
+                    .map_err(|err| AppError::Anyhow(err.into()))??;
+                state
+                    .disk_usage
+                    .lock()
+                    .await
+                    .insert(project_id.clone(), usage.clone());
+                usage
+            }
+        };
+
+        results.push(ProjectDiskUsage {
+            project_id,
+            name,
+            usage,
+        });
+    }
+
+    results.sort_by_key(|entry| std::cmp::Reverse(entry.usage.total_bytes));
+    Ok(results)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportDocxRequest {
+    pub project_id: String,
+    pub file_path: String,
+    #[serde(default)]
+    pub title_page: bool,
+}
+
+#[tauri::command]
+pub async fn export_docx(
+    state: State<'_, AppState>,
+    payload: ExportDocxRequest,
+) -> Result<JobStartedResponse, String> {
+    export_docx_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn export_docx_inner(
+    state: State<'_, AppState>,
+    payload: ExportDocxRequest,
+) -> AppResult<JobStartedResponse> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(&project_row.base_path);
+    let source = resolve_project_path(&base_path, &payload.file_path)?;
+    if !source.is_file() {
+        return Err(AppError::Message("Draft not found".into()));
+    }
+
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| project_row.slug.clone());
+    let target = resolve_project_path(&base_path, &format!("exports/{stem}.docx"))?;
+    let title = payload.title_page.then_some(project_row.name);
+    let project_id = payload.project_id;
+
+    let job_id = state
+        .jobs
+        .enqueue("export_docx", move |job| async move {
+            tokio::task::spawn_blocking({
+                let target = target.clone();
+                move || -> AppResult<()> {
+                    let content = fs::read_to_string(&source)?;
+                    let bytes = markdown_to_docx(&content, title.as_deref())?;
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    write_atomic(&target, &bytes)
+                }
+            })
+            .await
+            .map_err(|err| AppError::Anyhow(err.into()))??;
+
+            job.app()
+                .state::<AppState>()
+                .invalidate_disk_usage(&project_id)
+                .await;
+
+            Ok(json!({ "path": to_relative_string(&base_path, &target)? }))
+        })
+        .await?;
+
+    Ok(JobStartedResponse { job_id })
+}
+
+const DEFAULT_RECENT_FILES: usize = 20;
+const MAX_RECENT_FILES: usize = 100;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RecentActivityRequest {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecentFile {
+    pub project_id: String,
+    pub project_name: String,
+    pub path: String,
+    pub modified_at: u64,
+}
+
+#[tauri::command]
+pub async fn recent_activity(
+    state: State<'_, AppState>,
+    payload: Option<RecentActivityRequest>,
+) -> Result<Vec<RecentFile>, String> {
+    recent_activity_inner(state, payload.unwrap_or_default())
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn recent_activity_inner(
+    state: State<'_, AppState>,
+    payload: RecentActivityRequest,
+) -> AppResult<Vec<RecentFile>> {
+    let _user = require_session(&state).await?;
+    let limit = payload
+        .limit
+        .unwrap_or(DEFAULT_RECENT_FILES)
+        .clamp(1, MAX_RECENT_FILES);
+
+    let projects: Vec<(String, String, String)> =
+        sqlx::query_as("SELECT id, name, base_path FROM projects WHERE archived = 0")
+            .fetch_all(&state.pool)
+            .await?;
+
+    tokio::task::spawn_blocking(move || -> AppResult<Vec<RecentFile>> {
+        let mut recent = Vec::new();
+        for (project_id, project_name, base_path) in projects {
+            let base_path = PathBuf::from(base_path);
+            for (path, modified) in recent_markdown_files(&base_path, limit) {
+                recent.push(RecentFile {
+                    project_id: project_id.clone(),
+                    project_name: project_name.clone(),
+                    path: to_relative_string(&base_path, &path)?,
+                    modified_at: modified
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|elapsed| elapsed.as_secs())
+                        .unwrap_or_default(),
+                });
+            }
+        }
+
+        recent.sort_by_key(|file| std::cmp::Reverse(file.modified_at));
+        recent.truncate(limit);
+        Ok(recent)
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))?
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectChangelogRequest {
+    pub project_id: String,
+    /// Start of the range, as an ISO 8601 date or time in UTC.
+    pub from: String,
+    /// End of the range; a plain date includes that whole day. Defaults to now.
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub words_before: usize,
+    pub words_after: usize,
+    pub words_added: usize,
+    pub words_removed: usize,
+    /// Snapshots taken in the range, roughly one per save.
+    pub saves: usize,
+    pub modified_at: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectChangelog {
+    pub from: u64,
+    pub to: u64,
+    pub files: Vec<FileChange>,
+    pub words_added: usize,
+    pub words_removed: usize,
+    pub net_words: i64,
+}
+
+/// Parses a changelog bound with SQLite into Unix seconds. `end_of_day`
+/// moves a plain date to the start of the following day.
+async fn changelog_bound(state: &AppState, value: &str, end_of_day: bool) -> AppResult<u64> {
+    let value = value.trim();
+    let modifier = if end_of_day && value.len() == 10 {
+        "+1 day"
+    } else {
+        "+0 seconds"
+    };
+    let (seconds,): (Option<i64>,) =
+        sqlx::query_as("SELECT CAST(strftime('%s', ?1, ?2) AS INTEGER)")
+            .bind(value)
+            .bind(modifier)
+            .fetch_one(&state.pool)
+            .await?;
+    seconds
+        .and_then(|seconds| u64::try_from(seconds).ok())
+        .ok_or_else(|| AppError::Message(format!("'{value}' is not a valid date and time")))
+}
+
+/// Words in a draft's body, front matter excluded.
+fn body_word_count(content: &str) -> usize {
+    strip_front_matter(content).split_whitespace().count()
+}
+
+/// Which drafts changed between two dates and by how many words, for a
+/// "what I wrote this week" report. Content at each bound comes from the
+/// first snapshot taken after it, or the file itself when it has not changed
+/// since; files created in the range start from zero. Deleted files and
+/// history lost to snapshot pruning are not counted.
+#[tauri::command]
+pub async fn project_changelog(
+    state: State<'_, AppState>,
+    payload: ProjectChangelogRequest,
+) -> Result<ProjectChangelog, String> {
+    project_changelog_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn project_changelog_inner(
+    state: State<'_, AppState>,
+    payload: ProjectChangelogRequest,
+) -> AppResult<ProjectChangelog> {
+    let _user = require_session(&state).await?;
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(&project_row.base_path);
+
+    let from = changelog_bound(&state, &payload.from, false).await?;
+    let to = match payload.to.as_deref() {
+        Some(to) => changelog_bound(&state, to, true).await?,
+        None => changelog_bound(&state, "now", false).await?,
+    };
+    if from >= to {
+        return Err(AppError::Message(
+            "The start of the range must be before its end".into(),
+        ));
+    }
+
+    tokio::task::spawn_blocking(move || -> AppResult<ProjectChangelog> {
+        let mut files = Vec::new();
+        for path in project_markdown_files(&base_path) {
+            let relative = to_relative_string(&base_path, &path)?;
+            if relative.starts_with("exports/") {
+                continue;
+            }
+            let modified_at = fs::metadata(&path)?
+                .modified()?
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default();
+            let snapshots = file_snapshots(&base_path, &path)?;
+            let saves = snapshots
+                .iter()
+                .filter(|(stamp, _)| (from..to).contains(stamp))
+                .count();
+            if saves == 0 && !(from..to).contains(&modified_at) {
+                continue;
+            }
+
+            let words_at = |time: u64| -> AppResult<usize> {
+                let source = match snapshots.iter().find(|(stamp, _)| *stamp > time) {
+                    Some((_, snapshot)) => snapshot,
+                    None if modified_at <= time => &path,
+                    None => return Ok(0),
+                };
+                Ok(body_word_count(&fs::read_to_string(source)?))
+            };
+            let words_before = words_at(from)?;
+            let words_after = words_at(to)?;
+            files.push(FileChange {
+                path: relative,
+                words_before,
+                words_after,
+                words_added: words_after.saturating_sub(words_before),
+                words_removed: words_before.saturating_sub(words_after),
+                saves,
+                modified_at,
+            });
+        }
+
+        let words_added = files.iter().map(|file| file.words_added).sum::<usize>();
+        let words_removed = files.iter().map(|file| file.words_removed).sum::<usize>();
+        Ok(ProjectChangelog {
+            from,
+            to,
+            files,
+            words_added,
+            words_removed,
+            net_words: words_added as i64 - words_removed as i64,
+        })
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))?
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportBreakdownRequest {
+    pub project_id: String,
+    pub file_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportBreakdownResponse {
+    pub path: String,
+    pub rows: usize,
+}
+
+#[tauri::command]
+pub async fn export_breakdown_csv(
+    state: State<'_, AppState>,
+    payload: ExportBreakdownRequest,
+) -> Result<ExportBreakdownResponse, String> {
+    export_breakdown_csv_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn export_breakdown_csv_inner(
+    state: State<'_, AppState>,
+    payload: ExportBreakdownRequest,
+) -> AppResult<ExportBreakdownResponse> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(&project_row.base_path);
+    let source = resolve_project_path(&base_path, &payload.file_path)?;
+    if !source.is_file() {
+        return Err(AppError::Message("Draft not found".into()));
+    }
+
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| project_row.slug.clone());
+    let target = resolve_project_path(&base_path, &format!("exports/{stem}-breakdown.csv"))?;
+
+    let scenes = scene_breakdown(&fs::read_to_string(&source)?);
+    let bytes = breakdown_csv(&scenes)?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&target, &bytes)?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    Ok(ExportBreakdownResponse {
+        path: to_relative_string(&base_path, &target)?,
+        rows: scenes.len(),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompileManuscriptRequest {
+    pub project_id: String,
+    /// Drafts in the order they should appear; all drafts sorted by folder and
+    /// name when omitted.
+    pub file_paths: Option<Vec<String>>,
+    pub separator: Option<String>,
+    #[serde(default)]
+    pub title_page: bool,
+    /// Pull `drafts/scratch/` into the automatic ordering as well.
+    #[serde(default)]
+    pub include_scratch: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompileManuscriptResponse {
+    pub path: String,
+    pub drafts: usize,
+    pub word_count: usize,
+}
+
+#[tauri::command]
+pub async fn compile_manuscript(
+    state: State<'_, AppState>,
+    payload: CompileManuscriptRequest,
+) -> Result<CompileManuscriptResponse, String> {
+    compile_manuscript_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn compile_manuscript_inner(
+    state: State<'_, AppState>,
+    payload: CompileManuscriptRequest,
+) -> AppResult<CompileManuscriptResponse> {
+    let user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(&project_row.base_path);
+    let sources = match payload.file_paths {
+        Some(paths) => paths
+            .iter()
+            .map(|path| {
+                let source = resolve_project_path(&base_path, path)?;
+                if FileKind::from_path(&source) != FileKind::Markdown || !source.is_file() {
+                    return Err(AppError::Message(format!("Draft not found: {path}")));
+                }
+                Ok(source)
+            })
+            .collect::<AppResult<Vec<_>>>()?,
+        None => draft_markdown_files(&base_path, payload.include_scratch),
+    };
+    if sources.is_empty() {
+        return Err(AppError::Message("No drafts to compile".into()));
+    }
+
+    let separator = payload
+        .separator
+        .map(|separator| separator.trim().to_string())
+        .unwrap_or_else(|| "---".to_string());
+    let mut sections = Vec::with_capacity(sources.len() + 1);
+    if payload.title_page {
+        let mut title = format!("# {}\n", project_row.name);
+        if let Some(author) = user.display_name.as_deref() {
+            title.push_str(&format!("\n{author}\n"));
+        }
+        if let Some(description) = project_row
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+        {
+            title.push_str(&format!("\n_{description}_\n"));
+        }
+        sections.push(title);
+    }
+
+    let mut word_count = 0;
+    for source in &sources {
+        let body = strip_front_matter(&fs::read_to_string(source)?);
+        word_count += body.split_whitespace().count();
+        sections.push(body.trim_end().to_string() + "\n");
+    }
+
+    let joiner = if separator.is_empty() {
+        "\n".to_string()
+    } else {
+        format!("\n{separator}\n\n")
+    };
+    let manuscript = sections.join(&joiner);
+
+    let target = resolve_project_path(&base_path, "exports/manuscript.md")?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&target, manuscript.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    Ok(CompileManuscriptResponse {
+        path: to_relative_string(&base_path, &target)?,
+        drafts: sources.len(),
+        word_count,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportHtmlSiteRequest {
+    pub project_id: String,
+    #[serde(default)]
+    pub include_scratch: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportHtmlSiteResponse {
+    pub path: String,
+    pub pages: usize,
+    pub assets: usize,
+}
+
+#[tauri::command]
+pub async fn export_html_site(
+    state: State<'_, AppState>,
+    payload: ExportHtmlSiteRequest,
+) -> Result<ExportHtmlSiteResponse, String> {
+    export_html_site_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn export_html_site_inner(
+    state: State<'_, AppState>,
+    payload: ExportHtmlSiteRequest,
+) -> AppResult<ExportHtmlSiteResponse> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(&project_row.base_path);
+    let drafts = draft_markdown_files(&base_path, payload.include_scratch)
+        .into_iter()
+        .filter_map(|path| path.strip_prefix(&base_path).ok().map(Path::to_path_buf))
+        .collect::<Vec<_>>();
+    if drafts.is_empty() {
+        return Err(AppError::Message("No drafts to export".into()));
+    }
+    let language = serde_json::from_str::<Vec<String>>(&project_row.languages)
+        .ok()
+        .and_then(|languages| languages.into_iter().next())
+        .unwrap_or_else(|| "ta".to_string());
+
+    let site = tokio::task::spawn_blocking({
+        let base_path = base_path.clone();
+        move || {
+            crate::export::export_html_site(
+                &base_path,
+                &project_row.name,
+                project_row.description.as_deref(),
+                &language,
+                &drafts,
+            )
+        }
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    Ok(ExportHtmlSiteResponse {
+        path: SITE_DIR.to_string(),
+        pages: site.pages,
+        assets: site.assets,
+    })
+}
+
+#[derive(Copy, Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaylistFormat {
+    #[default]
+    M3u,
+    Json,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportTableReadPlaylistRequest {
+    pub project_id: String,
+    #[serde(default)]
+    pub format: PlaylistFormat,
+    #[serde(default)]
+    pub include_scratch: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportTableReadPlaylistResponse {
+    pub path: String,
+    pub tracks: usize,
+    /// Recordings that matched no draft and were appended at the end.
+    pub unmatched: usize,
+}
+
+/// Orders the recordings under `audio/table-reads/` by scene and writes them
+/// out as a playlist in `exports/`.
+#[tauri::command]
+pub async fn export_table_read_playlist(
+    state: State<'_, AppState>,
+    payload: ExportTableReadPlaylistRequest,
+) -> Result<ExportTableReadPlaylistResponse, String> {
+    export_table_read_playlist_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn export_table_read_playlist_inner(
+    state: State<'_, AppState>,
+    payload: ExportTableReadPlaylistRequest,
+) -> AppResult<ExportTableReadPlaylistResponse> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(&project_row.base_path);
+    let drafts = draft_markdown_files(&base_path, payload.include_scratch)
+        .into_iter()
+        .filter_map(|path| path.strip_prefix(&base_path).ok().map(Path::to_path_buf))
+        .collect::<Vec<_>>();
+
+    let tracks = tokio::task::spawn_blocking({
+        let base_path = base_path.clone();
+        move || table_read_playlist(&base_path, &drafts)
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
+    if tracks.is_empty() {
+        return Err(AppError::Message(format!(
+            "No recordings found under {TABLE_READS_DIR}"
+        )));
+    }
+
+    let (file_name, bytes) = match payload.format {
+        PlaylistFormat::M3u => (
+            "table-read.m3u",
+            playlist_m3u(&project_row.name, &tracks).into_bytes(),
+        ),
+        PlaylistFormat::Json => (
+            "table-read.json",
+            serde_json::to_vec_pretty(&json!({
+                "project": project_row.name,
+                "tracks": tracks,
+            }))?,
+        ),
+    };
+    let target = resolve_project_path(&base_path, &format!("exports/{file_name}"))?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&target, &bytes)?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    Ok(ExportTableReadPlaylistResponse {
+        path: to_relative_string(&base_path, &target)?,
+        tracks: tracks.len(),
+        unmatched: tracks.iter().filter(|track| track.scene.is_none()).count(),
+    })
+}
+
+#[derive(Copy, Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceFormat {
+    #[default]
+    Markdown,
+    Bibtex,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportReferencesRequest {
+    pub project_id: String,
+    #[serde(default)]
+    pub format: ReferenceFormat,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportReferencesResponse {
+    pub path: String,
+    pub references: Vec<Reference>,
+    /// Research files that contributed, with their distinct reference count.
+    pub files: Vec<ReferenceSource>,
+}
+
+/// Gathers the links and citations in `research/` into a references file in
+/// `exports/`.
+#[tauri::command]
+pub async fn export_references(
+    state: State<'_, AppState>,
+    payload: ExportReferencesRequest,
+) -> Result<ExportReferencesResponse, String> {
+    export_references_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn export_references_inner(
+    state: State<'_, AppState>,
+    payload: ExportReferencesRequest,
+) -> AppResult<ExportReferencesResponse> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(&project_row.base_path);
+    let (references, files) = tokio::task::spawn_blocking({
+        let base_path = base_path.clone();
+        move || collect_references(&base_path)
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
+    if references.is_empty() {
+        return Err(AppError::Message(format!(
+            "No links or citations found under {RESEARCH_DIR}/"
+        )));
+    }
+
+    let (file_name, document) = match payload.format {
+        ReferenceFormat::Markdown => (
+            "references.md",
+            references_markdown(&project_row.name, &references),
+        ),
+        ReferenceFormat::Bibtex => ("references.bib", references_bibtex(&references)),
+    };
+    let target = resolve_project_path(&base_path, &format!("exports/{file_name}"))?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&target, document.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    Ok(ExportReferencesResponse {
+        path: to_relative_string(&base_path, &target)?,
+        references,
+        files,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportFountainRequest {
+    pub project_id: String,
+    pub file_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportFountainResponse {
+    pub path: String,
+    pub unmapped: Vec<UnmappedElement>,
+}
+
+/// Writes a draft to `exports/<name>.fountain` for screenwriting tools;
+/// `import_fountain` reads it back.
+#[tauri::command]
+pub async fn export_fountain(
+    state: State<'_, AppState>,
+    payload: ExportFountainRequest,
+) -> Result<ExportFountainResponse, String> {
+    export_fountain_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn export_fountain_inner(
+    state: State<'_, AppState>,
+    payload: ExportFountainRequest,
+) -> AppResult<ExportFountainResponse> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(&project_row.base_path);
+    let source = resolve_project_path(&base_path, &payload.file_path)?;
+    if FileKind::from_path(&source) != FileKind::Markdown || !source.is_file() {
+        return Err(AppError::Message(format!(
+            "Draft not found: {}",
+            payload.file_path
+        )));
+    }
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "script".into());
+
+    let FountainExport { fountain, unmapped } = markdown_to_fountain(&fs::read_to_string(&source)?);
+    let target = resolve_project_path(&base_path, &format!("exports/{stem}.fountain"))?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&target, fountain.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    Ok(ExportFountainResponse {
+        path: to_relative_string(&base_path, &target)?,
+        unmapped,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InspectArchiveRequest {
+    pub archive_path: String,
+}
+
+#[tauri::command]
+pub async fn inspect_project_archive(
+    state: State<'_, AppState>,
+    payload: InspectArchiveRequest,
+) -> Result<ArchiveInspection, String> {
+    inspect_project_archive_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn inspect_project_archive_inner(
+    state: State<'_, AppState>,
+    payload: InspectArchiveRequest,
+) -> AppResult<ArchiveInspection> {
+    let _user = require_session(&state).await?;
+
+    let archive_path = PathBuf::from(&payload.archive_path);
+    if !archive_path.is_file() {
+        return Err(AppError::Message("Archive not found".into()));
+    }
+
+    let mut inspection = tokio::task::spawn_blocking(move || inspect_archive(&archive_path))
+        .await
+        .map_err(|err| AppError::Anyhow(err.into()))??;
+
+    if let Some(slug) = &inspection.slug {
+        let existing: Option<(String,)> = sqlx::query_as("SELECT id FROM projects WHERE slug = ?1")
+            .bind(slug)
+            .fetch_optional(&state.pool)
+            .await?;
+        inspection.slug_taken =
+            existing.is_some() || project_path(&state.storage_root, slug).exists();
+    }
+
+    Ok(inspection)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportProjectArchiveRequest {
+    pub project_id: String,
+    /// Continue an interrupted export instead of starting over.
+    #[serde(default)]
+    pub resume: bool,
+}
+
+/// Zips the project into `exports/{slug}.zip` as a background job, reporting
+/// progress through `job-progress` events.
+#[tauri::command]
+pub async fn export_project_archive(
+    state: State<'_, AppState>,
+    payload: ExportProjectArchiveRequest,
+) -> Result<JobStartedResponse, String> {
+    export_project_archive_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn export_project_archive_inner(
+    state: State<'_, AppState>,
+    payload: ExportProjectArchiveRequest,
+) -> AppResult<JobStartedResponse> {
+    let _user = require_session(&state).await?;
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(&project_row.base_path);
+    let slug = project_row.slug;
+    let target = resolve_project_path(&base_path, &format!("exports/{slug}.zip"))?;
+    let project_id = payload.project_id;
+    let resume = payload.resume;
+
+    let job_id = state
+        .jobs
+        .enqueue("export_project_archive", move |job| async move {
+            let runtime = tokio::runtime::Handle::current();
+            let export = tokio::task::spawn_blocking({
+                let base_path = base_path.clone();
+                let target = target.clone();
+                let job = job.clone();
+                move || {
+                    let mut reported = 0;
+                    export_archive(&base_path, &slug, &target, resume, |done, total| {
+                        let percent = (done * 100).checked_div(total).unwrap_or(100);
+                        if percent > reported {
+                            reported = percent;
+                            runtime.block_on(
+                                job.progress(percent as f32 / 100.0, format!("{percent}%")),
+                            );
+                        }
+                        Ok(())
+                    })
+                }
+            })
+            .await
+            .map_err(|err| AppError::Anyhow(err.into()))??;
+
+            job.app()
+                .state::<AppState>()
+                .invalidate_disk_usage(&project_id)
+                .await;
+
+            Ok(json!({
+                "path": to_relative_string(&base_path, &target)?,
+                "file_count": export.file_count,
+                "resumed": export.resumed,
+                "total_bytes": export.total_bytes,
+            }))
+        })
+        .await?;
+
+    Ok(JobStartedResponse { job_id })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompareProjectsRequest {
+    pub project_a: String,
+    pub project_b: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompareProjectsResponse {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub common: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn compare_projects(
+    state: State<'_, AppState>,
+    payload: CompareProjectsRequest,
+) -> Result<CompareProjectsResponse, String> {
+    compare_projects_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn compare_projects_inner(
+    state: State<'_, AppState>,
+    payload: CompareProjectsRequest,
+) -> AppResult<CompareProjectsResponse> {
+    let _user = require_session(&state).await?;
+
+    let base_a = PathBuf::from(
+        fetch_project_row(&state, &payload.project_a)
+            .await?
+            .base_path,
+    );
+    let base_b = PathBuf::from(
+        fetch_project_row(&state, &payload.project_b)
+            .await?
+            .base_path,
+    );
+
+    let (files_a, files_b) = tokio::task::spawn_blocking(move || -> AppResult<_> {
+        let files_a = flatten_file_paths(&build_directory_entries(&base_a, &base_a, 0, false)?);
+        let files_b = flatten_file_paths(&build_directory_entries(&base_b, &base_b, 0, false)?);
+        Ok((files_a, files_b))
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
+
+    Ok(CompareProjectsResponse {
+        only_in_a: files_a.difference(&files_b).cloned().collect(),
+        only_in_b: files_b.difference(&files_a).cloned().collect(),
+        common: files_a.intersection(&files_b).cloned().collect(),
+    })
+}
+
+fn flatten_file_paths(entries: &[ProjectFileEntry]) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    for entry in entries {
+        if entry.is_directory {
+            if let Some(children) = &entry.children {
+                paths.extend(flatten_file_paths(children));
+            }
+        } else {
+            paths.insert(entry.path.clone());
+        }
+    }
+    paths
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RenameDirectoryRequest {
+    pub project_id: String,
+    pub directory_path: String,
+    pub new_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenameDirectoryResponse {
+    pub path: String,
+}
+
+#[tauri::command]
+pub async fn rename_project_directory(
+    state: State<'_, AppState>,
+    payload: RenameDirectoryRequest,
+) -> Result<RenameDirectoryResponse, String> {
+    rename_project_directory_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn rename_project_directory_inner(
+    state: State<'_, AppState>,
+    payload: RenameDirectoryRequest,
+) -> AppResult<RenameDirectoryResponse> {
+    let user = require_session(&state).await?;
+
+    let new_name = payload.new_name.trim();
+    if new_name.is_empty() || new_name == "." || new_name == ".." {
+        return Err(AppError::Message("Folder name cannot be empty".into()));
+    }
+    if new_name.contains(['/', '\\']) {
+        return Err(AppError::Message(
+            "Folder name cannot contain path separators".into(),
+        ));
+    }
+
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let source = resolve_project_path(&base_path, &payload.directory_path)?;
+    // "." or "/" resolve to the project folder itself, whose parent is the
+    // projects root; renaming it would strand the project row.
+    if source == base_path {
+        return Err(AppError::Message("Cannot rename the project root".into()));
+    }
+    if !source.is_dir() {
+        return Err(AppError::Message("Folder not found".into()));
+    }
+
+    let target = source
+        .parent()
+        .map(|parent| parent.join(new_name))
+        .ok_or_else(|| AppError::Message("Cannot rename the project root".into()))?;
+    if rename_conflicts(&source, &target) {
+        return Err(AppError::Message(format!(
+            "A file or folder named '{new_name}' already exists"
+        )));
+    }
+
+    rename_entry(&source, &target)?;
+    state.invalidate_disk_usage(&payload.project_id).await;
+
+    let old_relative = to_relative_string(&base_path, &source)?;
+    let new_relative = to_relative_string(&base_path, &target)?;
+
+    sqlx::query(
+        r#"
+      UPDATE scripts
+      SET file_path = ?1 || substr(file_path, length(?2) + 1),
+          updated_at = datetime('now')
+      WHERE project_id = ?3
+        AND (file_path = ?2 OR substr(file_path, 1, length(?2) + 1) = ?2 || '/')
+    "#,
+    )
+    .bind(&new_relative)
+    .bind(&old_relative)
+    .bind(&payload.project_id)
+    .execute(&state.pool)
+    .await?;
+
+    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(&payload.project_id)
+        .execute(&state.pool)
+        .await?;
+
+    let detail = format!("{old_relative} -> {new_relative}");
+    record_audit(
+        &state,
+        &user,
+        "rename_project_directory",
+        Some(&payload.project_id),
+        Some(&detail),
+    )
+    .await;
+
+    Ok(RenameDirectoryResponse { path: new_relative })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WatchProjectRequest {
+    pub project_id: String,
+}
+
+#[tauri::command]
+pub async fn watch_project(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: WatchProjectRequest,
+) -> Result<(), String> {
+    watch_project_inner(app, state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn watch_project_inner(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: WatchProjectRequest,
+) -> AppResult<()> {
+    let _user = require_session(&state).await?;
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    let base_path = PathBuf::from(project_row.base_path);
+    if !base_path.is_dir() {
+        return Err(AppError::Message("Project directory is missing".into()));
+    }
+
+    let mut watchers = state.watchers.lock().await;
+    watchers.remove(&payload.project_id);
+    let watcher = crate::watcher::watch_project(app, payload.project_id.clone(), base_path)?;
+    watchers.insert(payload.project_id, watcher);
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StopWatchingRequest {
+    pub project_id: Option<String>,
+}
+
+#[tauri::command]
+pub async fn stop_watching(
+    state: State<'_, AppState>,
+    payload: StopWatchingRequest,
+) -> Result<(), String> {
+    let _user = require_session(&state)
+        .await
+        .map_err(|err| err.to_string())?;
+    let mut watchers = state.watchers.lock().await;
+    match payload.project_id {
+        Some(project_id) => {
+            watchers.remove(&project_id);
+        }
+        None => watchers.clear(),
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RenderMarkdownRequest {
+    pub content: Option<String>,
+    pub project_id: Option<String>,
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenderMarkdownResponse {
+    pub html: String,
+}
+
+#[tauri::command]
+pub async fn render_markdown_preview(
+    state: State<'_, AppState>,
+    payload: RenderMarkdownRequest,
+) -> Result<RenderMarkdownResponse, String> {
+    render_markdown_preview_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn render_markdown_preview_inner(
+    state: State<'_, AppState>,
+    payload: RenderMarkdownRequest,
+) -> AppResult<RenderMarkdownResponse> {
+    let _user = require_session(&state).await?;
+
+    let content = match (payload.content, payload.project_id, payload.file_path) {
+        (Some(content), _, _) => content,
+        (None, Some(project_id), Some(file_path)) => {
+            load_project_file(&state, &project_id, &file_path).await?
+        }
+        _ => {
+            return Err(AppError::Message(
+                "Provide markdown content or a project file to render".into(),
+            ))
+        }
+    };
+
+    let html = tokio::task::spawn_blocking(move || render_html(&content))
+        .await
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+
+    Ok(RenderMarkdownResponse { html })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RenderScriptColoredRequest {
+    pub project_id: String,
+    pub file_path: String,
+}
+
+#[tauri::command]
+pub async fn render_script_colored(
+    state: State<'_, AppState>,
+    payload: RenderScriptColoredRequest,
+) -> Result<ColoredScript, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let content = load_project_file(&state, &payload.project_id, &payload.file_path).await?;
+        tokio::task::spawn_blocking(move || crate::markdown::render_colored_script(&content))
+            .await
+            .map_err(|err| AppError::Anyhow(err.into()))
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutlineMarkdownRequest {
+    pub project_id: String,
+    pub file_path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OutlineMarkdownResponse {
+    pub outline: Vec<OutlineNode>,
+}
+
+#[tauri::command]
+pub async fn outline_markdown(
+    state: State<'_, AppState>,
+    payload: OutlineMarkdownRequest,
+) -> Result<OutlineMarkdownResponse, String> {
+    outline_markdown_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn outline_markdown_inner(
+    state: State<'_, AppState>,
+    payload: OutlineMarkdownRequest,
+) -> AppResult<OutlineMarkdownResponse> {
+    let _user = require_session(&state).await?;
+    let content = load_project_file(&state, &payload.project_id, &payload.file_path).await?;
+    let outline = build_outline(&extract_headings(&content));
+    Ok(OutlineMarkdownResponse { outline })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EstimateRuntimeRequest {
+    pub project_id: String,
+    pub file_path: String,
+    /// One of `RUNTIME_FORMATS`; defaults to a feature screenplay.
+    pub format: Option<String>,
+    pub words_per_page: Option<u32>,
+    pub lines_per_page: Option<u32>,
+}
+
+#[tauri::command]
+pub async fn estimate_runtime(
+    state: State<'_, AppState>,
+    payload: EstimateRuntimeRequest,
+) -> Result<RuntimeEstimate, String> {
+    estimate_runtime_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn estimate_runtime_inner(
+    state: State<'_, AppState>,
+    payload: EstimateRuntimeRequest,
+) -> AppResult<RuntimeEstimate> {
+    let _user = require_session(&state).await?;
+
+    let format = payload.format.as_deref().unwrap_or(DEFAULT_RUNTIME_FORMAT);
+    let mut density = RUNTIME_FORMATS
+        .iter()
+        .find(|(name, _)| *name == format)
+        .map(|(_, density)| *density)
+        .ok_or_else(|| AppError::Message(format!("Unknown runtime format '{format}'")))?;
+    if payload.words_per_page == Some(0) || payload.lines_per_page == Some(0) {
+        return Err(AppError::Message(
+            "Page density must be greater than zero".into(),
+        ));
+    }
+    density.words_per_page = payload.words_per_page.unwrap_or(density.words_per_page);
+    density.lines_per_page = payload.lines_per_page.unwrap_or(density.lines_per_page);
+
+    let content = load_project_file(&state, &payload.project_id, &payload.file_path).await?;
+    Ok(crate::markdown::estimate_runtime(&content, density))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReadingTimeRequest {
+    pub project_id: String,
+    /// A single draft; every draft (scratch excluded) when omitted.
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileReadingTime {
+    pub path: String,
+    #[serde(flatten)]
+    pub reading_time: ReadingTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadingTimeResponse {
+    pub files: Vec<FileReadingTime>,
+    pub words: usize,
+    pub minutes: f32,
+    pub english_wpm: u32,
+    pub tamil_wpm: u32,
 }
 
 #[tauri::command]
-pub async fn list_project_files(
+pub async fn reading_time(
     state: State<'_, AppState>,
-    payload: ListProjectFilesRequest,
-) -> Result<ProjectFilesResponse, String> {
-    if let Err(err) = require_session(&state).await {
-        return Err(err.to_string());
-    }
-
-    let project_row = fetch_project_row(&state, &payload.project_id)
+    payload: ReadingTimeRequest,
+) -> Result<ReadingTimeResponse, String> {
+    reading_time_inner(state, payload)
         .await
-        .map_err(|err| err.to_string())?;
-    let base_path = PathBuf::from(project_row.base_path);
-    let entries =
-        build_directory_entries(&base_path, &base_path, 0).map_err(|err| err.to_string())?;
-    Ok(ProjectFilesResponse { files: entries })
+        .map_err(|err| err.to_string())
 }
 
-#[tauri::command]
-pub async fn load_markdown_file(
+async fn reading_time_inner(
     state: State<'_, AppState>,
-    payload: LoadMarkdownRequest,
-) -> Result<LoadMarkdownResponse, String> {
-    if let Err(err) = require_session(&state).await {
-        return Err(err.to_string());
-    }
+    payload: ReadingTimeRequest,
+) -> AppResult<ReadingTimeResponse> {
+    let _user = require_session(&state).await?;
 
-    let project_row = fetch_project_row(&state, &payload.project_id)
-        .await
-        .map_err(|err| err.to_string())?;
+    let settings = fetch_settings(&state).await?;
+    let (english_wpm, tamil_wpm) = (settings.reading_wpm_english, settings.reading_wpm_tamil);
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
     let base_path = PathBuf::from(project_row.base_path);
-    let target_path =
-        resolve_project_path(&base_path, &payload.file_path).map_err(|err| err.to_string())?;
-
-    let content = if target_path.exists() {
-        fs::read_to_string(&target_path).map_err(|err| err.to_string())?
-    } else {
-        String::new()
+    let sources = match payload.file_path {
+        Some(path) => {
+            let source = resolve_project_path(&base_path, &path)?;
+            if FileKind::from_path(&source) != FileKind::Markdown || !source.is_file() {
+                return Err(AppError::Message(format!("Draft not found: {path}")));
+            }
+            vec![source]
+        }
+        None => draft_markdown_files(&base_path, false),
     };
 
-    Ok(LoadMarkdownResponse { content })
+    let files = tokio::task::spawn_blocking(move || {
+        sources
+            .iter()
+            .map(|source| {
+                let content = fs::read_to_string(source)?;
+                Ok(FileReadingTime {
+                    path: to_relative_string(&base_path, source)?,
+                    reading_time: crate::markdown::reading_time(&content, english_wpm, tamil_wpm),
+                })
+            })
+            .collect::<AppResult<Vec<_>>>()
+    })
+    .await
+    .map_err(|err| AppError::Anyhow(err.into()))??;
+
+    let words = files.iter().map(|file| file.reading_time.words).sum();
+    let minutes = files
+        .iter()
+        .map(|file| file.reading_time.minutes)
+        .sum::<f32>();
+    Ok(ReadingTimeResponse {
+        files,
+        words,
+        minutes: (minutes * 10.0).round() / 10.0,
+        english_wpm,
+        tamil_wpm,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectCharacterIndexRequest {
+    pub project_id: String,
 }
 
 #[tauri::command]
-pub async fn save_markdown_file(
+pub async fn project_character_index(
     state: State<'_, AppState>,
-    payload: SaveMarkdownRequest,
-) -> Result<SaveMarkdownResponse, String> {
-    if let Err(err) = require_session(&state).await {
-        return Err(err.to_string());
-    }
+    payload: ProjectCharacterIndexRequest,
+) -> Result<Vec<CharacterEntry>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let project_row = fetch_project_row(&state, &payload.project_id).await?;
+        let base_path = PathBuf::from(project_row.base_path);
 
-    let project_row = fetch_project_row(&state, &payload.project_id)
+        tokio::task::spawn_blocking(move || -> AppResult<Vec<CharacterEntry>> {
+            let documents = draft_markdown_files(&base_path, false)
+                .into_iter()
+                .map(|path| {
+                    let content = fs::read_to_string(&path)?;
+                    Ok((to_relative_string(&base_path, &path)?, content))
+                })
+                .collect::<AppResult<Vec<_>>>()?;
+            Ok(crate::markdown::character_index(&documents))
+        })
         .await
-        .map_err(|err| err.to_string())?;
-    let base_path = PathBuf::from(project_row.base_path);
-    let target_path =
-        resolve_project_path(&base_path, &payload.file_path).map_err(|err| err.to_string())?;
-
-    if let Some(parent) = target_path.parent() {
-        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        .map_err(|err| AppError::Anyhow(err.into()))?
     }
+    .await;
+    result.map_err(|err| err.to_string())
+}
 
-    fs::write(&target_path, payload.content).map_err(|err| err.to_string())?;
-
-    sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
-        .bind(&payload.project_id)
-        .execute(&state.pool)
-        .await
-        .map_err(|err| err.to_string())?;
-
-    let relative = to_relative_string(&base_path, &target_path).map_err(|err| err.to_string())?;
+#[derive(Debug, Deserialize)]
+pub struct InsertTocRequest {
+    pub project_id: String,
+    pub file_path: String,
+}
 
-    Ok(SaveMarkdownResponse { path: relative })
+#[derive(Debug, Serialize)]
+pub struct InsertTocResponse {
+    pub path: String,
+    pub entries: usize,
 }
 
 #[tauri::command]
-pub async fn copy_project_asset(
+pub async fn insert_toc(
     state: State<'_, AppState>,
-    payload: CopyAssetRequest,
-) -> Result<CopyAssetResponse, String> {
-    if let Err(err) = require_session(&state).await {
-        return Err(err.to_string());
-    }
-
-    let project_row = fetch_project_row(&state, &payload.project_id)
+    payload: InsertTocRequest,
+) -> Result<InsertTocResponse, String> {
+    insert_toc_inner(state, payload)
         .await
-        .map_err(|err| err.to_string())?;
-    let base_path = PathBuf::from(project_row.base_path);
-
-    let source_path = PathBuf::from(&payload.source);
-    if !source_path.exists() {
-        return Err(AppError::Message("Selected file does not exist".into()).to_string());
-    }
-
-    let target_dir_relative = payload.target_dir.unwrap_or_else(|| "assets/images".into());
-    let target_dir =
-        resolve_project_path(&base_path, &target_dir_relative).map_err(|err| err.to_string())?;
-    fs::create_dir_all(&target_dir).map_err(|err| err.to_string())?;
-
-    let original_name = source_path
-        .file_name()
-        .map(|name| name.to_string_lossy().to_string())
-        .ok_or_else(|| AppError::Message("Invalid source file".into()).to_string())?;
-    let mut sanitized = sanitize(&original_name);
-    if sanitized.is_empty() {
-        sanitized = "asset".into();
-    }
+        .map_err(|err| err.to_string())
+}
 
-    let mut candidate = target_dir.join(&sanitized);
-    if candidate.exists() {
-        let mut counter = 1usize;
-        let stem = candidate
-            .file_stem()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| "asset".into());
-        let extension = candidate
-            .extension()
-            .map(|ext| ext.to_string_lossy().to_string());
+async fn insert_toc_inner(
+    state: State<'_, AppState>,
+    payload: InsertTocRequest,
+) -> AppResult<InsertTocResponse> {
+    let _user = require_session(&state).await?;
 
-        loop {
-            let new_name = if let Some(ext) = &extension {
-                format!("{}-{}.{}", stem, counter, ext)
-            } else {
-                format!("{}-{}", stem, counter)
-            };
-            candidate = target_dir.join(&new_name);
-            if !candidate.exists() {
-                break;
-            }
-            counter += 1;
-        }
+    let project_row = fetch_project_row(&state, &payload.project_id).await?;
+    ensure_unlocked(&project_row)?;
+    let base_path = PathBuf::from(project_row.base_path);
+    let target_path = resolve_project_path(&base_path, &payload.file_path)?;
+    if FileKind::from_path(&target_path) != FileKind::Markdown || !target_path.is_file() {
+        return Err(AppError::Message("Markdown file not found".into()));
     }
 
-    fs::copy(&source_path, &candidate).map_err(|err| err.to_string())?;
+    let content = fs::read_to_string(&target_path)?;
+    let (updated, entries) = crate::markdown::insert_toc(&content);
 
-    let relative = to_relative_string(&base_path, &candidate).map_err(|err| err.to_string())?;
+    let _write = state.pending_writes.read().await;
+    write_atomic(&target_path, updated.as_bytes())?;
+    state.invalidate_disk_usage(&payload.project_id).await;
 
     sqlx::query("UPDATE projects SET updated_at = datetime('now') WHERE id = ?1")
         .bind(&payload.project_id)
         .execute(&state.pool)
-        .await
-        .map_err(|err| err.to_string())?;
+        .await?;
 
-    Ok(CopyAssetResponse {
-        relative_path: relative,
+    Ok(InsertTocResponse {
+        path: to_relative_string(&base_path, &target_path)?,
+        entries,
     })
 }
 
@@ -844,6 +7481,7 @@ async fn login_user_inner(
 #[tauri::command]
 pub async fn logout_user(state: State<'_, AppState>) -> Result<(), String> {
     state.set_user(None).await;
+    state.watchers.lock().await.clear();
     Ok(())
 }
 
@@ -852,3 +7490,287 @@ pub async fn current_user(state: State<'_, AppState>) -> Result<CurrentUserRespo
     let user = state.current_user().await;
     Ok(CurrentUserResponse { user })
 }
+
+const MAX_DISPLAY_NAME_CHARS: usize = 80;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateDisplayNameRequest {
+    pub display_name: Option<String>,
+}
+
+#[tauri::command]
+pub async fn update_display_name(
+    state: State<'_, AppState>,
+    payload: UpdateDisplayNameRequest,
+) -> Result<UserProfile, String> {
+    update_display_name_inner(state, payload)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn update_display_name_inner(
+    state: State<'_, AppState>,
+    payload: UpdateDisplayNameRequest,
+) -> AppResult<UserProfile> {
+    let user = require_session(&state).await?;
+
+    let display_name = payload
+        .display_name
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if display_name
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > MAX_DISPLAY_NAME_CHARS)
+    {
+        return Err(AppError::Message(format!(
+            "Display name must be at most {MAX_DISPLAY_NAME_CHARS} characters"
+        )));
+    }
+
+    let updated = sqlx::query(
+        r#"
+      UPDATE users
+      SET display_name = ?1, updated_at = datetime('now')
+      WHERE id = ?2
+    "#,
+    )
+    .bind(&display_name)
+    .bind(&user.id)
+    .execute(&state.pool)
+    .await?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::Unauthorized);
+    }
+
+    let user_row = sqlx::query_as::<_, UserRow>(
+        r#"
+      SELECT id, email, display_name, password_hash, created_at, updated_at
+      FROM users
+      WHERE id = ?1
+    "#,
+    )
+    .bind(&user.id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let profile = user_row.into_profile();
+    state.set_user(Some(profile.clone())).await;
+
+    Ok(profile)
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobStartedResponse {
+    pub job_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetJobStatusRequest {
+    pub job_id: String,
+}
+
+#[tauri::command]
+pub async fn get_job_status(
+    state: State<'_, AppState>,
+    payload: GetJobStatusRequest,
+) -> Result<JobRecord, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        state
+            .jobs
+            .get(&payload.job_id)
+            .await
+            .ok_or_else(|| AppError::Message("Job not found".into()))
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn list_jobs(state: State<'_, AppState>) -> Result<Vec<JobRecord>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        Ok::<_, AppError>(state.jobs.list().await)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+const MAX_REMINDER_CHARS: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct AddReminderRequest {
+    /// Leave unset for a reminder that is not tied to a project.
+    pub project_id: Option<String>,
+    pub message: String,
+    /// An ISO 8601 time; one with no offset is taken as UTC.
+    pub due_at: String,
+}
+
+#[tauri::command]
+pub async fn add_reminder(
+    state: State<'_, AppState>,
+    payload: AddReminderRequest,
+) -> Result<Reminder, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let message = payload.message.trim();
+        if message.is_empty() {
+            return Err(AppError::Message("Reminder message cannot be empty".into()));
+        }
+        if message.chars().count() > MAX_REMINDER_CHARS {
+            return Err(AppError::Message(format!(
+                "Reminder messages are limited to {MAX_REMINDER_CHARS} characters"
+            )));
+        }
+        if let Some(project_id) = &payload.project_id {
+            fetch_project_row(&state, project_id).await?;
+        }
+
+        // SQLite normalises the time to UTC, or gives NULL when it cannot parse it.
+        let (due_at,): (Option<String>,) = sqlx::query_as("SELECT datetime(?1)")
+            .bind(payload.due_at.trim())
+            .fetch_one(&state.pool)
+            .await?;
+        let due_at = due_at.ok_or_else(|| {
+            AppError::Message(format!("'{}' is not a valid date and time", payload.due_at))
+        })?;
+
+        let reminder = sqlx::query_as::<_, Reminder>(
+            r#"
+      INSERT INTO reminders (project_id, message, due_at)
+      VALUES (?1, ?2, ?3)
+      RETURNING id, project_id, message, due_at, fired_at, created_at
+    "#,
+        )
+        .bind(&payload.project_id)
+        .bind(message)
+        .bind(due_at)
+        .fetch_one(&state.pool)
+        .await?;
+        Ok(reminder)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListRemindersRequest {
+    /// Limits the list to this project's reminders plus global ones.
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub include_fired: bool,
+}
+
+#[tauri::command]
+pub async fn list_reminders(
+    state: State<'_, AppState>,
+    payload: ListRemindersRequest,
+) -> Result<Vec<Reminder>, String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let reminders = sqlx::query_as::<_, Reminder>(
+            r#"
+      SELECT id, project_id, message, due_at, fired_at, created_at
+      FROM reminders
+      WHERE (?1 IS NULL OR project_id IS NULL OR project_id = ?1)
+        AND (?2 OR fired_at IS NULL)
+      ORDER BY due_at, id
+    "#,
+        )
+        .bind(&payload.project_id)
+        .bind(payload.include_fired)
+        .fetch_all(&state.pool)
+        .await?;
+        Ok::<_, AppError>(reminders)
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteReminderRequest {
+    pub reminder_id: i64,
+}
+
+#[tauri::command]
+pub async fn delete_reminder(
+    state: State<'_, AppState>,
+    payload: DeleteReminderRequest,
+) -> Result<(), String> {
+    let result = async {
+        let _user = require_session(&state).await?;
+        let removed = sqlx::query("DELETE FROM reminders WHERE id = ?1")
+            .bind(payload.reminder_id)
+            .execute(&state.pool)
+            .await?
+            .rows_affected();
+        if removed == 0 {
+            return Err(AppError::Message("Reminder not found".into()));
+        }
+        Ok(())
+    }
+    .await;
+    result.map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "கொ" typed as க + ெ + ா rather than the precomposed vowel sign ொ.
+    const DECOMPOSED_KO: &str = "\u{0B95}\u{0BC6}\u{0BBE}";
+    const COMPOSED_KO: &str = "\u{0B95}\u{0BCA}";
+
+    #[test]
+    fn saved_content_is_nfc() {
+        let content = format!("# {DECOMPOSED_KO}டு\n");
+        let saved = normalize_for_save(content.clone(), true);
+        assert_eq!(saved, format!("# {COMPOSED_KO}டு\n"));
+        assert_eq!(normalize_for_save(content.clone(), false), content);
+
+        let path = std::env::temp_dir().join(format!("scriptwriter-test-{}.md", Uuid::new_v4()));
+        write_atomic(&path, saved.as_bytes()).unwrap();
+        let on_disk = fs::read_to_string(&path).unwrap();
+        assert!(on_disk.contains(COMPOSED_KO));
+        assert!(!on_disk.contains(DECOMPOSED_KO));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn fuzzy_score_counts_characters_not_bytes() {
+        // Each Tamil letter here is three bytes in UTF-8.
+        let name = "கதை நேரம்";
+        assert_eq!(fuzzy_score("கதை", "கதை", name), Some(3000 - 9));
+        assert_eq!(fuzzy_score("நேரம்", "நேரம்", name), Some(2000 - 4));
+        assert_eq!(fuzzy_score("கநே", "கநே", name), Some(1000 - (6 - 3)));
+        assert_eq!(fuzzy_score("ரக", "ரக", name), None);
+        assert!(fuzzy_score("story", "story", "a story") > fuzzy_score("sty", "sty", "a story"));
+    }
+
+    #[tokio::test]
+    async fn fetch_settings_recreates_a_missing_row() {
+        let state = crate::state::in_memory_state().await;
+        sqlx::query("DELETE FROM settings")
+            .execute(&state.pool)
+            .await
+            .unwrap();
+
+        let settings = fetch_settings(&state).await.unwrap();
+        assert_eq!(settings.preferred_theme, "system");
+        assert_eq!(
+            settings.max_file_size_bytes,
+            crate::models::DEFAULT_MAX_FILE_SIZE_BYTES
+        );
+        assert_eq!(
+            settings.max_concurrent_ml_jobs,
+            crate::models::DEFAULT_MAX_CONCURRENT_ML_JOBS
+        );
+
+        let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM settings")
+            .fetch_one(&state.pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, 1);
+    }
+}
@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -6,6 +10,8 @@ pub enum AppError {
     Message(String),
     #[error("Unauthorized")]
     Unauthorized,
+    #[error("Validation failed")]
+    Validation(BTreeMap<&'static str, String>),
     #[error(transparent)]
     Io(#[from] std::io::Error),
     #[error(transparent)]
@@ -18,4 +24,18 @@ pub enum AppError {
     Anyhow(#[from] anyhow::Error),
 }
 
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            AppError::Validation(fields) => {
+                let mut state = serializer.serialize_struct("AppError", 2)?;
+                state.serialize_field("code", "validation")?;
+                state.serialize_field("fields", fields)?;
+                state.end()
+            }
+            other => serializer.serialize_str(&other.to_string()),
+        }
+    }
+}
+
 pub type AppResult<T> = Result<T, AppError>;
@@ -0,0 +1,1130 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use docx_rs::{
+    AbstractNumbering, AlignmentType, BreakType, Docx, IndentLevel, Level, LevelJc, LevelOverride,
+    LevelText, NumberFormat, Numbering, NumberingId, Paragraph, Run, RunFonts, SpecialIndentType,
+    Start, Style, StyleType,
+};
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+use serde::Serialize;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use crate::error::{AppError, AppResult};
+use crate::filesystem::{reference_candidates, write_atomic, FileKind};
+use crate::language::detect_language;
+use crate::markdown::{
+    extract_headings, link_targets, markdown_options, render_html_with_links, scene_breakdown,
+    strip_front_matter, SceneBreakdown,
+};
+
+const BODY_FONT: &str = "Noto Sans";
+const TAMIL_FONT: &str = "Noto Sans Tamil";
+const CODE_FONT: &str = "Courier New";
+
+// docx-rs always writes its own numbering with id 1, so ours start at 2.
+const BULLET_ABSTRACT_ID: usize = 2;
+const ORDERED_ABSTRACT_ID: usize = 3;
+const BULLET_NUMBERING_ID: usize = 2;
+const MAX_LIST_DEPTH: usize = 6;
+
+fn heading_style(level: HeadingLevel) -> &'static str {
+    match level {
+        HeadingLevel::H1 => "Heading1",
+        HeadingLevel::H2 => "Heading2",
+        HeadingLevel::H3 => "Heading3",
+        HeadingLevel::H4 => "Heading4",
+        HeadingLevel::H5 => "Heading5",
+        HeadingLevel::H6 => "Heading6",
+    }
+}
+
+fn list_abstract(id: usize, format: &str, text: impl Fn(usize) -> String) -> AbstractNumbering {
+    (0..MAX_LIST_DEPTH).fold(AbstractNumbering::new(id), |numbering, depth| {
+        numbering.add_level(
+            Level::new(
+                depth,
+                Start::new(1),
+                NumberFormat::new(format),
+                LevelText::new(text(depth)),
+                LevelJc::new("left"),
+            )
+            .indent(
+                Some(720 * (depth as i32 + 1)),
+                Some(SpecialIndentType::Hanging(360)),
+                None,
+                None,
+            ),
+        )
+    })
+}
+
+fn base_document() -> Docx {
+    let fonts = RunFonts::new()
+        .ascii(BODY_FONT)
+        .hi_ansi(BODY_FONT)
+        .cs(TAMIL_FONT);
+
+    let mut docx = Docx::new()
+        .default_fonts(fonts)
+        .add_style(
+            Style::new("Title", StyleType::Paragraph)
+                .name("Title")
+                .size(48)
+                .bold()
+                .align(AlignmentType::Center),
+        )
+        .add_style(
+            Style::new("Quote", StyleType::Paragraph)
+                .name("Quote")
+                .italic()
+                .indent(Some(720), None, Some(720), None),
+        )
+        .add_style(
+            Style::new("Code", StyleType::Paragraph)
+                .name("Code")
+                .size(20)
+                .fonts(RunFonts::new().ascii(CODE_FONT).hi_ansi(CODE_FONT)),
+        )
+        .add_abstract_numbering(list_abstract(BULLET_ABSTRACT_ID, "bullet", |_| {
+            "•".to_string()
+        }))
+        .add_abstract_numbering(list_abstract(ORDERED_ABSTRACT_ID, "decimal", |depth| {
+            format!("%{}.", depth + 1)
+        }))
+        .add_numbering(Numbering::new(BULLET_NUMBERING_ID, BULLET_ABSTRACT_ID));
+
+    for (index, size) in [36, 30, 26, 24, 22, 22].into_iter().enumerate() {
+        let level = index + 1;
+        docx = docx.add_style(
+            Style::new(format!("Heading{level}"), StyleType::Paragraph)
+                .name(format!("Heading {level}"))
+                .size(size)
+                .bold()
+                .outline_lvl(index),
+        );
+    }
+
+    docx
+}
+
+#[derive(Default)]
+struct Formatting {
+    bold: usize,
+    italic: usize,
+    strike: usize,
+    code: bool,
+}
+
+impl Formatting {
+    fn run(&self, text: &str) -> Run {
+        let mut run = Run::new().add_text(text);
+        if self.bold > 0 {
+            run = run.bold();
+        }
+        if self.italic > 0 {
+            run = run.italic();
+        }
+        if self.strike > 0 {
+            run = run.strike();
+        }
+        if self.code {
+            run = run.fonts(RunFonts::new().ascii(CODE_FONT).hi_ansi(CODE_FONT));
+        }
+        run
+    }
+}
+
+struct DocxBuilder {
+    paragraphs: Vec<Paragraph>,
+    numberings: Vec<Numbering>,
+    paragraph: Option<Paragraph>,
+    formatting: Formatting,
+    lists: Vec<usize>,
+    next_numbering_id: usize,
+    quote_depth: usize,
+    table_cell: usize,
+    in_code_block: bool,
+}
+
+impl DocxBuilder {
+    fn new() -> Self {
+        Self {
+            paragraphs: Vec::new(),
+            numberings: Vec::new(),
+            paragraph: None,
+            formatting: Formatting::default(),
+            lists: Vec::new(),
+            next_numbering_id: BULLET_NUMBERING_ID + 1,
+            quote_depth: 0,
+            table_cell: 0,
+            in_code_block: false,
+        }
+    }
+
+    fn start_paragraph(&mut self, style: Option<&str>) {
+        self.flush();
+        let mut paragraph = Paragraph::new();
+        if let Some(style) = style {
+            paragraph = paragraph.style(style);
+        } else if self.quote_depth > 0 {
+            paragraph = paragraph.style("Quote");
+        }
+        self.paragraph = Some(paragraph);
+    }
+
+    fn flush(&mut self) {
+        if let Some(paragraph) = self.paragraph.take() {
+            self.paragraphs.push(paragraph);
+        }
+    }
+
+    fn push_run(&mut self, run: Run) {
+        if self.paragraph.is_none() {
+            self.start_paragraph(None);
+        }
+        self.paragraph = self
+            .paragraph
+            .take()
+            .map(|paragraph| paragraph.add_run(run));
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let run = self.formatting.run(text);
+        self.push_run(run);
+    }
+
+    fn start_list(&mut self, start: Option<u64>) {
+        let numbering_id = match start {
+            Some(start) => {
+                let id = self.next_numbering_id;
+                self.next_numbering_id += 1;
+                self.numberings.push(
+                    Numbering::new(id, ORDERED_ABSTRACT_ID)
+                        .add_override(LevelOverride::new(0).start(start as usize)),
+                );
+                id
+            }
+            None => BULLET_NUMBERING_ID,
+        };
+        self.lists.push(numbering_id);
+    }
+
+    fn start_item(&mut self) {
+        self.start_paragraph(None);
+        let depth = self.lists.len().saturating_sub(1).min(MAX_LIST_DEPTH - 1);
+        if let Some(numbering_id) = self.lists.last().copied() {
+            self.paragraph = self.paragraph.take().map(|paragraph| {
+                paragraph.numbering(NumberingId::new(numbering_id), IndentLevel::new(depth))
+            });
+        }
+    }
+
+    fn push_code_lines(&mut self, text: &str) {
+        for line in text.trim_end_matches('\n').split('\n') {
+            self.start_paragraph(Some("Code"));
+            self.push_run(Run::new().add_text(line));
+        }
+        self.flush();
+    }
+
+    fn handle(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                self.start_paragraph(Some(heading_style(level)))
+            }
+            Event::Start(Tag::Paragraph) => {
+                let in_fresh_item = self
+                    .paragraph
+                    .as_ref()
+                    .is_some_and(|paragraph| paragraph.children().is_empty());
+                if !in_fresh_item {
+                    self.start_paragraph(None);
+                }
+            }
+            Event::End(TagEnd::Heading(_) | TagEnd::Paragraph | TagEnd::Item) => self.flush(),
+            Event::Start(Tag::BlockQuote(_)) => {
+                self.flush();
+                self.quote_depth += 1;
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            Event::Start(Tag::List(start)) => {
+                self.flush();
+                self.start_list(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                self.flush();
+                self.lists.pop();
+            }
+            Event::Start(Tag::Item) => self.start_item(),
+            Event::Start(Tag::CodeBlock(_)) => {
+                self.flush();
+                self.in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => self.in_code_block = false,
+            Event::Start(Tag::Emphasis) => self.formatting.italic += 1,
+            Event::End(TagEnd::Emphasis) => {
+                self.formatting.italic = self.formatting.italic.saturating_sub(1)
+            }
+            Event::Start(Tag::Strong) => self.formatting.bold += 1,
+            Event::End(TagEnd::Strong) => {
+                self.formatting.bold = self.formatting.bold.saturating_sub(1)
+            }
+            Event::Start(Tag::Strikethrough) => self.formatting.strike += 1,
+            Event::End(TagEnd::Strikethrough) => {
+                self.formatting.strike = self.formatting.strike.saturating_sub(1)
+            }
+            Event::Start(Tag::TableRow | Tag::TableHead) => {
+                self.table_cell = 0;
+                self.start_paragraph(None);
+            }
+            Event::End(TagEnd::TableRow | TagEnd::TableHead) => self.flush(),
+            Event::Start(Tag::TableCell) => {
+                if self.table_cell > 0 {
+                    self.push_run(Run::new().add_tab());
+                }
+                self.table_cell += 1;
+            }
+            Event::Text(text) if self.in_code_block => self.push_code_lines(&text),
+            Event::Text(text) => self.push_text(&text),
+            Event::Code(text) => {
+                self.formatting.code = true;
+                self.push_text(&text);
+                self.formatting.code = false;
+            }
+            Event::SoftBreak => self.push_text(" "),
+            Event::HardBreak => self.push_run(Run::new().add_break(BreakType::TextWrapping)),
+            Event::Rule => {
+                self.start_paragraph(None);
+                self.paragraph = self
+                    .paragraph
+                    .take()
+                    .map(|paragraph| paragraph.align(AlignmentType::Center));
+                self.push_run(Run::new().add_text("* * *"));
+                self.flush();
+            }
+            Event::TaskListMarker(checked) => self.push_text(if checked { "☑ " } else { "☐ " }),
+            Event::FootnoteReference(label) => self.push_text(&format!("[{label}]")),
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Docx {
+        self.flush();
+        let docx = self
+            .numberings
+            .into_iter()
+            .fold(base_document(), |docx, numbering| {
+                docx.add_numbering(numbering)
+            });
+        self.paragraphs
+            .into_iter()
+            .fold(docx, |docx, paragraph| docx.add_paragraph(paragraph))
+    }
+}
+
+pub fn markdown_to_docx(content: &str, title: Option<&str>) -> AppResult<Vec<u8>> {
+    let mut builder = DocxBuilder::new();
+
+    if let Some(title) = title {
+        builder.start_paragraph(Some("Title"));
+        builder.push_run(Run::new().add_text(title));
+        builder.push_run(Run::new().add_break(BreakType::Page));
+        builder.flush();
+    }
+
+    for event in Parser::new_ext(content, markdown_options()) {
+        builder.handle(event);
+    }
+
+    let mut buffer = Cursor::new(Vec::new());
+    builder
+        .finish()
+        .build()
+        .pack(&mut buffer)
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+    Ok(buffer.into_inner())
+}
+
+/// Spreadsheet apps only detect UTF-8 (and so Tamil text) with a byte-order mark.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+pub fn breakdown_csv(scenes: &[SceneBreakdown]) -> AppResult<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(UTF8_BOM.to_vec());
+    writer
+        .write_record(["Scene", "Line", "Characters", "Word count", "Location"])
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+    for scene in scenes {
+        writer
+            .write_record([
+                scene.heading.as_str(),
+                &scene.line.to_string(),
+                &scene.characters.join(", "),
+                &scene.word_count.to_string(),
+                scene.location.as_deref().unwrap_or_default(),
+            ])
+            .map_err(|err| AppError::Anyhow(err.into()))?;
+    }
+    writer
+        .into_inner()
+        .map_err(|err| AppError::Message(err.to_string()))
+}
+
+/// Where [`export_html_site`] writes, relative to the project root.
+pub const SITE_DIR: &str = "exports/site";
+const SITE_INDEX: &str = "index.html";
+const SITE_STYLESHEET: &str = "style.css";
+
+pub struct SiteExport {
+    pub pages: usize,
+    pub assets: usize,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn url_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .replace(' ', "%20")
+}
+
+/// The page a draft renders to. A root `index.md` is moved aside so it does
+/// not replace the generated index.
+fn site_page(draft: &Path) -> PathBuf {
+    let page = draft.with_extension("html");
+    if page == Path::new(SITE_INDEX) {
+        PathBuf::from("index.md.html")
+    } else {
+        page
+    }
+}
+
+/// Just the prose of a draft, so link targets and markup do not sway
+/// language detection.
+fn prose_text(content: &str) -> String {
+    Parser::new_ext(content, markdown_options())
+        .filter_map(|event| match event {
+            Event::Text(text) => Some(text),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The project file a link in `folder` points at, if it exists.
+fn resolve_link(root: &Path, folder: &Path, target: &str) -> Option<PathBuf> {
+    let path = target.split(['#', '?']).next()?.replace("%20", " ");
+    if path.is_empty() {
+        return None;
+    }
+    reference_candidates(folder, &path)
+        .into_iter()
+        .find(|candidate| root.join(candidate).is_file())
+}
+
+#[derive(Default)]
+struct NavFolder {
+    folders: BTreeMap<String, NavFolder>,
+    pages: Vec<(String, PathBuf)>,
+}
+
+impl NavFolder {
+    fn insert(&mut self, draft: &Path) {
+        let folder =
+            draft
+                .parent()
+                .unwrap_or(Path::new(""))
+                .iter()
+                .fold(self, |folder, component| {
+                    folder
+                        .folders
+                        .entry(component.to_string_lossy().to_string())
+                        .or_default()
+                });
+        let label = draft
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        folder.pages.push((label, site_page(draft)));
+    }
+
+    fn render(&self, prefix: &str, current: Option<&Path>, out: &mut String) {
+        out.push_str("<ul>");
+        for (name, folder) in &self.folders {
+            out.push_str(&format!(
+                "<li><span class=\"folder\">{}</span>",
+                escape_html(name)
+            ));
+            folder.render(prefix, current, out);
+            out.push_str("</li>");
+        }
+        for (label, page) in &self.pages {
+            let marker = if current == Some(page.as_path()) {
+                " aria-current=\"page\""
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "<li><a href=\"{prefix}{}\"{marker}>{}</a></li>",
+                url_path(page),
+                escape_html(label)
+            ));
+        }
+        out.push_str("</ul>");
+    }
+}
+
+fn site_stylesheet() -> String {
+    format!(
+        "body {{ margin: 0; display: flex; font-family: \"{TAMIL_FONT}\", \"Latha\", \"Tamil Sangam MN\", \"{BODY_FONT}\", system-ui, sans-serif; line-height: 1.7; color: #1f2328; }}\n\
+         nav {{ flex: 0 0 16rem; padding: 1.5rem 1rem; border-right: 1px solid #d0d7de; background: #f6f8fa; min-height: 100vh; box-sizing: border-box; }}\n\
+         nav ul {{ list-style: none; padding-left: 1rem; margin: 0.25rem 0; }}\n\
+         nav > ul {{ padding-left: 0; }}\n\
+         nav a {{ color: inherit; text-decoration: none; }}\n\
+         nav a[aria-current] {{ font-weight: 600; }}\n\
+         nav .home {{ display: block; font-weight: 600; margin-bottom: 1rem; }}\n\
+         nav .folder {{ color: #656d76; }}\n\
+         main {{ flex: 1; max-width: 48rem; padding: 2rem 3rem; }}\n\
+         img {{ max-width: 100%; }}\n\
+         pre, code {{ font-family: \"{CODE_FONT}\", monospace; }}\n"
+    )
+}
+
+fn site_document(
+    project: &str,
+    title: &str,
+    lang: &str,
+    prefix: &str,
+    nav: &str,
+    body: &str,
+) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{lang}\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<link rel=\"stylesheet\" href=\"{prefix}{SITE_STYLESHEET}\">\n\
+         </head>\n<body>\n<nav><a class=\"home\" href=\"{prefix}{SITE_INDEX}\">{project}</a>{nav}</nav>\n\
+         <main lang=\"{lang}\">\n{body}</main>\n</body>\n</html>\n",
+        project = escape_html(project),
+        title = escape_html(title),
+    )
+}
+
+/// Renders `drafts` (project-relative markdown paths) into a static site
+/// under [`SITE_DIR`], replacing any earlier export. Pages keep the project's
+/// folder layout so relative links between drafts and to assets still work;
+/// links to drafts are pointed at their pages and root-anchored links are
+/// made relative. Linked files that are not drafts are copied alongside.
+/// Each page declares the language detected in it, falling back to
+/// `language`; the index shows the project name and `description`.
+///
+/// The site is built in a hidden folder beside [`SITE_DIR`] and swapped in
+/// once complete, so a failed export leaves the previous site untouched.
+pub fn export_html_site(
+    root: &Path,
+    project: &str,
+    description: Option<&str>,
+    language: &str,
+    drafts: &[PathBuf],
+) -> AppResult<SiteExport> {
+    let site = root.join(SITE_DIR);
+    let staging = site.with_file_name(format!(".site-{}.tmp", Uuid::new_v4()));
+    let built = build_site(root, &staging, project, description, language, drafts);
+    if built.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+        return built;
+    }
+
+    let previous = site.with_file_name(format!(".site-{}.old", Uuid::new_v4()));
+    if site.exists() {
+        fs::rename(&site, &previous)?;
+    }
+    if let Err(err) = fs::rename(&staging, &site) {
+        let _ = fs::rename(&previous, &site);
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err.into());
+    }
+    if previous.exists() {
+        if let Err(err) = fs::remove_dir_all(&previous) {
+            log::warn!("Could not remove the previous site export: {err}");
+        }
+    }
+    built
+}
+
+fn build_site(
+    root: &Path,
+    site: &Path,
+    project: &str,
+    description: Option<&str>,
+    language: &str,
+    drafts: &[PathBuf],
+) -> AppResult<SiteExport> {
+    fs::create_dir_all(site)?;
+
+    let draft_set: HashSet<&Path> = drafts.iter().map(PathBuf::as_path).collect();
+    let mut nav = NavFolder::default();
+    for draft in drafts {
+        nav.insert(draft);
+    }
+
+    let mut assets = BTreeSet::new();
+    for draft in drafts {
+        let content = fs::read_to_string(root.join(draft))?;
+        let body = strip_front_matter(&content);
+        let folder = draft.parent().unwrap_or(Path::new(""));
+        let prefix = "../".repeat(folder.components().count());
+
+        for target in link_targets(&body) {
+            if let Some(found) = resolve_link(root, folder, &target) {
+                if !draft_set.contains(found.as_path()) {
+                    assets.insert(found);
+                }
+            }
+        }
+
+        let html = render_html_with_links(&body, |target| {
+            let found = resolve_link(root, folder, target)?;
+            let linked = if draft_set.contains(found.as_path()) {
+                site_page(&found)
+            } else {
+                found
+            };
+            let fragment = target.find('#').map_or("", |index| &target[index..]);
+            Some(format!("{prefix}{}{fragment}", url_path(&linked)))
+        });
+
+        let page = site_page(draft);
+        let mut nav_html = String::new();
+        nav.render(&prefix, Some(&page), &mut nav_html);
+        let title = draft
+            .file_stem()
+            .map(|stem| format!("{} · {project}", stem.to_string_lossy()))
+            .unwrap_or_else(|| project.to_string());
+        let lang = detect_language(&prose_text(&body))
+            .primary
+            .unwrap_or_else(|| language.to_string());
+
+        let target = site.join(&page);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let document = site_document(project, &title, &lang, &prefix, &nav_html, &html);
+        write_atomic(&target, document.as_bytes())?;
+    }
+
+    let mut nav_html = String::new();
+    nav.render("", None, &mut nav_html);
+    let mut index_body = format!("<h1>{}</h1>\n", escape_html(project));
+    if let Some(description) = description.map(str::trim).filter(|text| !text.is_empty()) {
+        index_body.push_str(&format!("<p>{}</p>\n", escape_html(description)));
+    }
+    let index = site_document(project, project, language, "", &nav_html, &index_body);
+    write_atomic(&site.join(SITE_INDEX), index.as_bytes())?;
+    write_atomic(&site.join(SITE_STYLESHEET), site_stylesheet().as_bytes())?;
+
+    for asset in &assets {
+        let target = site.join(asset);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(root.join(asset), &target)?;
+    }
+
+    Ok(SiteExport {
+        pages: drafts.len(),
+        assets: assets.len(),
+    })
+}
+
+pub const TABLE_READS_DIR: &str = "audio/table-reads";
+
+#[derive(Debug, Serialize)]
+pub struct PlaylistTrack {
+    /// Project-relative path of the audio file.
+    pub path: String,
+    pub title: String,
+    /// The draft the track was matched to.
+    pub scene: Option<String>,
+    pub characters: Vec<String>,
+}
+
+fn forward_slashes(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Whether a recording (relative to the table-reads folder) could belong to
+/// the draft named `stem`: it shares the stem exactly, extends it after a
+/// separator (`scene1-02.wav`) or sits in a folder named after it.
+fn recording_of(recording: &Path, stem: &str) -> bool {
+    let in_folder = recording
+        .parent()
+        .and_then(|parent| parent.components().next())
+        .is_some_and(|folder| folder.as_os_str().to_string_lossy().to_lowercase() == stem);
+    let name = recording
+        .file_stem()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    in_folder
+        || name == stem
+        || name
+            .strip_prefix(stem)
+            .is_some_and(|rest| rest.starts_with(['-', '_', '.', ' ']))
+}
+
+/// Table-read recordings in scene order. Drafts (project-relative, in
+/// reading order) claim their recordings first; anything left over follows
+/// by path with no scene attached.
+pub fn table_read_playlist(root: &Path, drafts: &[PathBuf]) -> AppResult<Vec<PlaylistTrack>> {
+    let folder = root.join(TABLE_READS_DIR);
+    let mut recordings = WalkDir::new(&folder)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry.file_type().is_file() && FileKind::from_path(entry.path()) == FileKind::Audio
+        })
+        .filter_map(|entry| {
+            entry
+                .path()
+                .strip_prefix(&folder)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .collect::<Vec<_>>();
+    recordings.sort();
+
+    let stems = drafts
+        .iter()
+        .map(|draft| {
+            draft
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_lowercase())
+        })
+        .collect::<Vec<_>>();
+    // A recording goes to the most specific draft it matches, so
+    // `scene1-2-take1.wav` belongs to `scene1-2.md` rather than `scene1.md`.
+    let owner = |recording: &Path| {
+        stems
+            .iter()
+            .enumerate()
+            .filter_map(|(index, stem)| Some((index, stem.as_deref()?)))
+            .filter(|(_, stem)| recording_of(recording, stem))
+            .max_by_key(|(index, stem)| (stem.len(), std::cmp::Reverse(*index)))
+            .map(|(index, _)| index)
+    };
+
+    let mut tracks = Vec::with_capacity(recordings.len());
+    for (index, draft) in drafts.iter().enumerate() {
+        let (matched, rest): (Vec<_>, Vec<_>) = recordings
+            .into_iter()
+            .partition(|recording| owner(recording) == Some(index));
+        recordings = rest;
+        if matched.is_empty() {
+            continue;
+        }
+
+        let content = fs::read_to_string(root.join(draft))?;
+        let title = extract_headings(&strip_front_matter(&content))
+            .into_iter()
+            .next()
+            .map(|heading| heading.text)
+            .unwrap_or_else(|| {
+                draft
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string()
+            });
+        let mut characters: Vec<String> = Vec::new();
+        for name in scene_breakdown(&content)
+            .into_iter()
+            .flat_map(|scene| scene.characters)
+        {
+            if !characters.contains(&name) {
+                characters.push(name);
+            }
+        }
+
+        let parts = matched.len();
+        for (index, recording) in matched.into_iter().enumerate() {
+            tracks.push(PlaylistTrack {
+                path: forward_slashes(&Path::new(TABLE_READS_DIR).join(recording)),
+                title: if parts > 1 {
+                    format!("{title} ({}/{parts})", index + 1)
+                } else {
+                    title.clone()
+                },
+                scene: Some(forward_slashes(draft)),
+                characters: characters.clone(),
+            });
+        }
+    }
+
+    for recording in recordings {
+        tracks.push(PlaylistTrack {
+            path: forward_slashes(&Path::new(TABLE_READS_DIR).join(&recording)),
+            title: recording
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            scene: None,
+            characters: Vec::new(),
+        });
+    }
+    Ok(tracks)
+}
+
+/// An extended M3U playlist meant to sit in `exports/`, so tracks point one
+/// folder up. Characters are folded into each title for players that only
+/// show `#EXTINF` text.
+pub fn playlist_m3u(project: &str, tracks: &[PlaylistTrack]) -> String {
+    let mut playlist = format!("#EXTM3U\n#PLAYLIST:{project}\n");
+    for track in tracks {
+        let mut title = track.title.clone();
+        if !track.characters.is_empty() {
+            title.push_str(&format!(" – {}", track.characters.join(", ")));
+        }
+        playlist.push_str(&format!("#EXTINF:-1,{title}\n../{}\n", track.path));
+    }
+    playlist
+}
+
+pub const RESEARCH_DIR: &str = "research";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    Url,
+    Citation,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Reference {
+    pub kind: ReferenceKind,
+    /// The URL, or the citation line as written.
+    pub text: String,
+    /// Link text for URLs written as markdown links.
+    pub title: Option<String>,
+    /// Project-relative research files that mention it.
+    pub sources: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReferenceSource {
+    pub path: String,
+    pub references: usize,
+}
+
+/// URLs in a line, each with the link text when written as `[text](url)`.
+fn line_urls(line: &str) -> Vec<(String, Option<String>)> {
+    let mut urls = Vec::new();
+    let mut searched = 0;
+    while let Some(found) = ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| line[searched..].find(scheme))
+        .min()
+    {
+        let start = searched + found;
+        let length = line[start..]
+            .find(|ch: char| ch.is_whitespace() || matches!(ch, ')' | '>' | ']' | '"' | '\'' | '<'))
+            .unwrap_or(line.len() - start);
+        searched = start + length;
+        let url = line[start..searched].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        if url.len() <= "https://".len() {
+            continue;
+        }
+        let title = line[..start]
+            .strip_suffix("](")
+            .and_then(|before| before.rfind('[').map(|open| &before[open + 1..]))
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty() && title != url);
+        urls.push((url.to_string(), title));
+    }
+    urls
+}
+
+/// A line that reads like a bibliography entry: a DOI or ISBN, a footnote
+/// definition, or an author-year reference such as `Rao, K. (2019). ...`.
+fn is_citation(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    if lower.contains("doi:") || lower.contains("doi.org/") || lower.contains("isbn") {
+        return true;
+    }
+    if line.starts_with("[^") && line.contains("]:") {
+        return true;
+    }
+    let has_year = line.char_indices().any(|(index, ch)| {
+        ch == '('
+            && line[index + 1..].get(..5).is_some_and(|year| {
+                year.ends_with(')')
+                    && year[..4].bytes().all(|byte| byte.is_ascii_digit())
+                    && matches!(&year[..2], "18" | "19" | "20")
+            })
+    });
+    has_year && line.split_whitespace().count() >= 4
+}
+
+fn url_key(url: &str) -> String {
+    url.trim_end_matches('/').to_lowercase()
+}
+
+fn citation_key(citation: &str) -> String {
+    citation
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Collects URLs and citation-like lines from the markdown under
+/// `research/`, deduplicated, in the order first seen. Returns the
+/// references and the files that contributed at least one.
+pub fn collect_references(root: &Path) -> AppResult<(Vec<Reference>, Vec<ReferenceSource>)> {
+    let folder = root.join(RESEARCH_DIR);
+    let mut files = WalkDir::new(&folder)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry.file_type().is_file() && FileKind::from_path(entry.path()) == FileKind::Markdown
+        })
+        .map(|entry| entry.into_path())
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut references: Vec<Reference> = Vec::new();
+    let mut keys: BTreeMap<String, usize> = BTreeMap::new();
+    let mut sources = Vec::new();
+    for file in files {
+        let relative = forward_slashes(file.strip_prefix(root).unwrap_or(&file));
+        let content = fs::read_to_string(&file)?;
+        let mut found = BTreeSet::new();
+        let mut add =
+            |kind: ReferenceKind, key: String, text: String, title: Option<String>| match keys
+                .get(&key)
+            {
+                Some(&index) => {
+                    found.insert(index);
+                    let reference = &mut references[index];
+                    if !reference.sources.contains(&relative) {
+                        reference.sources.push(relative.clone());
+                    }
+                    if reference.title.is_none() {
+                        reference.title = title;
+                    }
+                }
+                None => {
+                    found.insert(references.len());
+                    keys.insert(key, references.len());
+                    references.push(Reference {
+                        kind,
+                        text,
+                        title,
+                        sources: vec![relative.clone()],
+                    });
+                }
+            };
+
+        for line in strip_front_matter(&content).lines() {
+            let line = line
+                .trim()
+                .trim_start_matches(['-', '*', '+', '>'])
+                .trim_start();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // A citation keeps its URL inline rather than listing it twice.
+            if is_citation(line) {
+                let citation = match line.split_once("]:") {
+                    Some((_, rest)) if line.starts_with("[^") => rest.trim(),
+                    _ => line,
+                };
+                add(
+                    ReferenceKind::Citation,
+                    format!("citation:{}", citation_key(citation)),
+                    citation.to_string(),
+                    None,
+                );
+                continue;
+            }
+            for (url, title) in line_urls(line) {
+                add(
+                    ReferenceKind::Url,
+                    format!("url:{}", url_key(&url)),
+                    url,
+                    title,
+                );
+            }
+        }
+        if !found.is_empty() {
+            sources.push(ReferenceSource {
+                path: relative,
+                references: found.len(),
+            });
+        }
+    }
+    Ok((references, sources))
+}
+
+pub fn references_markdown(project: &str, references: &[Reference]) -> String {
+    let mut document = format!("# References – {project}\n");
+    for (kind, heading) in [
+        (ReferenceKind::Url, "Links"),
+        (ReferenceKind::Citation, "Citations"),
+    ] {
+        let entries = references
+            .iter()
+            .filter(|reference| reference.kind == kind)
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            continue;
+        }
+        document.push_str(&format!("\n## {heading}\n\n"));
+        for reference in entries {
+            let entry = match (&reference.title, kind) {
+                (Some(title), _) => format!("[{title}]({})", reference.text),
+                (None, ReferenceKind::Url) => format!("<{}>", reference.text),
+                (None, ReferenceKind::Citation) => reference.text.clone(),
+            };
+            document.push_str(&format!("- {entry} _({})_\n", reference.sources.join(", ")));
+        }
+    }
+    document
+}
+
+fn bibtex_escape(text: &str) -> String {
+    text.replace('\\', "\\textbackslash{}")
+        .replace('{', "\\{")
+        .replace('}', "\\}")
+        .replace('%', "\\%")
+        .replace('&', "\\&")
+        .replace('#', "\\#")
+}
+
+/// Every reference as a `@misc` entry; free-form citations go in `note` since
+/// their fields cannot be split reliably. The research files are kept in a
+/// `source` field, which BibTeX ignores.
+pub fn references_bibtex(references: &[Reference]) -> String {
+    let mut document = String::new();
+    for (index, reference) in references.iter().enumerate() {
+        document.push_str(&format!("@misc{{ref{},\n", index + 1));
+        match reference.kind {
+            ReferenceKind::Url => {
+                if let Some(title) = &reference.title {
+                    document.push_str(&format!("  title = {{{}}},\n", bibtex_escape(title)));
+                }
+                document.push_str(&format!(
+                    "  howpublished = {{\\url{{{}}}}},\n",
+                    reference.text.replace('{', "%7B").replace('}', "%7D")
+                ));
+            }
+            ReferenceKind::Citation => {
+                document.push_str(&format!(
+                    "  note = {{{}}},\n",
+                    bibtex_escape(&reference.text)
+                ));
+            }
+        }
+        document.push_str(&format!(
+            "  source = {{{}}}\n}}\n\n",
+            bibtex_escape(&reference.sources.join(", "))
+        ));
+    }
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_read_recordings_go_to_the_matching_draft() {
+        let root = std::env::temp_dir().join(format!("scriptwriter-test-{}", Uuid::new_v4()));
+        let reads = root.join(TABLE_READS_DIR);
+        fs::create_dir_all(reads.join("scene2")).unwrap();
+        let drafts = ["scene1.md", "scene1-2.md", "scene10.md", "scene2.md"]
+            .map(PathBuf::from)
+            .to_vec();
+        for draft in &drafts {
+            fs::write(root.join(draft), "# Scene\n").unwrap();
+        }
+        for recording in [
+            "scene1.wav",
+            "scene1-take2.wav",
+            "scene1-2.wav",
+            "scene1-2_take1.wav",
+            "scene10.wav",
+            "scene2/part1.wav",
+            "scene100.wav",
+        ] {
+            fs::write(reads.join(recording), b"").unwrap();
+        }
+
+        let scenes = table_read_playlist(&root, &drafts)
+            .unwrap()
+            .into_iter()
+            .map(|track| {
+                let name = track.path.trim_start_matches(TABLE_READS_DIR).to_string();
+                (name, track.scene)
+            })
+            .collect::<Vec<_>>();
+        let scene = |name: &str| Some(name.to_string());
+        assert_eq!(
+            scenes,
+            vec![
+                ("/scene1-take2.wav".to_string(), scene("scene1.md")),
+                ("/scene1.wav".to_string(), scene("scene1.md")),
+                ("/scene1-2.wav".to_string(), scene("scene1-2.md")),
+                ("/scene1-2_take1.wav".to_string(), scene("scene1-2.md")),
+                ("/scene10.wav".to_string(), scene("scene10.md")),
+                ("/scene2/part1.wav".to_string(), scene("scene2.md")),
+                ("/scene100.wav".to_string(), None),
+            ]
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn failed_site_export_keeps_the_previous_site() {
+        let root = std::env::temp_dir().join(format!("scriptwriter-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("drafts")).unwrap();
+        fs::write(root.join("drafts/scene1.md"), "# Scene 1\n").unwrap();
+        let site = root.join(SITE_DIR);
+
+        let drafts = vec![PathBuf::from("drafts/scene1.md")];
+        export_html_site(&root, "Film", None, "en", &drafts).unwrap();
+        assert!(site.join("drafts/scene1.html").is_file());
+
+        let missing = vec![
+            PathBuf::from("drafts/scene1.md"),
+            PathBuf::from("drafts/gone.md"),
+        ];
+        assert!(export_html_site(&root, "Film", None, "en", &missing).is_err());
+        assert!(site.join("drafts/scene1.html").is_file());
+        assert!(site.join(SITE_INDEX).is_file());
+        let leftovers: Vec<_> = fs::read_dir(root.join("exports"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(leftovers, vec!["site"]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,7 +1,16 @@
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use walkdir::WalkDir;
 
 use crate::error::{AppError, AppResult};
+use crate::markdown::link_targets;
 use crate::models::ProjectTemplate;
 
 pub fn ensure_template_scaffold(root: &Path, template: &ProjectTemplate) -> AppResult<()> {
@@ -22,13 +31,937 @@ pub fn ensure_projects_root(storage_root: &Path) -> AppResult<PathBuf> {
     Ok(projects_root)
 }
 
+/// Written into a relocated project's folder and holding the project id, so a
+/// folder outside the projects root can be recognised as that project's own.
+pub const PROJECT_MARKER: &str = ".scriptwriter-project";
+
+pub fn write_project_marker(base_path: &Path, project_id: &str) -> AppResult<()> {
+    write_atomic(&base_path.join(PROJECT_MARKER), project_id.as_bytes())
+}
+
+pub fn remove_project_dir(
+    storage_root: &Path,
+    base_path: &Path,
+    project_id: &str,
+) -> AppResult<()> {
+    if !base_path.exists() {
+        return Ok(());
+    }
+
+    let projects_root = storage_root.join("projects").canonicalize()?;
+    let resolved = base_path.canonicalize()?;
+    let inside_projects_root = resolved != projects_root && resolved.starts_with(&projects_root);
+    // Relocated projects live elsewhere; only delete them when the folder
+    // carries this project's marker.
+    let relocated_project_dir = fs::read_to_string(resolved.join(PROJECT_MARKER))
+        .is_ok_and(|marker| marker.trim() == project_id);
+    if !inside_projects_root && !relocated_project_dir {
+        return Err(AppError::Message(
+            "Refusing to delete a directory that is not a project folder".into(),
+        ));
+    }
+    if !resolved.is_dir() {
+        return Err(AppError::Message("Project path is not a directory".into()));
+    }
+
+    fs::remove_dir_all(&resolved)?;
+    Ok(())
+}
+
+fn format_megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+pub fn ensure_free_space(path: &Path, required: u64) -> AppResult<()> {
+    let available = fs4::available_space(path)?;
+    if available < required {
+        return Err(AppError::Message(format!(
+            "Not enough free space: {} needed, {} available",
+            format_megabytes(required),
+            format_megabytes(available)
+        )));
+    }
+    Ok(())
+}
+
+pub fn ensure_writable(dir: &Path) -> AppResult<()> {
+    let probe = dir.join(format!(".scriptwriter-probe-{}", Uuid::new_v4()));
+    fs::write(&probe, b"")
+        .map_err(|err| AppError::Message(format!("{} is not writable: {err}", dir.display())))?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+pub fn copy_dir_all(source: &Path, target: &Path) -> AppResult<()> {
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|err| AppError::Anyhow(err.into()))?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|err| AppError::Anyhow(err.into()))?;
+        let destination = target.join(relative);
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            fs::create_dir_all(&destination)?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), &destination)?;
+        } else {
+            log::warn!("Skipping non-regular file {}", entry.path().display());
+        }
+    }
+    Ok(())
+}
+
+pub fn verify_copy(source: &Path, target: &Path) -> AppResult<()> {
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|err| AppError::Anyhow(err.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|err| AppError::Anyhow(err.into()))?;
+        let expected = entry
+            .metadata()
+            .map_err(|err| AppError::Anyhow(err.into()))?
+            .len();
+        let copied = fs::metadata(target.join(relative))
+            .map(|meta| meta.len())
+            .ok();
+        if copied != Some(expected) {
+            return Err(AppError::Message(format!(
+                "Copy verification failed for {}",
+                relative.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectIssue {
+    pub kind: &'static str,
+    pub path: String,
+    pub message: String,
+}
+
+pub fn verify_project_layout(
+    base_path: &Path,
+    template: Option<&ProjectTemplate>,
+) -> Vec<ProjectIssue> {
+    let mut issues = Vec::new();
+
+    if !base_path.exists() {
+        issues.push(ProjectIssue {
+            kind: "missing_root",
+            path: String::new(),
+            message: format!("Project folder {} does not exist", base_path.display()),
+        });
+        return issues;
+    }
+    if !base_path.is_dir() {
+        issues.push(ProjectIssue {
+            kind: "root_not_directory",
+            path: String::new(),
+            message: format!("Project path {} is not a directory", base_path.display()),
+        });
+        return issues;
+    }
+
+    if let Some(template) = template {
+        for relative in template.directory_structure {
+            let path = base_path.join(relative);
+            if !path.is_dir() {
+                issues.push(ProjectIssue {
+                    kind: "missing_directory",
+                    path: relative.to_string(),
+                    message: format!("Template folder '{relative}' is missing"),
+                });
+            }
+        }
+    }
+
+    if !base_path.join("README.md").is_file() {
+        issues.push(ProjectIssue {
+            kind: "missing_readme",
+            path: "README.md".into(),
+            message: "README.md is missing".into(),
+        });
+    }
+
+    issues
+}
+
 pub fn write_markdown_placeholder(path: &Path, heading: &str, body: &str) -> AppResult<()> {
     if path.exists() {
         return Ok(());
     }
 
     let content = format!("# {heading}\n\n{body}\n");
-    fs::write(path, content)?;
+    write_atomic(path, content.as_bytes())
+}
+
+/// Markdown files under `root`, skipping hidden folders such as `.snapshots`.
+fn markdown_entries(root: &Path) -> impl Iterator<Item = walkdir::DirEntry> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| FileKind::from_path(entry.path()) == FileKind::Markdown)
+}
+
+/// Every markdown file in the project, hidden folders excluded, sorted.
+pub fn project_markdown_files(root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = markdown_entries(root)
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Newest-first markdown files under `root`.
+pub fn recent_markdown_files(root: &Path, limit: usize) -> Vec<(PathBuf, SystemTime)> {
+    let mut files: Vec<(PathBuf, SystemTime)> = markdown_entries(root)
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((entry.into_path(), modified))
+        })
+        .collect();
+
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    files.truncate(limit);
+    files
+}
+
+pub const SCRATCH_DIR: &str = "drafts/scratch";
+
+/// Drafts in reading order (sorted by folder, then name), leaving out the
+/// project README, anything under `exports/` and, unless asked for, scratch
+/// files.
+pub fn draft_markdown_files(root: &Path, include_scratch: bool) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = markdown_entries(root)
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            !relative.starts_with("exports")
+                && relative != Path::new("README.md")
+                && (include_scratch || !relative.starts_with(SCRATCH_DIR))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Folders whose files are expected to be referenced from drafts.
+pub const ASSET_DIRS: &[&str] = &["assets", "audio"];
+
+#[derive(Debug, Serialize)]
+pub struct OrphanedAsset {
+    pub path: String,
+    pub size: u64,
+}
+
+/// Resolves `.` and `..` without touching the disk; `None` if the path would
+/// climb out of the project.
+fn normalize_relative(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(normalized)
+}
+
+/// Links that leave the project: URLs, mail links and inline data.
+pub fn is_external_target(target: &str) -> bool {
+    target.contains("://") || target.starts_with("mailto:") || target.starts_with("data:")
+}
+
+/// Shell-style match of a forward-slash `path` against `pattern`: `?` is one
+/// character, `*` any run within a folder and `**` any run across folders.
+/// Case-insensitive, as quick-open queries are typed loosely.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+    let path = path.to_lowercase().chars().collect::<Vec<_>>();
+    let width = path.len() + 1;
+
+    // `matched[i * width + j]` records whether `pattern[i..]` matches
+    // `path[j..]`; `starred` is the same once the star at `i` has begun
+    // consuming, where `**/` can no longer stand for nothing. Filling both
+    // from the end keeps the cost at pattern × path, however many stars the
+    // query has.
+    let mut matched = vec![false; (pattern.len() + 1) * width];
+    let mut starred = vec![false; (pattern.len() + 1) * width];
+    matched[pattern.len() * width + path.len()] = true;
+    for i in (0..pattern.len()).rev() {
+        for j in (0..=path.len()).rev() {
+            let next = path.get(j);
+            matched[i * width + j] = match pattern[i] {
+                '*' => {
+                    let crosses = pattern.get(i + 1) == Some(&'*');
+                    let rest = if crosses { i + 2 } else { i + 1 };
+                    starred[i * width + j] = matched[rest * width + j]
+                        || (next.is_some_and(|ch| crosses || *ch != '/')
+                            && starred[i * width + j + 1]);
+                    // `**/` may also stand for no folder at all.
+                    starred[i * width + j]
+                        || (crosses
+                            && pattern.get(rest) == Some(&'/')
+                            && matched[(rest + 1) * width + j])
+                }
+                '?' => next.is_some_and(|ch| *ch != '/') && matched[(i + 1) * width + j + 1],
+                ch => next == Some(&ch) && matched[(i + 1) * width + j + 1],
+            };
+        }
+    }
+    matched[0]
+}
+
+/// Project-relative paths a link in `folder` may point at. A leading `/`
+/// anchors the target at the project root; otherwise it is tried relative to
+/// the linking file first and then to the root, since drafts use both styles.
+pub fn reference_candidates(folder: &Path, target: &str) -> Vec<PathBuf> {
+    if is_external_target(target) {
+        return Vec::new();
+    }
+    match target.strip_prefix('/') {
+        Some(absolute) => normalize_relative(Path::new(absolute))
+            .into_iter()
+            .collect(),
+        None => normalize_relative(&folder.join(target))
+            .into_iter()
+            .chain(normalize_relative(Path::new(target)))
+            .collect(),
+    }
+}
+
+/// Files under [`ASSET_DIRS`] that no markdown file links to. Targets are
+/// matched both relative to the linking file and to the project root, so
+/// either style counts as a reference. `also_referenced` covers paths kept
+/// outside markdown, such as the project cover.
+pub fn find_orphaned_assets(root: &Path, also_referenced: &[&str]) -> Vec<OrphanedAsset> {
+    let mut referenced: HashSet<PathBuf> = also_referenced
+        .iter()
+        .filter_map(|path| normalize_relative(Path::new(path)))
+        .collect();
+
+    for entry in markdown_entries(root) {
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let folder = entry
+            .path()
+            .parent()
+            .and_then(|parent| parent.strip_prefix(root).ok())
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        for target in link_targets(&content) {
+            referenced.extend(reference_candidates(&folder, &target));
+        }
+    }
+
+    let mut orphans: Vec<OrphanedAsset> = ASSET_DIRS
+        .iter()
+        .flat_map(|dir| {
+            WalkDir::new(root.join(dir))
+                .into_iter()
+                .filter_entry(|entry| {
+                    entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+                })
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+        })
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(root).ok()?.to_path_buf();
+            if referenced.contains(&relative) {
+                return None;
+            }
+            Some(OrphanedAsset {
+                path: relative.to_string_lossy().replace('\\', "/"),
+                size: entry.metadata().ok()?.len(),
+            })
+        })
+        .collect();
+    orphans.sort_by(|a, b| a.path.cmp(&b.path));
+    orphans
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileKind {
+    Markdown,
+    Text,
+    Image,
+    Audio,
+    Other,
+}
+
+impl FileKind {
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "md" | "markdown" => FileKind::Markdown,
+            "txt" | "fountain" | "json" | "csv" | "srt" | "vtt" => FileKind::Text,
+            "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "bmp" => FileKind::Image,
+            "wav" | "mp3" | "m4a" | "flac" | "ogg" | "aac" => FileKind::Audio,
+            _ => FileKind::Other,
+        }
+    }
+}
+
+pub const SNAPSHOTS_DIR: &str = ".snapshots";
+
+/// Top-level folders holding transient state, plus the relocation marker,
+/// left out of project checksums.
+pub const CHECKSUM_EXCLUDED_DIRS: &[&str] = &[SNAPSHOTS_DIR, TRASH_DIR, PROJECT_MARKER];
+
+#[derive(Debug, Serialize)]
+pub struct FileChecksum {
+    pub path: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+/// SHA-256 over every project file except [`CHECKSUM_EXCLUDED_DIRS`]. Files
+/// are visited in relative-path order and each contributes its path and
+/// content hash, so the result only changes when a file is added, removed,
+/// renamed or edited. Returns the combined hash and the per-file hashes.
+pub fn project_checksum(root: &Path) -> AppResult<(String, Vec<FileChecksum>)> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(root).into_iter().filter_entry(|entry| {
+        entry.depth() != 1
+            || !CHECKSUM_EXCLUDED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+    }) {
+        let entry = entry.map_err(|err| AppError::Anyhow(err.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .map_err(|_| AppError::Message("File is outside the project".into()))?
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let mut hasher = Sha256::new();
+        let size = std::io::copy(&mut fs::File::open(entry.path())?, &mut hasher)?;
+        files.push(FileChecksum {
+            path: relative,
+            sha256: format!("{:x}", hasher.finalize()),
+            size,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut combined = Sha256::new();
+    for file in &files {
+        combined.update(file.path.as_bytes());
+        combined.update([0]);
+        combined.update(file.sha256.as_bytes());
+        combined.update([b'\n']);
+    }
+    Ok((format!("{:x}", combined.finalize()), files))
+}
+
+#[derive(Debug, Serialize)]
+pub struct SceneVersion {
+    pub path: String,
+    /// Suffix after the scene name, such as `v2` or `alt`; `None` for the
+    /// canonical file.
+    pub label: Option<String>,
+    pub version: Option<u32>,
+    pub canonical: bool,
+    pub size: u64,
+    pub modified_at: u64,
+}
+
+/// Reads the version suffix of `stem` relative to the scene name `base`:
+/// `scene1-v2` and `scene1_2` are numbered versions, `scene1-alt` a labelled
+/// one. Anything else is a different file.
+fn version_suffix(stem: &str, base: &str) -> Option<(String, Option<u32>)> {
+    let suffix = stem
+        .strip_prefix(base)?
+        .strip_prefix(['-', '_'])
+        .filter(|suffix| !suffix.is_empty())?;
+    if !suffix
+        .chars()
+        .all(|ch| ch.is_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return None;
+    }
+    let digits = suffix.strip_prefix(['v', 'V']).unwrap_or(suffix);
+    let number = digits
+        .chars()
+        .all(|ch| ch.is_ascii_digit())
+        .then(|| digits.parse().ok())
+        .flatten();
+    Some((suffix.to_string(), number))
+}
+
+/// Sibling drafts that follow the version naming convention for `scene`,
+/// including the scene itself: the canonical file first, then numbered
+/// versions in order, then labelled versions alphabetically.
+pub fn scene_versions(root: &Path, scene: &Path) -> AppResult<Vec<SceneVersion>> {
+    let base = scene
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::Message("Scene path has no file name".into()))?;
+    let extension = scene.extension().map(|ext| ext.to_ascii_lowercase());
+    let Some(folder) = scene.parent().filter(|folder| folder.is_dir()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(folder)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !entry.file_type()?.is_file()
+            || path.extension().map(|ext| ext.to_ascii_lowercase()) != extension
+        {
+            continue;
+        }
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let (label, version) = if stem == base {
+            (None, None)
+        } else {
+            match version_suffix(&stem, &base) {
+                Some((label, version)) => (Some(label), version),
+                None => continue,
+            }
+        };
+        let metadata = entry.metadata()?;
+        let modified_at = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        versions.push(SceneVersion {
+            path: path
+                .strip_prefix(root)
+                .map_err(|_| AppError::Message("Scene is outside the project".into()))?
+                .to_string_lossy()
+                .replace('\\', "/"),
+            canonical: label.is_none(),
+            label,
+            version,
+            size: metadata.len(),
+            modified_at,
+        });
+    }
+
+    versions.sort_by(|a, b| {
+        let rank = |version: &SceneVersion| match (version.canonical, version.version) {
+            (true, _) => 0,
+            (false, Some(_)) => 1,
+            (false, None) => 2,
+        };
+        rank(a)
+            .cmp(&rank(b))
+            .then(a.version.cmp(&b.version))
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    Ok(versions)
+}
+
+pub const TRASH_DIR: &str = ".trash";
+
+#[derive(Debug, Serialize)]
+pub struct TrashedFile {
+    pub id: String,
+    pub original_path: String,
+    /// Unix seconds.
+    pub deleted_at: u64,
+    pub size: u64,
+}
+
+/// Moves `file` into the project's trash. Each deletion gets its own
+/// `.trash/<unix-seconds>-<suffix>/` folder that mirrors the file's relative
+/// path, so the original location and time survive without a manifest.
+/// Returns the entry id.
+pub fn move_to_trash(root: &Path, file: &Path) -> AppResult<String> {
+    let relative = file
+        .strip_prefix(root)
+        .map_err(|_| AppError::Message("File is outside the project".into()))?;
+    let stamp = SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let id = format!("{stamp}-{}", &Uuid::new_v4().simple().to_string()[..8]);
+    let target = root.join(TRASH_DIR).join(&id).join(relative);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(file, &target)?;
+    Ok(id)
+}
+
+/// Resolves a trash entry id to its folder, the single file inside it and
+/// the project-relative path the file was deleted from.
+fn trash_entry(root: &Path, id: &str) -> AppResult<(PathBuf, PathBuf, String)> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+        return Err(AppError::Message("Invalid trash entry".into()));
+    }
+    let entry_dir = root.join(TRASH_DIR).join(id);
+    let file = WalkDir::new(&entry_dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .find(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .ok_or_else(|| AppError::Message("Trash entry not found".into()))?;
+    let original_path = file
+        .strip_prefix(&entry_dir)
+        .unwrap_or(&file)
+        .to_string_lossy()
+        .replace('\\', "/");
+    Ok((entry_dir, file, original_path))
+}
+
+/// Trashed files, most recently deleted first.
+pub fn list_trash(root: &Path) -> AppResult<Vec<TrashedFile>> {
+    let trash = root.join(TRASH_DIR);
+    if !trash.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(&trash)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let id = entry.file_name().to_string_lossy().to_string();
+        let Ok((_, file, original_path)) = trash_entry(root, &id) else {
+            continue;
+        };
+        files.push(TrashedFile {
+            deleted_at: id
+                .split('-')
+                .next()
+                .and_then(|stamp| stamp.parse().ok())
+                .unwrap_or_default(),
+            size: fs::metadata(&file)?.len(),
+            id,
+            original_path,
+        });
+    }
+    files.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then(a.id.cmp(&b.id)));
+    Ok(files)
+}
+
+/// Moves a trashed file to `target` and drops its trash entry. Refuses to
+/// overwrite an existing file.
+pub fn restore_from_trash(root: &Path, id: &str, target: &Path) -> AppResult<()> {
+    let (entry_dir, file, _) = trash_entry(root, id)?;
+    if target.exists() {
+        return Err(AppError::Message(format!(
+            "{} already exists; choose another path to restore to",
+            target
+                .strip_prefix(root)
+                .unwrap_or(target)
+                .to_string_lossy()
+                .replace('\\', "/")
+        )));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&file, target)?;
+    fs::remove_dir_all(&entry_dir)?;
+    Ok(())
+}
+
+/// Where a trash entry will restore to by default, relative to the project.
+pub fn trashed_original_path(root: &Path, id: &str) -> AppResult<String> {
+    trash_entry(root, id).map(|(_, _, original_path)| original_path)
+}
+
+/// Permanently deletes everything in the trash, returning how many entries
+/// and bytes were removed.
+pub fn empty_trash(root: &Path) -> AppResult<(usize, u64)> {
+    let trash = root.join(TRASH_DIR);
+    if !trash.is_dir() {
+        return Ok((0, 0));
+    }
+    let removed = fs::read_dir(&trash)?.filter_map(Result::ok).count();
+    let bytes = directory_size(&trash)?;
+    fs::remove_dir_all(&trash)?;
+    Ok((removed, bytes))
+}
+
+/// Copies `file` into the project's snapshot folder, mirroring its relative path
+/// and suffixing the name with a timestamp, then drops the oldest snapshots of
+/// that file beyond `retention`. Returns `None` when there is nothing to
+/// preserve.
+pub fn snapshot_file(root: &Path, file: &Path, retention: usize) -> AppResult<Option<PathBuf>> {
+    let contents = match fs::read(file) {
+        Ok(contents) if !contents.is_empty() => contents,
+        Ok(_) => return Ok(None),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    let relative = file
+        .strip_prefix(root)
+        .map_err(|_| AppError::Message("File is outside the project".into()))?;
+    let target_dir = match relative.parent() {
+        Some(parent) => root.join(SNAPSHOTS_DIR).join(parent),
+        None => root.join(SNAPSHOTS_DIR),
+    };
+    fs::create_dir_all(&target_dir)?;
+
+    let stem = file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "snapshot".into());
+    let extension = file
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    let mut target = target_dir.join(format!("{stem}-{stamp}{extension}"));
+    let mut counter = 1usize;
+    while target.exists() {
+        target = target_dir.join(format!("{stem}-{stamp}-{counter}{extension}"));
+        counter += 1;
+    }
+
+    write_atomic(&target, &contents)?;
+    prune_snapshots(&target_dir, &stem, &extension, retention.max(1))?;
+    Ok(Some(target))
+}
+
+/// Snapshots in `dir` named `{stem}-{stamp}[-{n}]{extension}`, oldest first,
+/// keyed by their stamp and counter.
+fn snapshot_entries(
+    dir: &Path,
+    stem: &str,
+    extension: &str,
+) -> AppResult<Vec<((u64, u64), PathBuf)>> {
+    let mut snapshots: Vec<((u64, u64), PathBuf)> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let suffix = name
+                .strip_prefix(stem)?
+                .strip_prefix('-')?
+                .strip_suffix(extension)?;
+            Some((snapshot_key(suffix)?, entry.path()))
+        })
+        .collect();
+    snapshots.sort();
+    Ok(snapshots)
+}
+
+/// Parses the `{stamp}[-{n}]` part of a snapshot name. The stamp must be a
+/// full Unix timestamp so that `scene1-2-{stamp}.md`, a snapshot of
+/// `scene1-2.md`, is never read as a snapshot of `scene1.md`.
+fn snapshot_key(suffix: &str) -> Option<(u64, u64)> {
+    let (stamp, counter) = suffix.split_once('-').unwrap_or((suffix, "0"));
+    let all_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if stamp.len() < 10 || !all_digits(stamp) || !all_digits(counter) {
+        return None;
+    }
+    Some((stamp.parse().ok()?, counter.parse().ok()?))
+}
+
+/// Snapshots taken of `file`, oldest first, with the Unix time each was
+/// taken. A snapshot holds the content the file had just before that time.
+pub fn file_snapshots(root: &Path, file: &Path) -> AppResult<Vec<(u64, PathBuf)>> {
+    let relative = file
+        .strip_prefix(root)
+        .map_err(|_| AppError::Message("File is outside the project".into()))?;
+    let dir = match relative.parent() {
+        Some(parent) => root.join(SNAPSHOTS_DIR).join(parent),
+        None => root.join(SNAPSHOTS_DIR),
+    };
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let stem = file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "snapshot".into());
+    let extension = file
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    Ok(snapshot_entries(&dir, &stem, &extension)?
+        .into_iter()
+        .map(|((stamp, _), path)| (stamp, path))
+        .collect())
+}
+
+/// Removes all but the newest `keep` snapshots named `{stem}-{stamp}[-{n}]{extension}`.
+fn prune_snapshots(dir: &Path, stem: &str, extension: &str, keep: usize) -> AppResult<()> {
+    let snapshots = snapshot_entries(dir, stem, extension)?;
+    if snapshots.len() <= keep {
+        return Ok(());
+    }
+    for (_, path) in &snapshots[..snapshots.len() - keep] {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+pub fn write_atomic(path: &Path, contents: &[u8]) -> AppResult<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| AppError::Message("Target file has no parent directory".into()))?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::Message("Target path has no file name".into()))?;
+    let temp_path = parent.join(format!(".{file_name}.{}.tmp", Uuid::new_v4()));
+
+    let result = (|| -> AppResult<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+pub struct TextChunk {
+    pub content: String,
+    /// Byte offsets of the chunk, both on character boundaries.
+    pub start: u64,
+    pub end: u64,
+    pub total_bytes: u64,
+}
+
+fn is_continuation_byte(byte: u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+/// Reads about `length` bytes of UTF-8 text from `offset`. The start moves
+/// forward and the end back so no multi-byte character (Tamil letters take
+/// three bytes) is split; a chunk always holds at least one character.
+pub fn read_text_chunk(path: &Path, offset: u64, length: u64) -> AppResult<TextChunk> {
+    let mut file = fs::File::open(path)?;
+    let total_bytes = file.metadata()?.len();
+    let offset = offset.min(total_bytes);
+    // Up to three bytes either side to find the surrounding boundaries.
+    let read_start = offset.saturating_sub(3);
+    let read_end = offset
+        .saturating_add(length)
+        .saturating_add(4)
+        .min(total_bytes);
+    let mut bytes = Vec::with_capacity((read_end - read_start) as usize);
+    file.seek(SeekFrom::Start(read_start))?;
+    file.take(read_end - read_start).read_to_end(&mut bytes)?;
+
+    let boundary = |index: usize| {
+        !bytes
+            .get(index)
+            .is_some_and(|byte| is_continuation_byte(*byte))
+    };
+    let mut start = (offset - read_start) as usize;
+    while !boundary(start) {
+        start += 1;
+    }
+    let mut end = (start as u64 + length).min(read_end - read_start) as usize;
+    while end > start && !boundary(end) {
+        end -= 1;
+    }
+    if end == start && start < bytes.len() {
+        end += 1;
+        while !boundary(end) {
+            end += 1;
+        }
+    }
+
+    let content = std::str::from_utf8(&bytes[start..end])
+        .map_err(|_| AppError::Message("File is not valid UTF-8 text".into()))?
+        .to_string();
+    Ok(TextChunk {
+        content,
+        start: read_start + start as u64,
+        end: read_start + end as u64,
+        total_bytes,
+    })
+}
+
+/// Whether `target` differs from `source` only in letter case, within the
+/// same folder.
+fn is_case_only_change(source: &Path, target: &Path) -> bool {
+    let (Some(from), Some(to)) = (source.file_name(), target.file_name()) else {
+        return false;
+    };
+    source.parent() == target.parent()
+        && from != to
+        && from.to_string_lossy().to_lowercase() == to.to_string_lossy().to_lowercase()
+}
+
+/// Whether renaming `source` to `target` would clobber another entry. On
+/// case-insensitive filesystems (the macOS and Windows defaults) a case-only
+/// rename sees `source` itself at `target`, which is not a conflict; nor is
+/// renaming an entry to its own name.
+pub fn rename_conflicts(source: &Path, target: &Path) -> bool {
+    if source == target || !target.exists() {
+        return false;
+    }
+    if !is_case_only_change(source, target) {
+        return true;
+    }
+    let (Some(parent), Some(name)) = (target.parent(), target.file_name()) else {
+        return true;
+    };
+    fs::read_dir(parent)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .any(|entry| entry.file_name() == name)
+        })
+        .unwrap_or(true)
+}
+
+/// Renames a file or folder. Case-only renames go through a temporary name,
+/// since case-insensitive filesystems may reject or ignore them otherwise.
+pub fn rename_entry(source: &Path, target: &Path) -> AppResult<()> {
+    if !is_case_only_change(source, target) {
+        fs::rename(source, target)?;
+        return Ok(());
+    }
+
+    let name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path = source.with_file_name(format!(".{name}.{}.rename", Uuid::new_v4()));
+    fs::rename(source, &temp_path)?;
+    if let Err(err) = fs::rename(&temp_path, target) {
+        let _ = fs::rename(&temp_path, source);
+        return Err(err.into());
+    }
     Ok(())
 }
 
@@ -53,3 +986,196 @@ pub fn assert_slug_unique(existing: &[String], slug: &str) -> AppResult<()> {
         Ok(())
     }
 }
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DiskUsage {
+    pub total_bytes: u64,
+    pub root_files_bytes: u64,
+    pub directories: BTreeMap<String, u64>,
+}
+
+fn directory_size(path: &Path) -> AppResult<u64> {
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            continue;
+        }
+        if file_type.is_dir() {
+            total += directory_size(&entry.path())?;
+        } else {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+pub fn measure_disk_usage(root: &Path) -> AppResult<DiskUsage> {
+    let mut usage = DiskUsage::default();
+    if !root.is_dir() {
+        return Ok(usage);
+    }
+
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            continue;
+        }
+        if file_type.is_dir() {
+            let bytes = directory_size(&entry.path())?;
+            usage
+                .directories
+                .insert(entry.file_name().to_string_lossy().to_string(), bytes);
+            usage.total_bytes += bytes;
+        } else {
+            let bytes = entry.metadata()?.len();
+            usage.root_files_bytes += bytes;
+            usage.total_bytes += bytes;
+        }
+    }
+
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("scriptwriter-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn prune_snapshots_ignores_sibling_files_sharing_a_prefix() {
+        let dir = temp_dir();
+        for name in [
+            "scene1-1700000001.md",
+            "scene1-1700000002.md",
+            "scene1-1700000002-1.md",
+            "scene1-2-1600000000.md",
+            "scene1-2-1600000001.md",
+        ] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+
+        let own: Vec<_> = snapshot_entries(&dir, "scene1", ".md")
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(own, vec![(1700000001, 0), (1700000002, 0), (1700000002, 1)]);
+
+        prune_snapshots(&dir, "scene1", ".md", 1).unwrap();
+        assert!(!dir.join("scene1-1700000001.md").exists());
+        assert!(!dir.join("scene1-1700000002.md").exists());
+        assert!(dir.join("scene1-1700000002-1.md").exists());
+        assert!(dir.join("scene1-2-1600000000.md").exists());
+        assert!(dir.join("scene1-2-1600000001.md").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn file_snapshots_lists_only_the_requested_file() {
+        let root = temp_dir();
+        fs::write(root.join("scene1.md"), "one").unwrap();
+        fs::write(root.join("scene1-2.md"), "two").unwrap();
+        snapshot_file(&root, &root.join("scene1.md"), 5).unwrap();
+        snapshot_file(&root, &root.join("scene1-2.md"), 5).unwrap();
+
+        let own = file_snapshots(&root, &root.join("scene1.md")).unwrap();
+        assert_eq!(own.len(), 1);
+        assert_eq!(fs::read_to_string(&own[0].1).unwrap(), "one");
+        let sibling = file_snapshots(&root, &root.join("scene1-2.md")).unwrap();
+        assert_eq!(sibling.len(), 1);
+        assert_eq!(fs::read_to_string(&sibling[0].1).unwrap(), "two");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn glob_match_handles_folders_and_wildcards() {
+        assert!(glob_match("*.md", "scene1.md"));
+        assert!(!glob_match("*.md", "act1/scene1.md"));
+        assert!(glob_match("**/*.md", "scene1.md"));
+        assert!(glob_match("**/*.md", "act1/part2/scene1.md"));
+        assert!(glob_match("act?/SCENE*", "act1/scene12.md"));
+        assert!(!glob_match("act?/scene*", "act1/x/scene12.md"));
+        assert!(glob_match("act1/**", "act1/x/scene12.md"));
+        assert!(!glob_match("scene?.md", "scene12.md"));
+    }
+
+    #[test]
+    fn glob_match_is_fast_on_pathological_patterns() {
+        let started = std::time::Instant::now();
+        let name = "a".repeat(60);
+        assert!(!glob_match("*a*a*a*a*a*a*a*ab", &name));
+        let query = "*a".repeat(100);
+        assert!(!glob_match(&format!("{query}b"), &name.repeat(4)));
+        assert!(!glob_match(&"**a".repeat(66), &name));
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn rename_conflicts_ignores_the_entry_itself() {
+        let dir = temp_dir();
+        let file = dir.join("scene.md");
+        fs::write(&file, "draft").unwrap();
+        fs::write(dir.join("other.md"), "other").unwrap();
+
+        assert!(!rename_conflicts(&file, &file));
+        assert!(rename_conflicts(&file, &dir.join("other.md")));
+        assert!(!rename_conflicts(&file, &dir.join("new.md")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(any(target_os = "macos", windows))]
+    #[test]
+    fn rename_entry_changes_only_the_case() {
+        let dir = temp_dir();
+        let source = dir.join("Scene.md");
+        let target = dir.join("scene.md");
+        fs::write(&source, "draft").unwrap();
+
+        assert!(!rename_conflicts(&source, &target));
+        rename_entry(&source, &target).unwrap();
+
+        let names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["scene.md".to_string()]);
+        assert_eq!(fs::read_to_string(&target).unwrap(), "draft");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn remove_project_dir_requires_projects_root_or_marker() {
+        let storage_root = temp_dir();
+        let inside = project_path(&storage_root, "film");
+        fs::create_dir_all(&inside).unwrap();
+        remove_project_dir(&storage_root, &inside, "project-1").unwrap();
+        assert!(!inside.exists());
+
+        let elsewhere = temp_dir().join("film");
+        fs::create_dir_all(&elsewhere).unwrap();
+        fs::create_dir_all(storage_root.join("projects")).unwrap();
+        assert!(remove_project_dir(&storage_root, &elsewhere, "project-1").is_err());
+        write_project_marker(&elsewhere, "project-2").unwrap();
+        assert!(remove_project_dir(&storage_root, &elsewhere, "project-1").is_err());
+        assert!(elsewhere.exists());
+
+        write_project_marker(&elsewhere, "project-1").unwrap();
+        remove_project_dir(&storage_root, &elsewhere, "project-1").unwrap();
+        assert!(!elsewhere.exists());
+
+        fs::remove_dir_all(elsewhere.parent().unwrap()).unwrap();
+        fs::remove_dir_all(&storage_root).unwrap();
+    }
+}
@@ -0,0 +1,303 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, Mutex};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct JobRecord {
+    pub id: String,
+    pub kind: String,
+    pub status: JobStatus,
+    pub progress: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+type JobFuture = Pin<Box<dyn Future<Output = AppResult<Value>> + Send>>;
+type JobTask = Box<dyn FnOnce(JobHandle) -> JobFuture + Send>;
+
+struct QueuedJob {
+    id: String,
+    task: JobTask,
+}
+
+type JobTable = Arc<Mutex<Vec<JobRecord>>>;
+
+/// Finished jobs kept for `get_job_status`/`list_jobs` after their final
+/// event; older ones are dropped so the table does not grow for the life of
+/// the app.
+const MAX_FINISHED_JOBS: usize = 50;
+
+/// Handed to a running job so it can report progress and reach the app.
+#[derive(Clone)]
+pub struct JobHandle {
+    id: String,
+    app: AppHandle,
+    jobs: JobTable,
+}
+
+impl JobHandle {
+    pub fn app(&self) -> &AppHandle {
+        &self.app
+    }
+
+    pub async fn progress(&self, progress: f32, message: impl Into<String>) {
+        let message = message.into();
+        let record = update(&self.jobs, &self.id, |record| {
+            record.progress = progress.clamp(0.0, 1.0);
+            record.message = Some(message);
+        })
+        .await;
+        emit(&self.app, "job-progress", record);
+    }
+}
+
+pub struct JobQueue {
+    jobs: JobTable,
+    /// Set on exit; the worker skips anything still in the channel.
+    closed: Arc<AtomicBool>,
+    sender: mpsc::UnboundedSender<QueuedJob>,
+    receiver: std::sync::Mutex<Option<mpsc::UnboundedReceiver<QueuedJob>>>,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            jobs: Arc::new(Mutex::new(Vec::new())),
+            closed: Arc::new(AtomicBool::new(false)),
+            sender,
+            receiver: std::sync::Mutex::new(Some(receiver)),
+        }
+    }
+
+    /// Spawns the worker that runs queued jobs one at a time.
+    pub fn start(&self, app: AppHandle) {
+        let Some(mut receiver) = self
+            .receiver
+            .lock()
+            .ok()
+            .and_then(|mut receiver| receiver.take())
+        else {
+            return;
+        };
+        let jobs = self.jobs.clone();
+        let closed = self.closed.clone();
+
+        tauri::async_runtime::spawn(async move {
+            while let Some(job) = receiver.recv().await {
+                if closed.load(Ordering::SeqCst) {
+                    continue;
+                }
+                run_job(&app, &jobs, job).await;
+            }
+        });
+    }
+
+    pub async fn enqueue<F, Fut>(&self, kind: &str, task: F) -> AppResult<String>
+    where
+        F: FnOnce(JobHandle) -> Fut + Send + 'static,
+        Fut: Future<Output = AppResult<Value>> + Send + 'static,
+    {
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(AppError::Message("The app is shutting down".into()));
+        }
+        let id = Uuid::new_v4().to_string();
+        self.jobs.lock().await.push(JobRecord {
+            id: id.clone(),
+            kind: kind.to_string(),
+            status: JobStatus::Queued,
+            progress: 0.0,
+            message: None,
+            result: None,
+            error: None,
+        });
+
+        let task: JobTask = Box::new(move |handle| Box::pin(task(handle)));
+        self.sender
+            .send(QueuedJob {
+                id: id.clone(),
+                task,
+            })
+            .map_err(|_| AppError::Message("Job worker is not running".into()))?;
+
+        Ok(id)
+    }
+
+    pub async fn get(&self, job_id: &str) -> Option<JobRecord> {
+        self.jobs
+            .lock()
+            .await
+            .iter()
+            .find(|record| record.id == job_id)
+            .cloned()
+    }
+
+    pub async fn list(&self) -> Vec<JobRecord> {
+        self.jobs.lock().await.clone()
+    }
+
+    /// Stops the queue on exit: queued jobs are marked failed and never run,
+    /// and new ones are refused. A running job is left to fail once its ML
+    /// process is killed. Returns how many queued jobs were dropped.
+    pub async fn cancel_pending(&self) -> usize {
+        self.closed.store(true, Ordering::SeqCst);
+        let mut jobs = self.jobs.lock().await;
+        let mut cancelled = 0;
+        for record in jobs
+            .iter_mut()
+            .filter(|record| record.status == JobStatus::Queued)
+        {
+            record.status = JobStatus::Failed;
+            record.error = Some("Cancelled on exit".into());
+            cancelled += 1;
+        }
+        cancelled
+    }
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn run_job(app: &AppHandle, jobs: &JobTable, job: QueuedJob) {
+    let record = update(jobs, &job.id, |record| record.status = JobStatus::Running).await;
+    emit(app, "job-progress", record);
+
+    let handle = JobHandle {
+        id: job.id.clone(),
+        app: app.clone(),
+        jobs: jobs.clone(),
+    };
+    match (job.task)(handle).await {
+        Ok(result) => {
+            let record = update(jobs, &job.id, |record| {
+                record.status = JobStatus::Completed;
+                record.progress = 1.0;
+                record.result = Some(result);
+            })
+            .await;
+            emit(app, "job-complete", record);
+        }
+        Err(err) => {
+            let record = update(jobs, &job.id, |record| {
+                record.status = JobStatus::Failed;
+                record.error = Some(err.to_string());
+            })
+            .await;
+            emit(app, "job-failed", record);
+        }
+    }
+    prune_finished(&mut *jobs.lock().await, MAX_FINISHED_JOBS);
+}
+
+/// Drops the oldest completed or failed jobs beyond `keep`, leaving queued
+/// and running ones alone.
+fn prune_finished(jobs: &mut Vec<JobRecord>, keep: usize) {
+    let finished =
+        |record: &JobRecord| matches!(record.status, JobStatus::Completed | JobStatus::Failed);
+    let mut excess = jobs
+        .iter()
+        .filter(|record| finished(record))
+        .count()
+        .saturating_sub(keep);
+    jobs.retain(|record| {
+        if excess > 0 && finished(record) {
+            excess -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+async fn update(
+    jobs: &JobTable,
+    job_id: &str,
+    apply: impl FnOnce(&mut JobRecord),
+) -> Option<JobRecord> {
+    let mut jobs = jobs.lock().await;
+    let record = jobs.iter_mut().find(|record| record.id == job_id)?;
+    apply(record);
+    Some(record.clone())
+}
+
+fn emit(app: &AppHandle, event: &str, record: Option<JobRecord>) {
+    if let Some(record) = record {
+        if let Err(err) = app.emit(event, &record) {
+            log::warn!("Failed to emit {event} for job {}: {err}", record.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, status: JobStatus) -> JobRecord {
+        JobRecord {
+            id: id.to_string(),
+            kind: "test".to_string(),
+            status,
+            progress: 0.0,
+            message: None,
+            result: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn prune_finished_drops_the_oldest_finished_jobs() {
+        let mut jobs = vec![
+            record("a", JobStatus::Completed),
+            record("b", JobStatus::Running),
+            record("c", JobStatus::Failed),
+            record("d", JobStatus::Queued),
+            record("e", JobStatus::Completed),
+        ];
+        prune_finished(&mut jobs, 1);
+        let ids = jobs.iter().map(|job| job.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["b", "d", "e"]);
+    }
+
+    #[tokio::test]
+    async fn cancel_pending_fails_queued_jobs_and_refuses_new_ones() {
+        let queue = JobQueue::new();
+        let id = queue
+            .enqueue("test", |_| async { Ok(Value::Null) })
+            .await
+            .expect("enqueue");
+
+        assert_eq!(queue.cancel_pending().await, 1);
+        let record = queue.get(&id).await.expect("record");
+        assert_eq!(record.status, JobStatus::Failed);
+        assert_eq!(record.error.as_deref(), Some("Cancelled on exit"));
+        assert!(queue
+            .enqueue("test", |_| async { Ok(Value::Null) })
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,289 @@
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult};
+
+const MIXED_THRESHOLD: f32 = 0.8;
+
+pub const SUPPORTED_LANGUAGES: &[(&str, &str)] = &[
+    ("ta", "Tamil"),
+    ("en", "English"),
+    ("hi", "Hindi"),
+    ("te", "Telugu"),
+    ("ml", "Malayalam"),
+    ("kn", "Kannada"),
+];
+
+/// Other ways people write the supported languages: English and native
+/// names and ISO 639-2 codes.
+const LANGUAGE_ALIASES: &[(&str, &str)] = &[
+    ("tamil", "ta"),
+    ("tam", "ta"),
+    ("தமிழ்", "ta"),
+    ("english", "en"),
+    ("eng", "en"),
+    ("hindi", "hi"),
+    ("hin", "hi"),
+    ("हिन्दी", "hi"),
+    ("हिंदी", "hi"),
+    ("telugu", "te"),
+    ("tel", "te"),
+    ("తెలుగు", "te"),
+    ("malayalam", "ml"),
+    ("mal", "ml"),
+    ("മലയാളം", "ml"),
+    ("kannada", "kn"),
+    ("kan", "kn"),
+    ("ಕನ್ನಡ", "kn"),
+];
+
+/// The ISO 639-1 code for a supported language given as a code, a locale
+/// such as `ta-IN`, or a name such as `Tamil`.
+pub fn normalize_language_code(input: &str) -> Option<&'static str> {
+    let lowered = input.trim().to_lowercase();
+    // Drop a region subtag, as in `ta-IN` or `en_GB`.
+    let candidate = lowered.split(['-', '_']).next().unwrap_or_default();
+    SUPPORTED_LANGUAGES
+        .iter()
+        .map(|(code, _)| (*code, *code))
+        .chain(LANGUAGE_ALIASES.iter().copied())
+        .find(|(alias, _)| *alias == candidate)
+        .map(|(_, code)| code)
+}
+
+pub fn validate_language_codes(codes: &[String]) -> AppResult<Vec<String>> {
+    let mut validated: Vec<String> = Vec::with_capacity(codes.len());
+    for code in codes {
+        let Some(normalized) = normalize_language_code(code) else {
+            let names = SUPPORTED_LANGUAGES
+                .iter()
+                .map(|(code, name)| format!("{name} ({code})"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(AppError::Message(format!(
+                "Unsupported language '{}'; choose from {names}",
+                code.trim()
+            )));
+        };
+        if !validated.iter().any(|known| known == normalized) {
+            validated.push(normalized.to_string());
+        }
+    }
+
+    if validated.is_empty() {
+        return Err(AppError::Message(
+            "At least one default language is required".into(),
+        ));
+    }
+
+    Ok(validated)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageShare {
+    pub language: String,
+    pub ratio: f32,
+    pub characters: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageDetection {
+    pub primary: Option<String>,
+    pub ratio: f32,
+    pub mixed: bool,
+    pub languages: Vec<LanguageShare>,
+    pub suggested_directory: Option<String>,
+}
+
+pub fn is_tamil(ch: char) -> bool {
+    ('\u{0B80}'..='\u{0BFF}').contains(&ch)
+}
+
+fn is_latin(ch: char) -> bool {
+    ch.is_ascii_alphabetic() || (ch.is_alphabetic() && ('\u{00C0}'..='\u{024F}').contains(&ch))
+}
+
+pub fn detect_language(text: &str) -> LanguageDetection {
+    let mut tamil = 0usize;
+    let mut latin = 0usize;
+    for ch in text.chars() {
+        if is_tamil(ch) {
+            tamil += 1;
+        } else if is_latin(ch) {
+            latin += 1;
+        }
+    }
+
+    let total = tamil + latin;
+    if total == 0 {
+        return LanguageDetection {
+            primary: None,
+            ratio: 0.0,
+            mixed: false,
+            languages: Vec::new(),
+            suggested_directory: None,
+        };
+    }
+
+    let mut languages = [("ta", tamil), ("en", latin)]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(code, count)| LanguageShare {
+            language: code.to_string(),
+            ratio: count as f32 / total as f32,
+            characters: count,
+        })
+        .collect::<Vec<_>>();
+    languages.sort_by_key(|share| std::cmp::Reverse(share.characters));
+
+    let primary = &languages[0];
+    let mixed = primary.ratio < MIXED_THRESHOLD;
+    let suggested_directory = if mixed {
+        None
+    } else {
+        match primary.language.as_str() {
+            "ta" => Some("drafts/tamil".to_string()),
+            _ => Some("drafts/english".to_string()),
+        }
+    };
+
+    LanguageDetection {
+        primary: Some(primary.language.clone()),
+        ratio: primary.ratio,
+        mixed,
+        languages,
+        suggested_directory,
+    }
+}
+
+const VIRAMA: char = '\u{0BCD}';
+
+/// Same letter tables as `scriptwriter_ml.phonetics`, kept in Rust so names can
+/// be matched without a Python round trip.
+fn tamil_vowel(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        'அ' => "a",
+        'ஆ' => "aa",
+        'இ' => "i",
+        'ஈ' => "ii",
+        'உ' => "u",
+        'ஊ' => "uu",
+        'எ' => "e",
+        'ஏ' => "ee",
+        'ஐ' => "ai",
+        'ஒ' => "o",
+        'ஓ' => "oo",
+        'ஔ' => "au",
+        _ => return None,
+    })
+}
+
+fn tamil_consonant(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        'க' => "k",
+        'ங' => "ng",
+        'ச' => "ch",
+        'ஞ' => "nj",
+        'ட' => "t",
+        'ண' => "n",
+        'த' => "th",
+        'ந' => "n",
+        'ப' => "p",
+        'ம' => "m",
+        'ய' => "y",
+        'ர' => "r",
+        'ல' => "l",
+        'வ' => "v",
+        'ழ' => "zh",
+        'ள' => "l",
+        'ற' => "r",
+        'ன' => "n",
+        'ஜ' => "j",
+        'ஷ' => "sh",
+        'ஸ' => "s",
+        'ஹ' => "h",
+        _ => return None,
+    })
+}
+
+fn tamil_vowel_sign(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        'ா' => "aa",
+        'ி' => "i",
+        'ீ' => "ii",
+        'ு' => "u",
+        'ூ' => "uu",
+        'ெ' => "e",
+        'ே' => "ee",
+        'ை' => "ai",
+        'ொ' => "o",
+        'ோ' => "oo",
+        'ௌ' => "au",
+        _ => return None,
+    })
+}
+
+/// Romanizes Tamil letters and passes everything else through unchanged.
+pub fn romanize_tamil(text: &str) -> String {
+    let mut roman = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if let Some(consonant) = tamil_consonant(ch) {
+            roman.push_str(consonant);
+            match chars.peek().copied() {
+                Some(VIRAMA) => {
+                    chars.next();
+                }
+                Some(next) if tamil_vowel_sign(next).is_some() => {
+                    roman.push_str(tamil_vowel_sign(next).unwrap_or_default());
+                    chars.next();
+                }
+                _ => roman.push('a'),
+            }
+        } else if let Some(vowel) = tamil_vowel(ch) {
+            roman.push_str(vowel);
+        } else if ch == 'ஃ' {
+            roman.push('h');
+        } else {
+            roman.push(ch);
+        }
+    }
+    roman
+}
+
+/// A loose phonetic key for matching a name across Tamil and Latin spellings,
+/// e.g. `மீனா`, `Meena` and `Mina` all map to `mina`.
+pub fn name_key(name: &str) -> String {
+    let roman = romanize_tamil(name).to_lowercase();
+    let mut key: String = roman.chars().filter(char::is_ascii_alphanumeric).collect();
+    for (from, to) in [
+        ("zh", "l"),
+        ("th", "t"),
+        ("dh", "t"),
+        ("sh", "s"),
+        ("ch", "s"),
+        ("ph", "p"),
+        ("bh", "p"),
+        ("kh", "k"),
+        ("gh", "k"),
+        ("ee", "i"),
+        ("ii", "i"),
+        ("oo", "u"),
+        ("uu", "u"),
+        ("aa", "a"),
+    ] {
+        key = key.replace(from, to);
+    }
+    let mut folded = String::with_capacity(key.len());
+    for ch in key.chars().map(|ch| match ch {
+        'b' => 'p',
+        'd' => 't',
+        'g' => 'k',
+        'w' => 'v',
+        other => other,
+    }) {
+        if !folded.ends_with(ch) {
+            folded.push(ch);
+        }
+    }
+    folded
+}
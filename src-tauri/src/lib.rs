@@ -1,35 +1,144 @@
-use tauri::Manager;
+use tauri::{Manager, RunEvent};
 
+mod archive;
 mod auth;
 mod commands;
 mod error;
+mod export;
 mod filesystem;
+mod jobs;
+mod language;
+mod markdown;
 mod ml_bridge;
 mod models;
+mod reminders;
+mod screenplay;
 mod state;
+mod subtitles;
+mod transform;
+mod watcher;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
             commands::bootstrap,
+            commands::set_active_project,
             commands::create_project,
+            commands::quick_create_project,
+            commands::list_project_templates,
+            commands::get_template,
             commands::list_projects,
+            commands::search_projects,
+            commands::archive_project,
+            commands::unarchive_project,
+            commands::set_project_lock,
+            commands::update_project_metadata,
+            commands::supported_project_languages,
+            commands::delete_projects,
+            commands::prune_orphaned_projects,
+            commands::relocate_project,
+            commands::verify_project,
+            commands::repair_project,
+            commands::regenerate_readme,
+            commands::draft_vs_template,
+            commands::list_transformations,
+            commands::apply_transformation,
             commands::update_settings,
+            commands::set_theme,
+            commands::export_settings,
+            commands::import_settings,
+            commands::get_audit_log,
+            commands::vacuum_database,
+            commands::migration_status,
+            commands::app_info,
+            commands::reveal_api_key,
+            commands::test_llm_credentials,
             commands::transliterate_english_to_tamil,
+            commands::save_transliteration_correction,
             commands::refresh_model_inventory,
+            commands::download_model,
+            commands::cancel_model_download,
+            commands::benchmark_model,
             commands::list_project_files,
+            commands::query_project_files,
+            commands::compare_projects,
+            commands::inspect_project_archive,
+            commands::export_project_archive,
+            commands::recent_activity,
+            commands::project_changelog,
             commands::load_markdown_file,
+            commands::load_file_chunk,
             commands::save_markdown_file,
+            commands::create_scratch_file,
+            commands::clear_markdown_file,
+            commands::list_scene_versions,
+            commands::promote_scene_version,
+            commands::delete_project_file,
+            commands::list_trashed_files,
+            commands::restore_trashed_file,
+            commands::empty_trash,
             commands::copy_project_asset,
+            commands::import_subtitles,
+            commands::import_plaintext_script,
+            commands::import_fountain,
+            commands::set_project_cover,
+            commands::rename_project_directory,
+            commands::project_disk_usage,
+            commands::find_orphaned_assets,
+            commands::validate_references,
+            commands::project_checksum,
+            commands::render_markdown_preview,
+            commands::render_script_colored,
+            commands::outline_markdown,
+            commands::insert_toc,
+            commands::estimate_runtime,
+            commands::reading_time,
+            commands::project_character_index,
+            commands::export_docx,
+            commands::export_breakdown_csv,
+            commands::compile_manuscript,
+            commands::export_html_site,
+            commands::export_table_read_playlist,
+            commands::export_references,
+            commands::export_fountain,
+            commands::watch_project,
+            commands::stop_watching,
             commands::register_user,
             commands::login_user,
             commands::logout_user,
             commands::current_user,
+            commands::update_display_name,
+            commands::supported_languages,
             commands::transcribe_audio_file,
+            commands::transcribe_audio_batch,
             commands::record_from_microphone,
+            commands::list_audio_devices,
             commands::synthesize_speech,
+            commands::preview_speech,
+            commands::list_tts_voices,
             commands::generate_ai_scene,
+            commands::list_personas,
+            commands::save_persona,
+            commands::delete_persona,
+            commands::list_ai_history,
+            commands::clear_ai_history,
+            commands::insert_ai_history_entry,
+            commands::generate_scene_inline,
+            commands::summarize_scene,
+            commands::extract_outline_ai,
+            commands::estimate_llm_request,
+            commands::detect_language,
+            commands::set_file_language,
+            commands::tag_files_in_directory,
+            commands::phonetic_hint,
+            commands::speak_transliteration,
+            commands::input_suggestions,
+            commands::get_job_status,
+            commands::list_jobs,
+            commands::add_reminder,
+            commands::list_reminders,
+            commands::delete_reminder,
         ])
         .setup(|app| {
             #[cfg(debug_assertions)]
@@ -54,10 +163,19 @@ pub fn run() {
                 .map(|_| ())
                 .map_err(|err| -> Box<dyn std::error::Error> { Box::new(err) })?;
 
+            state.jobs.start(app.handle().clone());
+            reminders::start(app.handle().clone(), state.pool.clone());
             app.manage(state);
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::ExitRequested { .. } = event {
+                if let Some(state) = app.try_state::<state::AppState>() {
+                    tauri::async_runtime::block_on(state.shutdown());
+                }
+            }
+        });
 }
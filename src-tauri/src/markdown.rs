@@ -0,0 +1,968 @@
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OutlineNode {
+    pub level: u8,
+    pub text: String,
+    pub line: usize,
+    pub children: Vec<OutlineNode>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SceneBreakdown {
+    pub heading: String,
+    pub line: usize,
+    pub characters: Vec<String>,
+    pub word_count: usize,
+    pub location: Option<String>,
+}
+
+pub fn markdown_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_FOOTNOTES
+}
+
+pub fn render_html(content: &str) -> String {
+    let parser = Parser::new_ext(content, markdown_options());
+    sanitized_html(parser, content.len())
+}
+
+/// Same as [`render_html`], but every link and image target is passed through
+/// `rewrite` first; returning `None` keeps the target as written.
+pub fn render_html_with_links(
+    content: &str,
+    mut rewrite: impl FnMut(&str) -> Option<String>,
+) -> String {
+    let parser = Parser::new_ext(content, markdown_options()).map(|event| match event {
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: rewrite(&dest_url).map(CowStr::from).unwrap_or(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: rewrite(&dest_url).map(CowStr::from).unwrap_or(dest_url),
+            title,
+            id,
+        }),
+        other => other,
+    });
+    sanitized_html(parser, content.len())
+}
+
+fn sanitized_html<'a>(events: impl Iterator<Item = Event<'a>>, source_len: usize) -> String {
+    let mut unsafe_html = String::with_capacity(source_len * 3 / 2);
+    html::push_html(&mut unsafe_html, events);
+    sanitize(&unsafe_html, false)
+}
+
+/// Cleans rendered HTML. With `character_colors`, spans may also carry the
+/// class and colour written by [`render_colored_script`], and nothing else.
+fn sanitize(unsafe_html: &str, character_colors: bool) -> String {
+    let mut builder = ammonia::Builder::default();
+    builder
+        .add_tags(&["input"])
+        .add_tag_attributes("input", &["type", "checked", "disabled"]);
+    if character_colors {
+        builder.add_tag_attributes("span", &["class", "style"]);
+    }
+    builder
+        .attribute_filter(|element, attribute, value| match (element, attribute) {
+            ("input", "type") if value != "checkbox" => None,
+            ("span", "style") if !is_character_color(value) => None,
+            _ => Some(value.into()),
+        })
+        .clean(unsafe_html)
+        .to_string()
+}
+
+fn parse_atx_heading(line: &str) -> Option<(u8, String)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let mut text = rest.trim();
+    let without_closing = text.trim_end_matches('#');
+    if without_closing.is_empty() || without_closing.ends_with([' ', '\t']) {
+        text = without_closing.trim_end();
+    }
+    Some((level as u8, text.to_string()))
+}
+
+pub fn extract_headings(content: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+
+        if let Some((level, text)) = parse_atx_heading(line) {
+            headings.push(Heading {
+                level,
+                text,
+                line: index + 1,
+            });
+        }
+    }
+
+    headings
+}
+
+pub fn build_outline(headings: &[Heading]) -> Vec<OutlineNode> {
+    fn attach(nodes: &mut Vec<OutlineNode>, node: OutlineNode) {
+        match nodes.last_mut() {
+            Some(parent) if parent.level < node.level => attach(&mut parent.children, node),
+            _ => nodes.push(node),
+        }
+    }
+
+    let mut roots = Vec::new();
+    for heading in headings {
+        attach(
+            &mut roots,
+            OutlineNode {
+                level: heading.level,
+                text: heading.text.clone(),
+                line: heading.line,
+                children: Vec::new(),
+            },
+        );
+    }
+    roots
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatchedHeading {
+    pub template: String,
+    pub draft: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeadingComparison {
+    pub present: Vec<MatchedHeading>,
+    /// Template headings with no counterpart in the draft, in template order.
+    pub missing: Vec<String>,
+    /// Draft headings that match no template heading.
+    pub added: Vec<Heading>,
+}
+
+fn heading_key(text: &str) -> String {
+    text.to_lowercase()
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Matches template headings against a draft's. A draft heading counts when it
+/// equals the template heading or extends it, so `Act I: The Village` covers
+/// `Act I` but `Act II` does not. Level-1 headings are titles and are skipped
+/// on both sides; each draft heading covers at most one template heading.
+pub fn compare_headings(template: &[Heading], draft: &[Heading]) -> HeadingComparison {
+    let mut draft: Vec<(String, &Heading)> = draft
+        .iter()
+        .filter(|heading| heading.level > 1)
+        .map(|heading| (heading_key(&heading.text), heading))
+        .collect();
+    let mut present = Vec::new();
+    let mut missing = Vec::new();
+
+    for expected in template.iter().filter(|heading| heading.level > 1) {
+        let key = heading_key(&expected.text);
+        let found = draft.iter().position(|(candidate, _)| {
+            candidate == &key
+                || candidate
+                    .strip_prefix(&key)
+                    .is_some_and(|rest| rest.starts_with(' '))
+        });
+        match found {
+            Some(index) => {
+                let (_, heading) = draft.remove(index);
+                present.push(MatchedHeading {
+                    template: expected.text.clone(),
+                    draft: heading.text.clone(),
+                    line: heading.line,
+                });
+            }
+            None => missing.push(expected.text.clone()),
+        }
+    }
+
+    let mut added: Vec<Heading> = draft
+        .into_iter()
+        .map(|(_, heading)| heading.clone())
+        .collect();
+    added.sort_by_key(|heading| heading.line);
+    HeadingComparison {
+        present,
+        missing,
+        added,
+    }
+}
+
+/// Splits a leading `---` YAML block off the document, returning its simple
+/// `key: value` pairs and the line offset where the body starts.
+pub fn split_front_matter(content: &str) -> (Vec<(String, String)>, usize) {
+    let mut lines = content.lines();
+    if lines.next().map(str::trim_end) != Some("---") {
+        return (Vec::new(), 0);
+    }
+
+    let mut fields = Vec::new();
+    for (index, line) in lines.enumerate() {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            return (fields, index + 2);
+        }
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let value = value.trim().trim_matches(['"', '\'']);
+            fields.push((key.trim().to_lowercase(), value.to_string()));
+        }
+    }
+    (Vec::new(), 0)
+}
+
+/// The document without its leading front matter block.
+pub fn strip_front_matter(content: &str) -> String {
+    let (_, body_start) = split_front_matter(content);
+    if body_start == 0 {
+        return content.to_string();
+    }
+    content
+        .lines()
+        .skip(body_start)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_start_matches('\n')
+        .to_string()
+}
+
+/// The non-empty value of `key` in the document's front matter.
+pub fn front_matter_value(content: &str, key: &str) -> Option<String> {
+    let (fields, _) = split_front_matter(content);
+    fields
+        .into_iter()
+        .find(|(field, _)| field == key)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+/// Sets `key` in the front matter, adding a block when the document has none.
+/// `None` removes the key, and the block with it once nothing else is left.
+/// Files written with CRLF line endings keep them.
+pub fn set_front_matter_value(content: &str, key: &str, value: Option<&str>) -> String {
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let (_, body_start) = split_front_matter(content);
+    let lines = content.lines().collect::<Vec<_>>();
+    let (mut block, body) = if body_start == 0 {
+        (Vec::new(), &lines[..])
+    } else {
+        (
+            lines[1..body_start - 1]
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>(),
+            &lines[body_start..],
+        )
+    };
+
+    let existing = block.iter().position(|line| {
+        line.split_once(':')
+            .is_some_and(|(field, _)| field.trim().eq_ignore_ascii_case(key))
+    });
+    match (existing, value) {
+        (Some(index), Some(value)) => block[index] = format!("{key}: {value}"),
+        (None, Some(value)) => block.push(format!("{key}: {value}")),
+        (Some(index), None) => {
+            block.remove(index);
+        }
+        (None, None) => {}
+    }
+
+    let mut updated = String::with_capacity(content.len() + key.len() + 16);
+    if !block.is_empty() {
+        updated.push_str("---");
+        updated.push_str(newline);
+        for line in block {
+            updated.push_str(&line);
+            updated.push_str(newline);
+        }
+        updated.push_str("---");
+        updated.push_str(newline);
+    }
+    updated.push_str(&body.join(newline));
+    if !body.is_empty() && content.ends_with('\n') {
+        updated.push_str(newline);
+    }
+    updated
+}
+
+fn scene_location(heading: &str) -> Option<String> {
+    let upper = heading.to_uppercase();
+    let rest = ["INT./EXT.", "EXT./INT.", "INT.", "EXT.", "I/E"]
+        .iter()
+        .find_map(|prefix| {
+            upper
+                .starts_with(prefix)
+                .then(|| heading.get(prefix.len()..))
+                .flatten()
+        })?;
+    let location = rest.split(" - ").next().unwrap_or(rest).trim();
+    (!location.is_empty()).then(|| location.to_string())
+}
+
+/// Detects a speaking character from a dialogue line such as `RAVI: ...`,
+/// `**Meena**: ...` or a screenplay cue written on its own in capitals.
+pub fn dialogue_speaker(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    if trimmed.is_empty()
+        || (trimmed.starts_with(['#', '-', '*', '>', '|']) && !trimmed.starts_with("**"))
+    {
+        return None;
+    }
+
+    if let Some((speaker, _)) = trimmed.split_once(':') {
+        let speaker = speaker.trim().trim_matches('*').trim();
+        let words = speaker.split_whitespace().count();
+        let starts_like_name = speaker
+            .chars()
+            .next()
+            .is_some_and(|ch| ch.is_uppercase() || crate::language::is_tamil(ch));
+        if (1..=3).contains(&words) && starts_like_name && !speaker.contains(['(', '[', '/']) {
+            return Some(speaker.to_string());
+        }
+        return None;
+    }
+
+    let cue = trimmed.split('(').next().unwrap_or(trimmed).trim();
+    let words = cue.split_whitespace().count();
+    let is_cue = (1..=3).contains(&words)
+        && cue.chars().any(|ch| ch.is_alphabetic())
+        && cue
+            .chars()
+            .all(|ch| ch.is_uppercase() || ch.is_whitespace() || ch == '.' || ch == '\'')
+        && !cue.starts_with("INT")
+        && !cue.starts_with("EXT");
+    is_cue.then(|| cue.to_string())
+}
+
+pub fn scene_breakdown(content: &str) -> Vec<SceneBreakdown> {
+    let (front_matter, body_start) = split_front_matter(content);
+    let default_location = front_matter
+        .into_iter()
+        .find(|(key, _)| key == "location")
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty());
+
+    let lines: Vec<&str> = content.lines().collect();
+    let headings: Vec<Heading> = extract_headings(content)
+        .into_iter()
+        .filter(|heading| heading.line > body_start)
+        .collect();
+
+    headings
+        .iter()
+        .enumerate()
+        .map(|(index, heading)| {
+            let end = headings
+                .get(index + 1)
+                .map(|next| next.line - 1)
+                .unwrap_or(lines.len());
+            let body = &lines[heading.line.min(end)..end];
+
+            let mut characters: Vec<String> = Vec::new();
+            for speaker in body.iter().filter_map(|line| dialogue_speaker(line)) {
+                if !characters
+                    .iter()
+                    .any(|known| known.to_lowercase() == speaker.to_lowercase())
+                {
+                    characters.push(speaker);
+                }
+            }
+
+            SceneBreakdown {
+                heading: heading.text.clone(),
+                line: heading.line,
+                characters,
+                word_count: body
+                    .iter()
+                    .map(|line| line.split_whitespace().count())
+                    .sum(),
+                location: scene_location(&heading.text).or_else(|| default_location.clone()),
+            }
+        })
+        .collect()
+}
+
+pub const TOC_START: &str = "<!-- toc -->";
+pub const TOC_END: &str = "<!-- /toc -->";
+
+/// GitHub-style anchor for a heading. Headings with non-ASCII letters (Tamil
+/// and friends) have no stable slug, so they get `section-<n>` instead.
+fn heading_anchor(text: &str, index: usize) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for ch in text.trim().chars() {
+        match ch {
+            ' ' | '-' => slug.push('-'),
+            '_' => slug.push('_'),
+            ch if ch.is_ascii_alphanumeric() => slug.push(ch.to_ascii_lowercase()),
+            ch if ch.is_alphanumeric() => return format!("section-{index}"),
+            _ => {}
+        }
+    }
+    if slug.trim_matches('-').is_empty() {
+        format!("section-{index}")
+    } else {
+        slug
+    }
+}
+
+/// Escapes brackets in a heading so it can sit inside `[...]` link text.
+/// Brackets the heading already escapes are left alone.
+fn escape_link_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut after_backslash = false;
+    for ch in text.chars() {
+        if matches!(ch, '[' | ']') && !after_backslash {
+            escaped.push('\\');
+        }
+        after_backslash = ch == '\\' && !after_backslash;
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Inserts or refreshes the `<!-- toc -->` block, returning the new content and
+/// the number of entries. An existing block is replaced in place, a lone start
+/// marker acts as a placeholder, and otherwise the block goes at the top of the
+/// body after any front matter.
+pub fn insert_toc(content: &str) -> (String, usize) {
+    let (_, body_start) = split_front_matter(content);
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.iter().position(|line| line.trim() == TOC_START);
+    let end = start.and_then(|start| {
+        lines[start + 1..]
+            .iter()
+            .position(|line| line.trim() == TOC_END)
+            .map(|offset| start + 1 + offset)
+    });
+
+    let headings: Vec<Heading> = extract_headings(content)
+        .into_iter()
+        .filter(|heading| heading.line > body_start)
+        .collect();
+    let min_level = headings
+        .iter()
+        .map(|heading| heading.level)
+        .min()
+        .unwrap_or(1);
+
+    let mut anchors: Vec<String> = Vec::new();
+    let mut block = vec![TOC_START.to_string()];
+    for (index, heading) in headings.iter().enumerate() {
+        let base = heading_anchor(&heading.text, index + 1);
+        let duplicates = anchors.iter().filter(|anchor| **anchor == base).count();
+        anchors.push(base.clone());
+        let anchor = if duplicates == 0 {
+            base
+        } else {
+            format!("{base}-{duplicates}")
+        };
+        let indent = "  ".repeat((heading.level - min_level) as usize);
+        block.push(format!(
+            "{indent}- [{}](#{anchor})",
+            escape_link_text(&heading.text)
+        ));
+    }
+    block.push(TOC_END.to_string());
+
+    let (before, after) = match (start, end) {
+        (Some(start), Some(end)) => (&lines[..start], &lines[end + 1..]),
+        (Some(start), None) => (&lines[..start], &lines[start + 1..]),
+        (None, _) => {
+            let at = body_start.min(lines.len());
+            if lines.get(at).is_some_and(|line| !line.trim().is_empty()) {
+                block.push(String::new());
+            }
+            (&lines[..at], &lines[at..])
+        }
+    };
+
+    let mut output: Vec<&str> = before.to_vec();
+    output.extend(block.iter().map(String::as_str));
+    output.extend_from_slice(after);
+    (output.join("\n") + "\n", headings.len())
+}
+
+/// Every link or image target in the document: inline `[..](target)` links,
+/// reference definitions and `src`/`href` attributes in raw HTML. Titles,
+/// fragments and query strings are dropped.
+pub fn link_targets(content: &str) -> Vec<String> {
+    link_references(content)
+        .into_iter()
+        .map(|(_, target)| target)
+        .collect()
+}
+
+/// Same targets as [`link_targets`], paired with the 1-based line they
+/// appear on.
+pub fn link_references(content: &str) -> Vec<(usize, String)> {
+    fn clean(raw: &str) -> Option<String> {
+        let raw = raw.trim();
+        let target = match raw.strip_prefix('<') {
+            Some(rest) => rest.split('>').next().unwrap_or(rest),
+            None => raw.split_whitespace().next().unwrap_or(raw),
+        };
+        let target = target.split(['#', '?']).next().unwrap_or(target);
+        (!target.is_empty()).then(|| target.replace("%20", " "))
+    }
+
+    let mut references = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let mut targets = Vec::new();
+        for (offset, _) in line.match_indices("](") {
+            let rest = &line[offset + 2..];
+            if let Some(end) = rest.find(')') {
+                targets.extend(clean(&rest[..end]));
+            }
+        }
+        if let Some((label, target)) = line.trim_start().split_once("]:") {
+            if label.starts_with('[') && !label.starts_with("[^") {
+                targets.extend(clean(target));
+            }
+        }
+        for attribute in ["src=", "href="] {
+            for (offset, _) in line.match_indices(attribute) {
+                let rest = &line[offset + attribute.len()..];
+                let Some(quote) = rest.chars().next().filter(|ch| *ch == '"' || *ch == '\'') else {
+                    continue;
+                };
+                if let Some(value) = rest[1..].split(quote).next() {
+                    targets.extend(clean(value));
+                }
+            }
+        }
+        references.extend(targets.into_iter().map(|target| (line_number, target)));
+    }
+    references
+}
+
+/// How densely a format packs text onto a page, used for the page-a-minute
+/// runtime rule.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct PageDensity {
+    pub words_per_page: u32,
+    pub lines_per_page: u32,
+}
+
+pub const DEFAULT_RUNTIME_FORMAT: &str = "feature";
+
+pub const RUNTIME_FORMATS: &[(&str, PageDensity)] = &[
+    (
+        "feature",
+        PageDensity {
+            words_per_page: 180,
+            lines_per_page: 55,
+        },
+    ),
+    (
+        "short",
+        PageDensity {
+            words_per_page: 160,
+            lines_per_page: 50,
+        },
+    ),
+    (
+        "tv",
+        PageDensity {
+            words_per_page: 200,
+            lines_per_page: 56,
+        },
+    ),
+];
+
+/// Characters on a screenplay line before it wraps.
+const SCREENPLAY_LINE_CHARS: usize = 60;
+
+#[derive(Debug, Serialize)]
+pub struct RuntimeEstimate {
+    pub scenes: usize,
+    pub words: usize,
+    pub lines: usize,
+    pub pages: f32,
+    pub min_minutes: f32,
+    pub max_minutes: f32,
+    pub density: PageDensity,
+}
+
+/// Estimates pages from both word and wrapped-line counts; the two figures
+/// bound the runtime range at one page per minute.
+pub fn estimate_runtime(content: &str, density: PageDensity) -> RuntimeEstimate {
+    let (_, body_start) = split_front_matter(content);
+    let body: Vec<&str> = content.lines().skip(body_start).collect();
+
+    let scenes = body
+        .iter()
+        .filter(|line| {
+            let text = line.trim_start().trim_start_matches('#').trim();
+            scene_location(text).is_some()
+        })
+        .count();
+    let words = body
+        .iter()
+        .map(|line| line.split_whitespace().count())
+        .sum();
+    let lines = body
+        .iter()
+        .map(|line| {
+            line.trim()
+                .chars()
+                .count()
+                .div_ceil(SCREENPLAY_LINE_CHARS)
+                .max(1)
+        })
+        .sum();
+
+    let by_words = words as f32 / density.words_per_page.max(1) as f32;
+    let by_lines = lines as f32 / density.lines_per_page.max(1) as f32;
+    let round = |value: f32| (value * 10.0).round() / 10.0;
+
+    RuntimeEstimate {
+        scenes,
+        words,
+        lines,
+        pages: round((by_words + by_lines) / 2.0),
+        min_minutes: round(by_words.min(by_lines)),
+        max_minutes: round(by_words.max(by_lines)),
+        density,
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadingTime {
+    pub words: usize,
+    pub tamil_words: usize,
+    pub minutes: f32,
+}
+
+/// Minutes to read the body aloud, timing Tamil words at `tamil_wpm` and
+/// everything else at `english_wpm` so mixed drafts are paced fairly.
+pub fn reading_time(content: &str, english_wpm: u32, tamil_wpm: u32) -> ReadingTime {
+    let (_, body_start) = split_front_matter(content);
+    let (mut words, mut tamil_words) = (0, 0);
+    for word in content
+        .lines()
+        .skip(body_start)
+        .flat_map(str::split_whitespace)
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+    {
+        words += 1;
+        if word.chars().any(crate::language::is_tamil) {
+            tamil_words += 1;
+        }
+    }
+
+    let minutes = (words - tamil_words) as f32 / english_wpm.max(1) as f32
+        + tamil_words as f32 / tamil_wpm.max(1) as f32;
+    ReadingTime {
+        words,
+        tamil_words,
+        minutes: (minutes * 10.0).round() / 10.0,
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CharacterLocation {
+    pub path: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CharacterEntry {
+    pub name: String,
+    /// Other spellings folded into this entry, e.g. the Tamil form of a name.
+    pub aliases: Vec<String>,
+    pub appearances: usize,
+    pub files: Vec<String>,
+    pub first_appearance: CharacterLocation,
+    pub last_appearance: CharacterLocation,
+}
+
+/// Merges the speaking characters of several documents, given in reading
+/// order as `(path, content)`. Names are matched on their phonetic key so case
+/// and Tamil/Latin spellings collapse into one entry.
+pub fn character_index(documents: &[(String, String)]) -> Vec<CharacterEntry> {
+    let mut entries: Vec<(String, CharacterEntry)> = Vec::new();
+
+    for (path, content) in documents {
+        let (_, body_start) = split_front_matter(content);
+        for (index, line) in content.lines().enumerate().skip(body_start) {
+            let Some(speaker) = dialogue_speaker(line) else {
+                continue;
+            };
+            let key = crate::language::name_key(&speaker);
+            if key.is_empty() {
+                continue;
+            }
+            let location = CharacterLocation {
+                path: path.clone(),
+                line: index + 1,
+            };
+
+            match entries.iter_mut().find(|(existing, _)| *existing == key) {
+                Some((_, entry)) => {
+                    entry.appearances += 1;
+                    if !entry.files.contains(path) {
+                        entry.files.push(path.clone());
+                    }
+                    let known = std::iter::once(&entry.name)
+                        .chain(&entry.aliases)
+                        .any(|name| name.to_lowercase() == speaker.to_lowercase());
+                    if !known {
+                        entry.aliases.push(speaker);
+                    }
+                    entry.last_appearance = location;
+                }
+                None => entries.push((
+                    key,
+                    CharacterEntry {
+                        name: speaker,
+                        aliases: Vec::new(),
+                        appearances: 1,
+                        files: vec![path.clone()],
+                        first_appearance: location.clone(),
+                        last_appearance: location,
+                    },
+                )),
+            }
+        }
+    }
+
+    let mut entries: Vec<CharacterEntry> = entries.into_iter().map(|(_, entry)| entry).collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.appearances));
+    entries
+}
+
+#[derive(Debug, Serialize)]
+pub struct CharacterColor {
+    pub name: String,
+    pub color: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColoredScript {
+    pub html: String,
+    /// Speaking characters in order of first appearance.
+    pub characters: Vec<CharacterColor>,
+}
+
+/// A colour derived from the character's phonetic key with FNV-1a, so a name
+/// keeps its colour across files, sessions and Tamil/Latin spellings.
+fn character_color(name: &str) -> String {
+    let key = crate::language::name_key(name);
+    let key = if key.is_empty() {
+        name.to_lowercase()
+    } else {
+        key
+    };
+    let hash = key.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    format!("hsl({}, 60%, 40%)", hash % 360)
+}
+
+fn is_character_color(style: &str) -> bool {
+    style
+        .strip_prefix("color: hsl(")
+        .and_then(|rest| rest.strip_suffix(", 60%, 40%)"))
+        .and_then(|hue| hue.parse::<u16>().ok())
+        .is_some_and(|hue| hue < 360)
+}
+
+/// Renders a screenplay with each character cue and its dialogue wrapped in a
+/// span coloured for that character. A cue on its own line colours the lines
+/// below it up to the next blank line; `NAME: line` colours the rest of the
+/// line. Front matter is left out.
+pub fn render_colored_script(content: &str) -> ColoredScript {
+    let (_, body_start) = split_front_matter(content);
+    let mut characters: Vec<CharacterColor> = Vec::new();
+    let mut source = String::with_capacity(content.len() * 2);
+    let mut fence: Option<&str> = None;
+    // Colour of the cue whose dialogue block is still open.
+    let mut speaking: Option<String> = None;
+
+    for line in content.lines().skip(body_start) {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            source.push_str(line);
+            source.push('\n');
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            speaking = None;
+            source.push_str(line);
+            source.push('\n');
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            speaking = None;
+            source.push_str(line);
+            source.push('\n');
+            continue;
+        }
+
+        if let Some(speaker) = dialogue_speaker(line) {
+            let color = character_color(&speaker);
+            if !characters
+                .iter()
+                .any(|known| known.name.to_lowercase() == speaker.to_lowercase())
+            {
+                characters.push(CharacterColor {
+                    name: speaker,
+                    color: color.clone(),
+                });
+            }
+            match trimmed.split_once(':') {
+                Some((cue, dialogue)) => {
+                    source.push_str(&format!(
+                        "{indent}<span class=\"character-cue\" style=\"color: {color}\">{cue}:</span>"
+                    ));
+                    let dialogue = dialogue.trim();
+                    if !dialogue.is_empty() {
+                        source.push_str(&format!(
+                            " <span class=\"dialogue\" style=\"color: {color}\">{dialogue}</span>"
+                        ));
+                    }
+                    speaking = None;
+                }
+                None => {
+                    source.push_str(&format!(
+                        "{indent}<span class=\"character-cue\" style=\"color: {color}\">{trimmed}</span>"
+                    ));
+                    speaking = Some(color);
+                }
+            }
+            source.push('\n');
+            continue;
+        }
+
+        // Lists, quotes and tables below a cue are not dialogue.
+        let plain =
+            !trimmed.starts_with(['-', '*', '+', '>', '|', '<']) || trimmed.starts_with("**");
+        match speaking.as_deref().filter(|_| plain) {
+            Some(color) => source.push_str(&format!(
+                "{indent}<span class=\"dialogue\" style=\"color: {color}\">{trimmed}</span>"
+            )),
+            None => source.push_str(line),
+        }
+        source.push('\n');
+    }
+
+    let mut unsafe_html = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(
+        &mut unsafe_html,
+        Parser::new_ext(&source, markdown_options()),
+    );
+    ColoredScript {
+        html: sanitize(&unsafe_html, true),
+        characters,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_front_matter_value_keeps_crlf_line_endings() {
+        let content = "---\r\ntitle: Scene\r\n---\r\n# Scene\r\n\r\nRAVI: Hello.\r\n";
+        let tagged = set_front_matter_value(content, "language", Some("ta"));
+        assert_eq!(
+            tagged,
+            "---\r\ntitle: Scene\r\nlanguage: ta\r\n---\r\n# Scene\r\n\r\nRAVI: Hello.\r\n"
+        );
+        assert_eq!(set_front_matter_value(&tagged, "language", None), content);
+
+        let plain = "# Scene\r\nRAVI: Hello.\r\n";
+        assert_eq!(
+            set_front_matter_value(plain, "status", Some("draft")),
+            "---\r\nstatus: draft\r\n---\r\n# Scene\r\nRAVI: Hello.\r\n"
+        );
+    }
+
+    #[test]
+    fn set_front_matter_value_keeps_lf_line_endings() {
+        let content = "# Scene\nRAVI: Hello.\n";
+        assert_eq!(
+            set_front_matter_value(content, "language", Some("en")),
+            "---\nlanguage: en\n---\n# Scene\nRAVI: Hello.\n"
+        );
+    }
+
+    #[test]
+    fn insert_toc_escapes_brackets_in_link_text() {
+        let content = "# Act [One]\n## Scene ]2[\n## Already \\[escaped\\]\n";
+        let (output, entries) = insert_toc(content);
+        assert_eq!(entries, 3);
+        assert!(output.contains("- [Act \\[One\\]](#act-one)\n"));
+        assert!(output.contains("  - [Scene \\]2\\[](#scene-2)\n"));
+        assert!(output.contains("  - [Already \\[escaped\\]](#already-escaped)\n"));
+    }
+}
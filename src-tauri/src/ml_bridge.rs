@@ -1,12 +1,167 @@
-use std::io::Write;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Child, ChildStderr, ChildStdout, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
 
 use log::{trace, warn};
-use serde::Deserialize;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use unicode_normalization::UnicodeNormalization;
+use uuid::Uuid;
 
 use crate::error::{AppError, AppResult};
+use crate::models::TemplateSection;
+
+struct TrackedChild {
+    child: Mutex<Child>,
+    cancelled: AtomicBool,
+}
+
+static TRACKED_CHILDREN: Lazy<Mutex<HashMap<String, Arc<TrackedChild>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn track_child(key: &str, child: Child) -> AppResult<Arc<TrackedChild>> {
+    let mut children = TRACKED_CHILDREN
+        .lock()
+        .map_err(|_| AppError::Message("Process registry poisoned".into()))?;
+    if children.contains_key(key) {
+        let mut child = child;
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(AppError::Message(format!("'{key}' is already running")));
+    }
+    let tracked = Arc::new(TrackedChild {
+        child: Mutex::new(child),
+        cancelled: AtomicBool::new(false),
+    });
+    children.insert(key.to_string(), tracked.clone());
+    Ok(tracked)
+}
+
+fn untrack_child(key: &str) {
+    if let Ok(mut children) = TRACKED_CHILDREN.lock() {
+        children.remove(key);
+    }
+}
+
+fn wait_tracked(tracked: &TrackedChild) -> AppResult<ExitStatus> {
+    loop {
+        {
+            let mut child = tracked
+                .child
+                .lock()
+                .map_err(|_| AppError::Message("Process handle poisoned".into()))?;
+            if let Some(status) = child.try_wait()? {
+                return Ok(status);
+            }
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Spawns `command` under a fresh key so [`cancel_all_tracked_processes`]
+/// can stop it; callers untrack the key once the process has exited.
+fn spawn_tracked(command: &mut Command) -> AppResult<(String, Arc<TrackedChild>)> {
+    let child = command
+        .spawn()
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+    let key = format!("ml-{}", Uuid::new_v4());
+    let tracked = track_child(&key, child)?;
+    Ok((key, tracked))
+}
+
+/// Takes the child's stdout and stderr, so they can be drained while it runs.
+fn take_output_pipes(
+    tracked: &TrackedChild,
+) -> AppResult<(Option<ChildStdout>, Option<ChildStderr>)> {
+    let mut child = tracked
+        .child
+        .lock()
+        .map_err(|_| AppError::Message("Process handle poisoned".into()))?;
+    Ok((child.stdout.take(), child.stderr.take()))
+}
+
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+/// Tracked counterpart of `Command::output`: runs `command` to completion,
+/// writing `input` to its stdin when given.
+fn output_tracked(command: &mut Command, input: Option<&[u8]>) -> AppResult<Output> {
+    command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let (key, tracked) = spawn_tracked(command)?;
+    let output = (|| {
+        let stdin = tracked
+            .child
+            .lock()
+            .map_err(|_| AppError::Message("Process handle poisoned".into()))?
+            .stdin
+            .take();
+        let (stdout, stderr) = take_output_pipes(&tracked)?;
+        let stdout = read_pipe(stdout);
+        let stderr = read_pipe(stderr);
+        if let (Some(mut pipe), Some(input)) = (stdin, input) {
+            pipe.write_all(input)?;
+        }
+        let status = wait_tracked(&tracked)?;
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        })
+    })();
+    untrack_child(&key);
+    output
+}
+
+pub fn cancel_tracked_process(key: &str) -> bool {
+    let tracked = TRACKED_CHILDREN
+        .lock()
+        .ok()
+        .and_then(|children| children.get(key).cloned());
+    match tracked {
+        Some(tracked) => {
+            tracked.cancelled.store(true, Ordering::SeqCst);
+            if let Ok(mut child) = tracked.child.lock() {
+                if let Err(err) = child.kill() {
+                    warn!("Failed to kill '{key}': {err}");
+                }
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Kills every tracked ML subprocess, returning how many were running. Every
+/// Python process the bridge starts is tracked.
+pub fn cancel_all_tracked_processes() -> usize {
+    let keys: Vec<String> = TRACKED_CHILDREN
+        .lock()
+        .map(|children| children.keys().cloned().collect())
+        .unwrap_or_default();
+    keys.iter()
+        .filter(|key| cancel_tracked_process(key))
+        .count()
+}
 
 #[derive(Debug, Deserialize)]
 struct PythonTransliteration {
@@ -14,33 +169,76 @@ struct PythonTransliteration {
     notes: Option<Vec<String>>,
 }
 
-pub async fn transliterate_english_to_tamil(input: &str) -> AppResult<Vec<String>> {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransliterationStatus {
+    Candidates,
+    NoSuggestions,
+    EmptyInput,
+    Fallback,
+}
+
+#[derive(Debug)]
+pub struct Transliteration {
+    pub candidates: Vec<String>,
+    pub status: TransliterationStatus,
+    pub notes: Vec<String>,
+}
+
+pub const TRANSLITERATION_MODES: &[&str] = &["phonetic", "formal", "colloquial"];
+pub const DEFAULT_TRANSLITERATION_MODE: &str = "phonetic";
+
+pub async fn transliterate_english_to_tamil(input: &str, mode: &str) -> AppResult<Transliteration> {
     if input.trim().is_empty() {
-        return Ok(Vec::new());
+        return Ok(Transliteration {
+            candidates: Vec::new(),
+            status: TransliterationStatus::EmptyInput,
+            notes: Vec::new(),
+        });
     }
 
     let text = input.to_owned();
-    let result = tokio::task::spawn_blocking(move || invoke_python_transliteration(&text)).await;
+    let mode = mode.to_owned();
+    let result =
+        tokio::task::spawn_blocking(move || invoke_python_transliteration(&text, &mode)).await;
 
     match result {
-        Ok(Ok(output)) => Ok(output),
+        Ok(Ok(parsed)) => {
+            let candidates = normalize_candidates(parsed.candidates);
+            let status = if candidates.is_empty() {
+                TransliterationStatus::NoSuggestions
+            } else {
+                TransliterationStatus::Candidates
+            };
+            Ok(Transliteration {
+                candidates,
+                status,
+                notes: parsed.notes.unwrap_or_default(),
+            })
+        }
         Ok(Err(err)) => {
             warn!("Python transliteration failed: {err}");
-            Ok(fallback_transliteration(input))
+            Ok(fallback_transliteration(input, err.to_string()))
         }
         Err(join_err) => {
             warn!("Failed to spawn python transliteration task: {join_err}");
-            Ok(fallback_transliteration(input))
+            Ok(fallback_transliteration(input, join_err.to_string()))
         }
     }
 }
 
-pub async fn transcribe_audio_file(audio_path: &str, language: &str) -> AppResult<Value> {
+pub async fn transcribe_audio_file(
+    audio_path: &str,
+    language: &str,
+    start_secs: Option<f64>,
+    end_secs: Option<f64>,
+    diarize: bool,
+) -> AppResult<Value> {
     let audio = audio_path.to_string();
     let lang = language.to_string();
     
     let result = tokio::task::spawn_blocking(move || {
-        invoke_python_stt_file(&audio, &lang)
+        invoke_python_stt_file(&audio, &lang, start_secs, end_secs, diarize)
     }).await;
     
     match result {
@@ -50,11 +248,17 @@ pub async fn transcribe_audio_file(audio_path: &str, language: &str) -> AppResul
     }
 }
 
-pub async fn record_and_transcribe(duration: i32, language: &str) -> AppResult<Value> {
+pub async fn record_and_transcribe(
+    duration: i32,
+    language: &str,
+    device_id: Option<u32>,
+    sample_rate: Option<u32>,
+    output: Option<PathBuf>,
+) -> AppResult<Value> {
     let lang = language.to_string();
     
     let result = tokio::task::spawn_blocking(move || {
-        invoke_python_stt_mic(duration, &lang)
+        invoke_python_stt_mic(duration, &lang, device_id, sample_rate, output.as_deref())
     }).await;
     
     match result {
@@ -79,13 +283,34 @@ pub async fn synthesize_speech(text: &str, output_path: Option<&str>) -> AppResu
     }
 }
 
-pub async fn generate_scene_ai(prompt: &str, context: &str, api_key: Option<&str>) -> AppResult<Value> {
+pub async fn generate_scene_ai<F>(
+    prompt: &str,
+    context: &str,
+    api_key: Option<&str>,
+    variations: u8,
+    local_model_path: Option<&str>,
+    system_prompt: Option<&str>,
+    on_variation: F,
+) -> AppResult<Value>
+where
+    F: FnMut(&Value) + Send + 'static,
+{
     let pmt = prompt.to_string();
     let ctx = context.to_string();
     let key = api_key.map(|s| s.to_string());
+    let local_model = local_model_path.map(|s| s.to_string());
+    let system = system_prompt.map(|s| s.to_string());
     
     let result = tokio::task::spawn_blocking(move || {
-        invoke_python_llm(&pmt, &ctx, key.as_deref())
+        invoke_python_llm(
+            &pmt,
+            &ctx,
+            key.as_deref(),
+            variations,
+            local_model.as_deref(),
+            system.as_deref(),
+            on_variation,
+        )
     }).await;
     
     match result {
@@ -95,6 +320,310 @@ pub async fn generate_scene_ai(prompt: &str, context: &str, api_key: Option<&str
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TtsVoice {
+    pub id: String,
+    pub name: String,
+    pub language: Option<String>,
+    pub gender: Option<String>,
+    pub sample_available: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct PythonTtsVoices {
+    voices: Vec<TtsVoice>,
+    error: Option<String>,
+}
+
+pub async fn list_tts_voices(model_id: &str, models_root: &Path) -> AppResult<Vec<TtsVoice>> {
+    let model_id = model_id.to_string();
+    let root = models_root.to_string_lossy().to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        invoke_python_cli(&["tts-voices", "--model", &model_id, "--root", &root], None)
+    })
+    .await;
+    let output = match result {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(err),
+        Err(join_err) => return Err(AppError::Anyhow(join_err.into())),
+    };
+
+    let listing: PythonTtsVoices = serde_json::from_value(output)?;
+    match listing.error {
+        Some(error) => Err(AppError::Message(error)),
+        None => Ok(listing.voices),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ToolkitVersion {
+    pub toolkit: String,
+    pub python: String,
+}
+
+pub async fn toolkit_version() -> AppResult<ToolkitVersion> {
+    let result = tokio::task::spawn_blocking(|| invoke_python_cli(&["version"], None)).await;
+    match result {
+        Ok(Ok(output)) => Ok(serde_json::from_value(output)?),
+        Ok(Err(err)) => Err(err),
+        Err(join_err) => Err(AppError::Anyhow(join_err.into())),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PythonSttLanguages {
+    languages: Vec<String>,
+    error: Option<String>,
+}
+
+pub async fn stt_languages(model_id: &str) -> AppResult<Vec<String>> {
+    let model_id = model_id.to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        invoke_python_cli(&["stt-languages", "--model", &model_id], None)
+    })
+    .await;
+    let output = match result {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(err),
+        Err(join_err) => return Err(AppError::Anyhow(join_err.into())),
+    };
+
+    let listing: PythonSttLanguages = serde_json::from_value(output)?;
+    match listing.error {
+        Some(error) => Err(AppError::Message(error)),
+        None => Ok(listing.languages),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SceneSummary {
+    pub summary: String,
+    pub logline: String,
+    pub model_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PythonSceneSummary {
+    summary: String,
+    logline: String,
+    model_id: String,
+    error: Option<String>,
+}
+
+/// One-paragraph summary plus a one-line logline for a scene, using the same
+/// API-then-local fallback as scene generation. `local_model_path` picks the
+/// downloaded model to fall back to; `offline` keeps generation on it.
+pub async fn summarize_scene(
+    scene: &str,
+    api_key: Option<&str>,
+    local_model_path: Option<&str>,
+    offline: bool,
+) -> AppResult<SceneSummary> {
+    let payload = serde_json::json!({
+        "scene": scene,
+        "api_key": api_key,
+        "local_model_path": local_model_path,
+    })
+    .to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        let args: &[&str] = if offline {
+            &["summarize", "--offline"]
+        } else {
+            &["summarize"]
+        };
+        invoke_python_cli(args, Some(&payload))
+    })
+    .await;
+    let output = match result {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(err),
+        Err(join_err) => return Err(AppError::Anyhow(join_err.into())),
+    };
+
+    let summary: PythonSceneSummary = serde_json::from_value(output)?;
+    if summary.summary.is_empty() {
+        return Err(AppError::Message(summary.error.unwrap_or_else(|| {
+            "The model returned no summary for this scene".into()
+        })));
+    }
+    Ok(SceneSummary {
+        summary: summary.summary,
+        logline: summary.logline,
+        model_id: summary.model_id,
+    })
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OutlineBeat {
+    pub section: String,
+    pub beat: String,
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DraftOutline {
+    pub beats: Vec<OutlineBeat>,
+    pub model_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PythonDraftOutline {
+    beats: Vec<OutlineBeat>,
+    model_id: String,
+    error: Option<String>,
+}
+
+/// Beat-by-beat outline of a draft, grouped under the template's sections when
+/// `structure` names any. `offline` keeps generation on the local model.
+pub async fn extract_outline(
+    draft: &str,
+    structure: &[TemplateSection],
+    api_key: Option<&str>,
+    offline: bool,
+) -> AppResult<DraftOutline> {
+    let payload = serde_json::json!({
+        "draft": draft,
+        "structure": structure,
+        "api_key": api_key,
+    })
+    .to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        let args: &[&str] = if offline {
+            &["outline", "--offline"]
+        } else {
+            &["outline"]
+        };
+        invoke_python_cli(args, Some(&payload))
+    })
+    .await;
+    let output = match result {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(err),
+        Err(join_err) => return Err(AppError::Anyhow(join_err.into())),
+    };
+
+    let outline: PythonDraftOutline = serde_json::from_value(output)?;
+    if outline.beats.is_empty() {
+        return Err(AppError::Message(outline.error.unwrap_or_else(|| {
+            "The model returned no outline for this draft".into()
+        })));
+    }
+    Ok(DraftOutline {
+        beats: outline.beats,
+        model_id: outline.model_id,
+    })
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SpeechPreview {
+    /// Base64-encoded audio, ready for a `data:` URL.
+    pub audio: String,
+    pub mime_type: String,
+    pub engine: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PythonSpeechPreview {
+    #[serde(default)]
+    success: bool,
+    audio: Option<String>,
+    mime_type: Option<String>,
+    engine: Option<String>,
+    error: Option<String>,
+}
+
+/// Synthesizes `text` into memory for a quick audition; the Python side
+/// renders to a temporary file and removes it before returning.
+pub async fn preview_speech(
+    text: &str,
+    language: &str,
+    voice: Option<&str>,
+) -> AppResult<SpeechPreview> {
+    let text = text.to_string();
+    let mut args = vec![
+        "tts-preview".to_string(),
+        "--stdin".to_string(),
+        "--language".to_string(),
+        language.to_string(),
+    ];
+    if let Some(voice) = voice {
+        args.extend(["--voice".to_string(), voice.to_string()]);
+    }
+    let result = tokio::task::spawn_blocking(move || {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        invoke_python_cli(&args, Some(&text))
+    })
+    .await;
+    let output = match result {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(err),
+        Err(join_err) => return Err(AppError::Anyhow(join_err.into())),
+    };
+
+    let preview: PythonSpeechPreview = serde_json::from_value(output)?;
+    match preview.audio {
+        Some(audio) if preview.success => Ok(SpeechPreview {
+            audio,
+            mime_type: preview.mime_type.unwrap_or_else(|| "audio/wav".into()),
+            engine: preview.engine,
+        }),
+        _ => {
+            Err(AppError::Message(preview.error.unwrap_or_else(|| {
+                "Speech preview produced no audio".into()
+            })))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InlineCompletion {
+    pub text: String,
+    pub model_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PythonInlineCompletion {
+    text: String,
+    model_id: String,
+    error: Option<String>,
+}
+
+/// Fill-in-the-middle generation: returns only the text to splice in between
+/// `before` and `after`.
+pub async fn generate_scene_inline(
+    before: &str,
+    after: &str,
+    instruction: &str,
+    api_key: Option<&str>,
+) -> AppResult<InlineCompletion> {
+    let payload = serde_json::json!({
+        "before": before,
+        "after": after,
+        "instruction": instruction,
+        "api_key": api_key,
+    })
+    .to_string();
+    let result =
+        tokio::task::spawn_blocking(move || invoke_python_cli(&["llm-inline"], Some(&payload)))
+            .await;
+    let output = match result {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(err),
+        Err(join_err) => return Err(AppError::Anyhow(join_err.into())),
+    };
+
+    let completion: PythonInlineCompletion = serde_json::from_value(output)?;
+    if completion.text.is_empty() {
+        return Err(AppError::Message(completion.error.unwrap_or_else(|| {
+            "The model returned no text for the cursor position".into()
+        })));
+    }
+    Ok(InlineCompletion {
+        text: completion.text,
+        model_id: completion.model_id,
+    })
+}
+
 pub async fn fetch_model_inventory(models_root: &Path) -> AppResult<Vec<Value>> {
     let root = models_root.to_path_buf();
     let result = tokio::task::spawn_blocking(move || python_model_inventory(&root)).await;
@@ -105,7 +634,222 @@ pub async fn fetch_model_inventory(models_root: &Path) -> AppResult<Vec<Value>>
     }
 }
 
-fn invoke_python_transliteration(text: &str) -> AppResult<Vec<String>> {
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PhoneticSyllable {
+    pub text: String,
+    pub roman: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PhoneticHint {
+    pub word: String,
+    pub romanized: String,
+    pub syllables: Vec<PhoneticSyllable>,
+    pub engine: String,
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
+
+pub async fn phonetic_hint(word: &str) -> AppResult<PhoneticHint> {
+    let text = word.to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        invoke_python_cli(&["phonetics", "--stdin"], Some(&text))
+    })
+    .await;
+    match result {
+        Ok(Ok(output)) => Ok(serde_json::from_value(output)?),
+        Ok(Err(err)) => Err(err),
+        Err(join_err) => Err(AppError::Anyhow(join_err.into())),
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InputSuggestion {
+    pub text: String,
+    pub source: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PythonSuggestions {
+    #[serde(default)]
+    suggestions: Vec<InputSuggestion>,
+}
+
+pub async fn input_suggestions(prefix: &str, limit: u8) -> AppResult<Vec<InputSuggestion>> {
+    let prefix_arg = format!("--prefix={prefix}");
+    let limit = limit.to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        invoke_python_cli(&["suggest", &prefix_arg, "--limit", &limit], None)
+    })
+    .await;
+    match result {
+        Ok(Ok(output)) => {
+            let parsed: PythonSuggestions = serde_json::from_value(output)?;
+            Ok(parsed.suggestions)
+        }
+        Ok(Err(err)) => Err(err),
+        Err(join_err) => Err(AppError::Anyhow(join_err.into())),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LlmCredentialCheck {
+    pub provider: String,
+    pub success: bool,
+    pub label: Option<String>,
+    pub usage: Option<f64>,
+    pub limit: Option<f64>,
+    pub is_free_tier: Option<bool>,
+    pub error: Option<String>,
+}
+
+pub async fn test_llm_credentials(provider: &str, api_key: &str) -> AppResult<LlmCredentialCheck> {
+    let provider = provider.to_string();
+    let api_key = api_key.to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        invoke_python_cli(&["test-llm", "--provider", &provider], Some(&api_key))
+    })
+    .await;
+    match result {
+        Ok(Ok(output)) => Ok(serde_json::from_value(output)?),
+        Ok(Err(err)) => Err(err),
+        Err(join_err) => Err(AppError::Anyhow(join_err.into())),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LlmEstimate {
+    pub model: String,
+    pub local: bool,
+    pub tokenizer: String,
+    pub prompt_tokens: u64,
+    pub max_completion_tokens: u64,
+    pub total_tokens: u64,
+    pub estimated_cost_usd: Option<f64>,
+}
+
+pub async fn estimate_llm_request(
+    prompt: &str,
+    context: &str,
+    model: Option<&str>,
+    variations: u8,
+) -> AppResult<LlmEstimate> {
+    let payload = serde_json::json!({ "prompt": prompt, "context": context }).to_string();
+    let mut args = vec![
+        "count-tokens".to_string(),
+        "--variations".to_string(),
+        variations.to_string(),
+    ];
+    if let Some(model) = model {
+        args.extend(["--model".to_string(), model.to_string()]);
+    }
+    let result = tokio::task::spawn_blocking(move || {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        invoke_python_cli(&args, Some(&payload))
+    })
+    .await;
+    match result {
+        Ok(Ok(output)) => Ok(serde_json::from_value(output)?),
+        Ok(Err(err)) => Err(err),
+        Err(join_err) => Err(AppError::Anyhow(join_err.into())),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AudioDevice {
+    pub id: u32,
+    pub name: String,
+    pub channels: u32,
+    pub default_sample_rate: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct AudioDeviceList {
+    devices: Vec<AudioDevice>,
+    error: Option<String>,
+}
+
+pub async fn list_audio_devices() -> AppResult<Vec<AudioDevice>> {
+    let result = tokio::task::spawn_blocking(|| invoke_python_cli(&["audio-devices"], None)).await;
+    let output = match result {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(err),
+        Err(join_err) => return Err(AppError::Anyhow(join_err.into())),
+    };
+    let list: AudioDeviceList = serde_json::from_value(output)?;
+    match list.error {
+        Some(error) => Err(AppError::Message(error)),
+        None => Ok(list.devices),
+    }
+}
+
+pub fn download_key(model_id: &str) -> String {
+    format!("download:{model_id}")
+}
+
+pub async fn download_model<F>(
+    models_root: &Path,
+    model_id: &str,
+    on_progress: F,
+) -> AppResult<Value>
+where
+    F: FnMut(&Value) + Send + 'static,
+{
+    let root = models_root.to_path_buf();
+    let id = model_id.to_string();
+    let result =
+        tokio::task::spawn_blocking(move || invoke_python_download(&root, &id, on_progress)).await;
+    match result {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(err)) => Err(err),
+        Err(join_err) => Err(AppError::Anyhow(join_err.into())),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ModelBenchmark {
+    pub model: String,
+    pub model_type: String,
+    pub runs: u32,
+    pub latencies_ms: Vec<f64>,
+    pub average_ms: Option<f64>,
+    pub engine: Option<String>,
+    /// Speech-to-text only: word accuracy against the known sample transcript.
+    pub accuracy: Option<f64>,
+    pub transcript: Option<String>,
+    pub error: Option<String>,
+}
+
+pub async fn benchmark_model<F>(
+    models_root: &Path,
+    model_id: &str,
+    runs: u8,
+    on_progress: F,
+) -> AppResult<ModelBenchmark>
+where
+    F: FnMut(&Value) + Send + 'static,
+{
+    let root = models_root.to_path_buf();
+    let id = model_id.to_string();
+    let result =
+        tokio::task::spawn_blocking(move || invoke_python_benchmark(&root, &id, runs, on_progress))
+            .await;
+    let output = match result {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(err),
+        Err(join_err) => return Err(AppError::Anyhow(join_err.into())),
+    };
+
+    let benchmark: ModelBenchmark = serde_json::from_value(output)?;
+    match benchmark.error {
+        Some(error) => Err(AppError::Message(format!(
+            "Benchmark of {model_id} failed: {error}"
+        ))),
+        None => Ok(benchmark),
+    }
+}
+
+fn invoke_python_transliteration(text: &str, mode: &str) -> AppResult<PythonTransliteration> {
     let ml_root = locate_ml_root()?;
     let python = std::env::var("SCRIPTWRITER_PYTHON").unwrap_or_else(|_| "python3".to_string());
 
@@ -115,6 +859,8 @@ fn invoke_python_transliteration(text: &str) -> AppResult<Vec<String>> {
         .arg("scriptwriter_ml.cli")
         .arg("transliterate")
         .arg("--stdin")
+        .arg("--mode")
+        .arg(mode)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -122,15 +868,7 @@ fn invoke_python_transliteration(text: &str) -> AppResult<Vec<String>> {
 
     trace!("Invoking python transliteration via {:?}", command);
 
-    let mut child = command
-        .spawn()
-        .map_err(|err| AppError::Anyhow(err.into()))?;
-
-    if let Some(stdin) = child.stdin.as_mut() {
-        stdin.write_all(text.as_bytes())?;
-    }
-
-    let output = child.wait_with_output()?;
+    let output = output_tracked(&mut command, Some(text.as_bytes()))?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(AppError::Message(format!(
@@ -145,7 +883,7 @@ fn invoke_python_transliteration(text: &str) -> AppResult<Vec<String>> {
             warn!("Python transliteration note: {note}");
         }
     }
-    Ok(parsed.candidates)
+    Ok(parsed)
 }
 
 fn python_model_inventory(models_root: &PathBuf) -> AppResult<Vec<Value>> {
@@ -165,7 +903,7 @@ fn python_model_inventory(models_root: &PathBuf) -> AppResult<Vec<Value>> {
 
     trace!("Checking model inventory via {:?}", command);
 
-    let output = command.output()?;
+    let output = output_tracked(&mut command, None)?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(AppError::Message(format!(
@@ -183,6 +921,204 @@ fn python_model_inventory(models_root: &PathBuf) -> AppResult<Vec<Value>> {
     Ok(parsed.models)
 }
 
+fn invoke_python_cli(args: &[&str], stdin: Option<&str>) -> AppResult<Value> {
+    let ml_root = locate_ml_root()?;
+    let python = std::env::var("SCRIPTWRITER_PYTHON").unwrap_or_else(|_| "python3".to_string());
+
+    let mut command = Command::new(python);
+    command
+        .arg("-m")
+        .arg("scriptwriter_ml.cli")
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .env("PYTHONPATH", &ml_root);
+
+    trace!("Invoking python CLI via {:?}", command);
+
+    let output = output_tracked(&mut command, stdin.map(str::as_bytes))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::Message(format!(
+            "Python CLI exited with status {}: {stderr}",
+            output.status
+        )));
+    }
+
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+fn invoke_python_download<F>(
+    models_root: &Path,
+    model_id: &str,
+    mut on_progress: F,
+) -> AppResult<Value>
+where
+    F: FnMut(&Value),
+{
+    let ml_root = locate_ml_root()?;
+    let python = std::env::var("SCRIPTWRITER_PYTHON").unwrap_or_else(|_| "python3".to_string());
+
+    let mut command = Command::new(python);
+    command
+        .arg("-m")
+        .arg("scriptwriter_ml.cli")
+        .arg("download")
+        .arg("--model")
+        .arg(model_id)
+        .arg("--root")
+        .arg(models_root)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .env("PYTHONPATH", &ml_root);
+
+    trace!("Downloading model via {:?}", command);
+
+    let mut child = command
+        .spawn()
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    let key = download_key(model_id);
+    let tracked = track_child(&key, child)?;
+
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buffer = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut buffer);
+        }
+        buffer
+    });
+
+    let mut final_payload = None;
+    if let Some(stdout) = stdout {
+        for line in BufReader::new(stdout).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Value>(&line) {
+                Ok(event) if event.get("event").and_then(Value::as_str) == Some("progress") => {
+                    on_progress(&event)
+                }
+                Ok(event) => final_payload = Some(event),
+                Err(err) => warn!("Ignoring malformed download output: {err}"),
+            }
+        }
+    }
+
+    let status = wait_tracked(&tracked);
+    untrack_child(&key);
+    let stderr = stderr_reader.join().unwrap_or_default();
+    let status = status?;
+
+    if tracked.cancelled.load(Ordering::SeqCst) {
+        return Err(AppError::Message(format!(
+            "Download of {model_id} was cancelled"
+        )));
+    }
+    if !status.success() {
+        return Err(AppError::Message(format!(
+            "Python CLI exited with status {status}: {stderr}"
+        )));
+    }
+
+    let payload = final_payload
+        .ok_or_else(|| AppError::Message("Model download finished without a result".into()))?;
+    if payload.get("success").and_then(Value::as_bool) != Some(true) {
+        let reason = payload
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Err(AppError::Message(format!(
+            "Model download failed: {reason}"
+        )));
+    }
+    Ok(payload)
+}
+
+fn invoke_python_benchmark<F>(
+    models_root: &Path,
+    model_id: &str,
+    runs: u8,
+    mut on_progress: F,
+) -> AppResult<Value>
+where
+    F: FnMut(&Value),
+{
+    let ml_root = locate_ml_root()?;
+    let python = std::env::var("SCRIPTWRITER_PYTHON").unwrap_or_else(|_| "python3".to_string());
+
+    let mut command = Command::new(python);
+    command
+        .arg("-m")
+        .arg("scriptwriter_ml.cli")
+        .arg("benchmark")
+        .arg("--model")
+        .arg(model_id)
+        .arg("--runs")
+        .arg(runs.to_string())
+        .arg("--root")
+        .arg(models_root)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .env("PYTHONPATH", &ml_root);
+
+    trace!("Benchmarking model via {:?}", command);
+
+    let (key, tracked) = spawn_tracked(&mut command)?;
+    let (stdout, stderr) = take_output_pipes(&tracked)?;
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buffer = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut buffer);
+        }
+        buffer
+    });
+
+    let mut final_payload = None;
+    if let Some(stdout) = stdout {
+        for line in BufReader::new(stdout).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Value>(&line) {
+                Ok(event) if event.get("event").and_then(Value::as_str) == Some("progress") => {
+                    on_progress(&event)
+                }
+                Ok(event) => final_payload = Some(event),
+                Err(err) => warn!("Ignoring malformed benchmark output: {err}"),
+            }
+        }
+    }
+
+    let status = wait_tracked(&tracked);
+    untrack_child(&key);
+    let stderr = stderr_reader.join().unwrap_or_default();
+    let status = status?;
+    if !status.success() {
+        return Err(AppError::Message(format!(
+            "Python CLI exited with status {status}: {stderr}"
+        )));
+    }
+
+    final_payload
+        .ok_or_else(|| AppError::Message("Model benchmark finished without a result".into()))
+}
+
 fn locate_ml_root() -> AppResult<PathBuf> {
     let candidates = {
         let mut options = Vec::new();
@@ -212,26 +1148,61 @@ fn locate_ml_root() -> AppResult<PathBuf> {
     ))
 }
 
-fn fallback_transliteration(text: &str) -> Vec<String> {
-    vec![text.to_string()]
+/// Python engines may emit decomposed Tamil; NFC keeps comparisons and search
+/// consistent. Normalizing can make two candidates identical, so drop repeats.
+fn normalize_candidates(candidates: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let candidate: String = candidate.nfc().collect();
+        if !normalized.contains(&candidate) {
+            normalized.push(candidate);
+        }
+    }
+    normalized
 }
 
-fn invoke_python_stt_file(audio_path: &str, language: &str) -> AppResult<Value> {
+fn fallback_transliteration(text: &str, reason: String) -> Transliteration {
+    Transliteration {
+        candidates: vec![text.to_string()],
+        status: TransliterationStatus::Fallback,
+        notes: vec![format!(
+            "Transliteration engine unavailable; showing the original input ({reason})"
+        )],
+    }
+}
+
+fn invoke_python_stt_file(
+    audio_path: &str,
+    language: &str,
+    start_secs: Option<f64>,
+    end_secs: Option<f64>,
+    diarize: bool,
+) -> AppResult<Value> {
     let ml_root = locate_ml_root()?;
     let python = std::env::var("SCRIPTWRITER_PYTHON").unwrap_or_else(|_| "python3".to_string());
 
-    let output = Command::new(python)
+    let mut range_args = Vec::new();
+    if let Some(start) = start_secs {
+        range_args.extend(["--start".to_string(), start.to_string()]);
+    }
+    if let Some(end) = end_secs {
+        range_args.extend(["--end".to_string(), end.to_string()]);
+    }
+    if diarize {
+        range_args.push("--diarize".to_string());
+    }
+
+    let mut command = Command::new(python);
+    command
         .arg("-m")
         .arg("scriptwriter_ml.cli")
         .arg("transcribe-file")
         .arg(audio_path)
         .arg("--language")
         .arg(language)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .env("PYTHONPATH", &ml_root)
-        .output()
-        .map_err(|err| AppError::Anyhow(err.into()))?;
+        .args(&range_args)
+        .env("PYTHONPATH", &ml_root);
+    let output = output_tracked(&mut command, None)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -242,23 +1213,37 @@ fn invoke_python_stt_file(audio_path: &str, language: &str) -> AppResult<Value>
     Ok(result)
 }
 
-fn invoke_python_stt_mic(duration: i32, language: &str) -> AppResult<Value> {
+fn invoke_python_stt_mic(
+    duration: i32,
+    language: &str,
+    device_id: Option<u32>,
+    sample_rate: Option<u32>,
+    output: Option<&Path>,
+) -> AppResult<Value> {
     let ml_root = locate_ml_root()?;
     let python = std::env::var("SCRIPTWRITER_PYTHON").unwrap_or_else(|_| "python3".to_string());
 
-    let output = Command::new(python)
+    let mut command = Command::new(python);
+    command
         .arg("-m")
         .arg("scriptwriter_ml.cli")
         .arg("transcribe-mic")
         .arg("--duration")
         .arg(duration.to_string())
         .arg("--language")
-        .arg(language)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .env("PYTHONPATH", &ml_root)
-        .output()
-        .map_err(|err| AppError::Anyhow(err.into()))?;
+        .arg(language);
+    if let Some(device_id) = device_id {
+        command.arg("--device").arg(device_id.to_string());
+    }
+    if let Some(sample_rate) = sample_rate {
+        command.arg("--sample-rate").arg(sample_rate.to_string());
+    }
+    if let Some(output) = output {
+        command.arg("--output").arg(output);
+    }
+
+    command.env("PYTHONPATH", &ml_root);
+    let output = output_tracked(&mut command, None)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -284,19 +1269,9 @@ fn invoke_python_tts(text: &str, output_path: Option<&str>) -> AppResult<Value>
         command.arg("--output").arg(output);
     }
     
-    command
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .env("PYTHONPATH", &ml_root);
-
-    let mut child = command.spawn().map_err(|err| AppError::Anyhow(err.into()))?;
-
-    if let Some(stdin) = child.stdin.as_mut() {
-        stdin.write_all(text.as_bytes())?;
-    }
+    command.env("PYTHONPATH", &ml_root);
 
-    let output = child.wait_with_output()?;
+    let output = output_tracked(&mut command, Some(text.as_bytes()))?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(AppError::Message(format!("Python TTS failed: {stderr}")));
@@ -306,53 +1281,138 @@ fn invoke_python_tts(text: &str, output_path: Option<&str>) -> AppResult<Value>
     Ok(result)
 }
 
-fn invoke_python_llm(prompt: &str, context: &str, api_key: Option<&str>) -> AppResult<Value> {
+fn invoke_python_llm<F>(
+    prompt: &str,
+    context: &str,
+    api_key: Option<&str>,
+    variations: u8,
+    local_model_path: Option<&str>,
+    system_prompt: Option<&str>,
+    mut on_variation: F,
+) -> AppResult<Value>
+where
+    F: FnMut(&Value),
+{
     let ml_root = locate_ml_root()?;
     let python = std::env::var("SCRIPTWRITER_PYTHON").unwrap_or_else(|_| "python3".to_string());
 
-    // Use Python inline script to call LLM
-    let api_key_str = api_key.unwrap_or("");
+    // Use Python inline script to call LLM, streaming one JSON line per variation
     let python_script = format!(
         r#"
 import json
-import os
-from scriptwriter_ml.llm import draft_scene
+from scriptwriter_ml.llm import draft_scene_variations, is_out_of_memory
 
-if "{}":
-    os.environ["OPENROUTER_API_KEY"] = "{}"
+def report(index, total, result):
+    print(json.dumps({{
+        "event": "variation",
+        "index": index,
+        "total": total,
+        "response": result.response,
+        "model_id": result.model_id,
+        "error": result.error
+    }}, ensure_ascii=False), flush=True)
 
-result = draft_scene(
+results = draft_scene_variations(
     prompt={},
-    context={}
+    context={},
+    variations={},
+    on_variation=report,
+    local_model_path={},
+    system_prompt={}
 )
+first = results[0]
 
 print(json.dumps({{
-    "prompt": result.prompt,
-    "response": result.response,
-    "model_id": result.model_id,
-    "error": result.error
+    "prompt": first.prompt,
+    "response": first.response,
+    "model_id": first.model_id,
+    "error": first.error,
+    "out_of_memory": is_out_of_memory(first.error),
+    "variations": [
+        {{"response": r.response, "model_id": r.model_id, "error": r.error}}
+        for r in results
+    ]
 }}, ensure_ascii=False))
-"#,
-        api_key_str.replace("\"", "\\\""),
-        api_key_str.replace("\"", "\\\""),
+        "#,
         serde_json::to_string(prompt)?,
-        serde_json::to_string(context)?
+        serde_json::to_string(context)?,
+        variations,
+        local_model_path
+            .map(serde_json::to_string)
+            .transpose()?
+            .unwrap_or_else(|| "None".to_string()),
+        system_prompt
+            .map(serde_json::to_string)
+            .transpose()?
+            .unwrap_or_else(|| "None".to_string())
     );
 
-    let output = Command::new(python)
+    let mut command = Command::new(python);
+    command
         .arg("-c")
         .arg(&python_script)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .env("PYTHONPATH", &ml_root)
-        .output()
-        .map_err(|err| AppError::Anyhow(err.into()))?;
+        .env("PYTHONPATH", &ml_root);
+    if let Some(key) = api_key.filter(|key| !key.is_empty()) {
+        command.env("OPENROUTER_API_KEY", key);
+    }
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    let (key, tracked) = spawn_tracked(&mut command)?;
+    let (stdout, stderr) = take_output_pipes(&tracked)?;
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buffer = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut buffer);
+        }
+        buffer
+    });
+
+    let mut final_payload = None;
+    if let Some(stdout) = stdout {
+        for line in BufReader::new(stdout).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Value>(&line) {
+                Ok(event) if event.get("event").and_then(Value::as_str) == Some("variation") => {
+                    on_variation(&event)
+                }
+                Ok(event) => final_payload = Some(event),
+                Err(err) => warn!("Ignoring malformed LLM output: {err}"),
+            }
+        }
+    }
+
+    let status = wait_tracked(&tracked);
+    untrack_child(&key);
+    let stderr = stderr_reader.join().unwrap_or_default();
+    let status = status?;
+    if !status.success() {
         return Err(AppError::Message(format!("Python LLM failed: {stderr}")));
     }
 
-    let result: Value = serde_json::from_slice(&output.stdout)?;
-    Ok(result)
+    final_payload.ok_or_else(|| AppError::Message("Python LLM returned no result".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_are_nfc_and_deduplicated() {
+        // "கொ" with ொ written as ெ + ா (U+0BC6 U+0BBE), and precomposed.
+        let decomposed = "\u{0B95}\u{0BC6}\u{0BBE}டு".to_string();
+        let composed = "\u{0B95}\u{0BCA}டு".to_string();
+        let candidates =
+            normalize_candidates(vec![decomposed, composed.clone(), "கோடு".to_string()]);
+        assert_eq!(candidates, vec![composed, "கோடு".to_string()]);
+        assert!(candidates
+            .iter()
+            .all(|candidate| !candidate.contains('\u{0BC6}')));
+    }
 }
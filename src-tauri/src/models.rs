@@ -3,6 +3,8 @@ use serde_json::{json, Value};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use crate::language::SUPPORTED_LANGUAGES;
+
 const FEATURE_FILM_DEFAULT: &str = r"# Feature Film Script
 
 ## Act I
@@ -76,6 +78,108 @@ pub struct ProjectTemplate {
     pub metadata: serde_json::Value,
 }
 
+/// A named part of a template (an act, section or segment) with the beats it
+/// is expected to contain, if the template lists any.
+#[derive(Clone, Debug, Serialize)]
+pub struct TemplateSection {
+    pub name: String,
+    pub beats: Vec<String>,
+}
+
+impl ProjectTemplate {
+    /// The template's acts, or failing that its outline, sections or segments.
+    pub fn structure(&self) -> Vec<TemplateSection> {
+        if let Some(acts) = self.metadata.get("acts").and_then(Value::as_array) {
+            return acts
+                .iter()
+                .map(|act| TemplateSection {
+                    name: act
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("Act")
+                        .to_string(),
+                    beats: act
+                        .get("beats")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect(),
+                })
+                .collect();
+        }
+
+        ["outline", "sections", "segments"]
+            .iter()
+            .find_map(|key| self.metadata.get(*key).and_then(Value::as_array))
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|name| TemplateSection {
+                name: name.to_string(),
+                beats: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Markdown describing the template's structure, derived from its metadata.
+    pub fn readme_body(&self) -> String {
+        let mut body = format!("{}.\n", self.description);
+
+        let languages = self
+            .languages
+            .iter()
+            .map(|code| {
+                SUPPORTED_LANGUAGES
+                    .iter()
+                    .find(|(known, _)| known == code)
+                    .map(|(_, name)| *name)
+                    .unwrap_or(code)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        body.push_str(&format!("\n**Languages:** {languages}\n"));
+
+        if let Some(acts) = self.metadata.get("acts").and_then(Value::as_array) {
+            body.push_str("\n## Structure\n");
+            for act in acts {
+                let name = act.get("name").and_then(Value::as_str).unwrap_or("Act");
+                body.push_str(&format!("\n### {name}\n"));
+                for beat in act
+                    .get("beats")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                {
+                    body.push_str(&format!("- {beat}\n"));
+                }
+            }
+        }
+
+        for (key, title) in [
+            ("outline", "Outline"),
+            ("sections", "Sections"),
+            ("segments", "Segments"),
+        ] {
+            if let Some(items) = self.metadata.get(key).and_then(Value::as_array) {
+                body.push_str(&format!("\n## {title}\n\n"));
+                for item in items.iter().filter_map(Value::as_str) {
+                    body.push_str(&format!("- {item}\n"));
+                }
+            }
+        }
+
+        body.push_str("\n## Folders\n\n");
+        for dir in self.directory_structure {
+            body.push_str(&format!("- `{dir}`\n"));
+        }
+
+        body
+    }
+}
+
 pub fn default_templates() -> Vec<ProjectTemplate> {
     vec![
         ProjectTemplate {
@@ -159,7 +263,7 @@ pub struct ModelOption {
     pub requires_gpu: bool,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ModelType {
     SpeechToText,
@@ -243,6 +347,42 @@ pub fn default_models() -> Vec<ModelOption> {
     ]
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct ModelAvailability {
+    #[serde(flatten)]
+    pub option: ModelOption,
+    pub installed: bool,
+    pub path: Option<String>,
+}
+
+/// Settings may name a model outside the catalogue with this prefix (for
+/// example `custom:/models/my-llm.gguf`); such ids are stored unchecked.
+pub const CUSTOM_MODEL_PREFIX: &str = "custom:";
+
+pub fn merge_model_inventory(inventory: &[Value]) -> Vec<ModelAvailability> {
+    default_models()
+        .into_iter()
+        .map(|option| {
+            let entry = inventory
+                .iter()
+                .find(|item| item.get("identifier").and_then(Value::as_str) == Some(option.id));
+            let installed = entry
+                .and_then(|item| item.get("downloaded"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            let path = entry
+                .and_then(|item| item.get("path"))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            ModelAvailability {
+                option,
+                installed,
+                path,
+            }
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProjectRecord {
     pub id: String,
@@ -253,6 +393,9 @@ pub struct ProjectRecord {
     pub description: Option<String>,
     pub template_id: Option<String>,
     pub base_path: String,
+    pub archived: bool,
+    pub locked: bool,
+    pub cover_path: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -271,6 +414,92 @@ pub struct SettingsPayload {
     pub tts_model: String,
     pub llm_model: String,
     pub api_keys: Value,
+    pub default_languages: Vec<String>,
+    pub default_template_id: String,
+    pub normalize_unicode: bool,
+    pub llm_fallback: bool,
+    pub max_file_size_bytes: u64,
+    pub audit_log_enabled: bool,
+    pub autosave_interval_secs: u32,
+    pub snapshot_retention: u32,
+    pub reading_wpm_english: u32,
+    pub reading_wpm_tamil: u32,
+    pub max_concurrent_ml_jobs: u32,
+}
+
+/// Largest draft `save_markdown_file` accepts unless the user raises it.
+pub const DEFAULT_MAX_FILE_SIZE_BYTES: u64 = 5 * 1024 * 1024;
+/// Floor for the limit so a typo cannot make every draft unsaveable.
+pub const MIN_MAX_FILE_SIZE_BYTES: u64 = 64 * 1024;
+
+pub const DEFAULT_AUTOSAVE_INTERVAL_SECS: u32 = 30;
+/// Faster than this hammers the disk; slower risks losing real work.
+pub const AUTOSAVE_INTERVAL_RANGE: std::ops::RangeInclusive<u32> = 5..=3600;
+pub const DEFAULT_SNAPSHOT_RETENTION: u32 = 20;
+/// Snapshots kept per file; at least one so clearing a draft stays undoable.
+pub const SNAPSHOT_RETENTION_RANGE: std::ops::RangeInclusive<u32> = 1..=500;
+pub const DEFAULT_READING_WPM_ENGLISH: u32 = 200;
+/// Tamil words are longer and read aloud more slowly than English ones.
+pub const DEFAULT_READING_WPM_TAMIL: u32 = 140;
+pub const READING_WPM_RANGE: std::ops::RangeInclusive<u32> = 50..=600;
+pub const DEFAULT_MAX_CONCURRENT_ML_JOBS: u32 = 2;
+/// Each model process can take gigabytes of RAM, so the ceiling stays low.
+pub const ML_JOB_LIMIT_RANGE: std::ops::RangeInclusive<u32> = 1..=8;
+
+impl SettingsPayload {
+    pub fn redacted(mut self) -> Self {
+        if let Value::Object(keys) = &mut self.api_keys {
+            for value in keys.values_mut() {
+                if let Value::String(secret) = value {
+                    *secret = mask_secret(secret);
+                }
+            }
+        }
+        self
+    }
+}
+
+pub fn mask_secret(secret: &str) -> String {
+    let chars = secret.chars().collect::<Vec<_>>();
+    if chars.is_empty() {
+        return String::new();
+    }
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    let prefix = chars[..3].iter().collect::<String>();
+    let suffix = chars[chars.len() - 4..].iter().collect::<String>();
+    format!("{prefix}...{suffix}")
+}
+
+/// Keeps stored secrets for any key the client sent back in masked form.
+pub fn merge_api_keys(stored: &Value, incoming: Value) -> Value {
+    let Value::Object(mut incoming) = incoming else {
+        return incoming;
+    };
+    for (name, value) in incoming.iter_mut() {
+        let Some(existing) = stored.get(name).and_then(Value::as_str) else {
+            continue;
+        };
+        if value.as_str() == Some(mask_secret(existing).as_str()) {
+            *value = Value::String(existing.to_string());
+        }
+    }
+    Value::Object(incoming)
+}
+
+pub fn resolve_api_key(stored: &Value, name: &str, provided: Option<String>) -> Option<String> {
+    let existing = stored
+        .get(name)
+        .and_then(Value::as_str)
+        .filter(|key| !key.is_empty());
+    match provided.filter(|key| !key.is_empty()) {
+        Some(key) if existing.is_some_and(|secret| mask_secret(secret) == key) => {
+            existing.map(str::to_string)
+        }
+        Some(key) => Some(key),
+        None => existing.map(str::to_string),
+    }
 }
 
 #[derive(Debug, FromRow)]
@@ -281,6 +510,17 @@ pub struct SettingsRow {
     pub tts_model: String,
     pub llm_model: String,
     pub api_keys: Option<String>,
+    pub default_languages: String,
+    pub default_template_id: String,
+    pub normalize_unicode: bool,
+    pub llm_fallback: bool,
+    pub max_file_size_bytes: i64,
+    pub audit_log_enabled: bool,
+    pub autosave_interval_secs: i64,
+    pub snapshot_retention: i64,
+    pub reading_wpm_english: i64,
+    pub reading_wpm_tamil: i64,
+    pub max_concurrent_ml_jobs: i64,
 }
 
 impl TryFrom<SettingsRow> for SettingsPayload {
@@ -292,6 +532,7 @@ impl TryFrom<SettingsRow> for SettingsPayload {
         } else {
             Value::Object(Default::default())
         };
+        let default_languages: Vec<String> = serde_json::from_str(&value.default_languages)?;
         Ok(SettingsPayload {
             preferred_theme: value.preferred_theme,
             transliteration_mode: value.transliteration_mode,
@@ -299,6 +540,23 @@ impl TryFrom<SettingsRow> for SettingsPayload {
             tts_model: value.tts_model,
             llm_model: value.llm_model,
             api_keys,
+            default_languages,
+            default_template_id: value.default_template_id,
+            normalize_unicode: value.normalize_unicode,
+            llm_fallback: value.llm_fallback,
+            max_file_size_bytes: u64::try_from(value.max_file_size_bytes)
+                .unwrap_or(DEFAULT_MAX_FILE_SIZE_BYTES),
+            audit_log_enabled: value.audit_log_enabled,
+            autosave_interval_secs: u32::try_from(value.autosave_interval_secs)
+                .unwrap_or(DEFAULT_AUTOSAVE_INTERVAL_SECS),
+            snapshot_retention: u32::try_from(value.snapshot_retention)
+                .unwrap_or(DEFAULT_SNAPSHOT_RETENTION),
+            reading_wpm_english: u32::try_from(value.reading_wpm_english)
+                .unwrap_or(DEFAULT_READING_WPM_ENGLISH),
+            reading_wpm_tamil: u32::try_from(value.reading_wpm_tamil)
+                .unwrap_or(DEFAULT_READING_WPM_TAMIL),
+            max_concurrent_ml_jobs: u32::try_from(value.max_concurrent_ml_jobs)
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_ML_JOBS),
         })
     }
 }
@@ -313,6 +571,9 @@ pub struct ProjectRow {
     pub description: Option<String>,
     pub template_id: Option<String>,
     pub base_path: String,
+    pub archived: bool,
+    pub locked: bool,
+    pub cover_path: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -331,6 +592,9 @@ impl TryFrom<ProjectRow> for ProjectRecord {
             description: value.description,
             template_id: value.template_id,
             base_path: value.base_path,
+            archived: value.archived,
+            locked: value.locked,
+            cover_path: value.cover_path,
             created_at: value.created_at,
             updated_at: value.updated_at,
         })
@@ -345,6 +609,64 @@ pub struct UserProfile {
     pub created_at: String,
 }
 
+/// One mutating command as recorded in the audit log. Only metadata is
+/// kept: file contents, API keys and other payload values never reach it.
+#[derive(Debug, Serialize, FromRow)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub user_id: Option<String>,
+    pub user_email: Option<String>,
+    pub project_id: Option<String>,
+    pub command: String,
+    pub detail: Option<String>,
+    pub created_at: String,
+}
+
+/// A past scene generation. `context` keeps only the tail of what was sent;
+/// `context_length` is the full length in characters.
+#[derive(Debug, Serialize, FromRow)]
+pub struct AiHistoryEntry {
+    pub id: i64,
+    pub project_id: String,
+    pub prompt: String,
+    pub context: String,
+    pub context_length: i64,
+    pub response: String,
+    pub model_id: Option<String>,
+    pub created_at: String,
+}
+
+/// A writing reminder, for one project or global when `project_id` is unset.
+/// Times are UTC in SQLite's `YYYY-MM-DD HH:MM:SS` form.
+#[derive(Debug, Serialize, FromRow)]
+pub struct Reminder {
+    pub id: i64,
+    pub project_id: Option<String>,
+    pub message: String,
+    pub due_at: String,
+    pub fired_at: Option<String>,
+    pub created_at: String,
+}
+
+/// A named system prompt that keeps AI generations in one voice.
+#[derive(Debug, Serialize, FromRow)]
+pub struct Persona {
+    pub id: i64,
+    pub name: String,
+    pub system_prompt: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A user's preferred Tamil output for a Latin input, keyed on the input in
+/// lower case.
+#[derive(Debug, Serialize, FromRow)]
+pub struct TransliterationCorrection {
+    pub input: String,
+    pub output: String,
+    pub updated_at: String,
+}
+
 #[derive(Debug, FromRow)]
 pub struct UserRow {
     pub id: String,
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppResult;
+use crate::models::Reminder;
+
+/// How often pending reminders are checked; a reminder may fire up to this
+/// late.
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Marks every reminder whose time has passed as fired and returns them.
+async fn take_due(pool: &SqlitePool) -> AppResult<Vec<Reminder>> {
+    let due = sqlx::query_as::<_, Reminder>(
+        r#"
+      UPDATE reminders
+      SET fired_at = datetime('now')
+      WHERE fired_at IS NULL AND due_at <= datetime('now')
+      RETURNING id, project_id, message, due_at, fired_at, created_at
+    "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(due)
+}
+
+/// Spawns the task that emits `reminder-due` while the app runs. Reminders
+/// that came due while the app was closed fire on the first check.
+pub fn start(app: AppHandle, pool: SqlitePool) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if pool.is_closed() {
+                break;
+            }
+            let due = match take_due(&pool).await {
+                Ok(due) => due,
+                Err(err) => {
+                    log::warn!("Failed to check reminders: {err}");
+                    continue;
+                }
+            };
+            for reminder in due {
+                if let Err(err) = app.emit("reminder-due", &reminder) {
+                    log::warn!("Failed to emit reminder {}: {err}", reminder.id);
+                }
+            }
+        }
+    });
+}
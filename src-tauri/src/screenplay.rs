@@ -0,0 +1,1091 @@
+use serde::Serialize;
+
+use crate::markdown::{dialogue_speaker, set_front_matter_value, split_front_matter};
+
+/// Extra indentation, in columns beyond the action margin, that marks a line
+/// as a character cue in a typeset script.
+const CUE_INDENT: usize = 10;
+/// Cues longer than this are treated as shouted action instead.
+const MAX_CUE_WORDS: usize = 4;
+
+const SCENE_PREFIXES: [&str; 7] = [
+    "INT./EXT", "EXT./INT", "INT/EXT", "I/E", "INT", "EXT", "EST",
+];
+
+/// What the importer recognised, so writers know how much to double-check.
+#[derive(Debug, Default, Serialize)]
+pub struct StructureReport {
+    pub scene_headings: usize,
+    pub character_cues: usize,
+    /// Distinct cue names in order of first appearance.
+    pub characters: Vec<String>,
+    pub dialogue_lines: usize,
+    pub parentheticals: usize,
+    pub transitions: usize,
+    pub action_lines: usize,
+    /// 1-based lines whose role was guessed from capitals alone.
+    pub uncertain_lines: Vec<usize>,
+    /// Share of non-blank lines placed on a strong signal, from 0 to 1.
+    pub confidence: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StructuredScript {
+    pub markdown: String,
+    pub report: StructureReport,
+}
+
+fn indentation(line: &str) -> usize {
+    line.chars()
+        .take_while(|ch| ch.is_whitespace())
+        .map(|ch| if ch == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn is_all_caps(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic) && !text.chars().any(char::is_lowercase)
+}
+
+/// Drops a leading or trailing scene number such as `12` or `12A.`.
+fn strip_scene_number(line: &str) -> &str {
+    let is_number = |token: &str| {
+        let token = token.trim_end_matches('.');
+        token.chars().next().is_some_and(|ch| ch.is_ascii_digit())
+            && token.len() <= 5
+            && token.chars().all(|ch| ch.is_ascii_alphanumeric())
+    };
+    let mut line = line.trim();
+    if let Some((first, rest)) = line.split_once(char::is_whitespace) {
+        if is_number(first) {
+            line = rest.trim_start();
+        }
+    }
+    if let Some((rest, last)) = line.rsplit_once(char::is_whitespace) {
+        if is_number(last) {
+            line = rest.trim_end();
+        }
+    }
+    line
+}
+
+fn has_scene_prefix(line: &str) -> bool {
+    let upper = line.to_uppercase();
+    SCENE_PREFIXES.iter().any(|prefix| {
+        upper
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(['.', ' ']))
+    })
+}
+
+fn scene_heading(line: &str) -> Option<String> {
+    let line = strip_scene_number(line);
+    has_scene_prefix(line).then(|| line.to_string())
+}
+
+fn is_transition(trimmed: &str) -> bool {
+    is_all_caps(trimmed)
+        && (trimmed.ends_with("TO:")
+            || trimmed.starts_with("FADE ")
+            || matches!(trimmed, "FADE IN:" | "FADE OUT." | "THE END" | "THE END."))
+}
+
+/// Escapes a leading character that markdown would read as block syntax, so
+/// pasted prose stays prose.
+fn escape_block_marker(text: &str) -> String {
+    let digits = text.len()
+        - text
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    if digits > 0 && text[digits..].starts_with(['.', ')']) {
+        format!("{}\\{}", &text[..digits], &text[digits..])
+    } else if text.starts_with(['#', '>', '-', '+', '*', '|', '=', '`', '~']) {
+        format!("\\{text}")
+    } else {
+        text.to_string()
+    }
+}
+
+/// The speaker of a cue line with any `(V.O.)`-style extension removed.
+fn cue_name(trimmed: &str) -> &str {
+    trimmed.split('(').next().unwrap_or(trimmed).trim()
+}
+
+/// Turns a pasted plain-text screenplay into markdown: scene headings become
+/// `##` headings, cues stay on their own line with dialogue directly below,
+/// and transitions are set in italics so they are not mistaken for speakers.
+pub fn structure_plaintext(title: &str, raw: &str) -> StructuredScript {
+    let raw = raw.trim_start_matches('\u{feff}');
+    let lines: Vec<&str> = raw.lines().map(|line| line.trim_end()).collect();
+    // Action sits at the left margin; cues and dialogue are indented from it.
+    let margin = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indentation(line))
+        .min()
+        .unwrap_or(0);
+
+    let mut report = StructureReport::default();
+    let mut blocks: Vec<String> = Vec::new();
+    let mut strong = 0usize;
+    let mut index = 0;
+    // Whether the previous line was action in the same paragraph.
+    let mut in_action = false;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim();
+        let line_number = index + 1;
+        index += 1;
+        if trimmed.is_empty() {
+            in_action = false;
+            continue;
+        }
+
+        if let Some(heading) = scene_heading(trimmed) {
+            in_action = false;
+            report.scene_headings += 1;
+            strong += 1;
+            blocks.push(format!("## {heading}"));
+            continue;
+        }
+        if is_transition(trimmed) {
+            in_action = false;
+            report.transitions += 1;
+            strong += 1;
+            blocks.push(format!("*{trimmed}*"));
+            continue;
+        }
+
+        let name = cue_name(trimmed);
+        let followed = lines.get(index).is_some_and(|next| !next.trim().is_empty());
+        let short = (1..=MAX_CUE_WORDS).contains(&name.split_whitespace().count());
+        let indented = indentation(line) >= margin + CUE_INDENT;
+        let extended = trimmed.ends_with(')') && trimmed.contains('(');
+        let is_cue = followed
+            && short
+            && !trimmed.ends_with([':', '.', '!', '?'])
+            && (is_all_caps(name) || indented);
+
+        if !is_cue {
+            report.action_lines += 1;
+            if is_all_caps(trimmed) && short {
+                report.uncertain_lines.push(line_number);
+            } else {
+                strong += 1;
+            }
+            // Keep wrapped action lines together as one paragraph.
+            match blocks.last_mut().filter(|_| in_action) {
+                Some(block) => {
+                    block.push(' ');
+                    block.push_str(trimmed);
+                }
+                None => blocks.push(escape_block_marker(trimmed)),
+            }
+            in_action = true;
+            continue;
+        }
+
+        in_action = false;
+        report.character_cues += 1;
+        if indented || extended {
+            strong += 1;
+        } else {
+            report.uncertain_lines.push(line_number);
+        }
+        if !report
+            .characters
+            .iter()
+            .any(|known| known.to_lowercase() == name.to_lowercase())
+        {
+            report.characters.push(name.to_string());
+        }
+
+        let mut block = vec![trimmed.to_uppercase()];
+        while let Some(next) = lines.get(index).map(|next| next.trim()) {
+            if next.is_empty() {
+                break;
+            }
+            if next.starts_with('(') {
+                report.parentheticals += 1;
+            } else {
+                report.dialogue_lines += 1;
+            }
+            strong += 1;
+            block.push(escape_block_marker(next));
+            index += 1;
+        }
+        blocks.push(block.join("\n"));
+    }
+
+    let classified = strong + report.uncertain_lines.len();
+    report.confidence = if classified == 0 {
+        0.0
+    } else {
+        (strong as f32 / classified as f32 * 100.0).round() / 100.0
+    };
+
+    let mut markdown = format!("# {title}\n");
+    for block in blocks {
+        markdown.push('\n');
+        markdown.push_str(&block);
+        markdown.push('\n');
+    }
+    StructuredScript { markdown, report }
+}
+
+/// Title page keys Fountain defines. Drafts keep them as front matter.
+const TITLE_PAGE_KEYS: [&str; 10] = [
+    "title",
+    "credit",
+    "author",
+    "authors",
+    "source",
+    "draft_date",
+    "contact",
+    "copyright",
+    "revision",
+    "notes",
+];
+
+/// Something with no counterpart in the other format.
+#[derive(Debug, Serialize)]
+pub struct UnmappedElement {
+    /// 1-based line in the source document.
+    pub line: usize,
+    pub kind: &'static str,
+    pub text: String,
+}
+
+fn unmapped(line: usize, kind: &'static str, text: &str) -> UnmappedElement {
+    UnmappedElement {
+        line,
+        kind,
+        text: text.to_string(),
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct FountainReport {
+    pub scene_headings: usize,
+    pub character_cues: usize,
+    /// Distinct cue names in order of first appearance.
+    pub characters: Vec<String>,
+    pub dialogue_lines: usize,
+    pub parentheticals: usize,
+    pub transitions: usize,
+    pub action_lines: usize,
+    /// `[[notes]]`, kept as `<!-- note: ... -->` comments.
+    pub notes: usize,
+    /// `/* boneyard */` text, kept as HTML comments.
+    pub boneyard: usize,
+    /// Cues marked `^`; their dialogue follows the first speaker's instead of
+    /// sitting beside it.
+    pub dual_dialogue: usize,
+    /// Sections, synopses, scene numbers, centered text, lyrics and dual
+    /// dialogue, which markdown has no place for.
+    pub unmapped: Vec<UnmappedElement>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FountainImport {
+    pub markdown: String,
+    pub report: FountainReport,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FountainExport {
+    pub fountain: String,
+    pub unmapped: Vec<UnmappedElement>,
+}
+
+fn html_comment(text: &str) -> String {
+    format!("<!-- {} -->", text.trim().replace("--", "- -"))
+}
+
+/// Fountain reads a line as a cue only when it has capitals and no lowercase,
+/// so Tamil names need forcing with `@`.
+fn is_upper_case(text: &str) -> bool {
+    text.chars().any(char::is_uppercase) && !text.chars().any(char::is_lowercase)
+}
+
+/// A line of a Fountain body with its notes and boneyard lifted out.
+struct FountainLine {
+    text: String,
+    number: usize,
+    comments: Vec<String>,
+}
+
+impl FountainLine {
+    fn new(number: usize) -> Self {
+        Self {
+            text: String::new(),
+            number,
+            comments: Vec::new(),
+        }
+    }
+
+    /// A line left empty by a removed note does not end a paragraph.
+    fn is_blank(&self) -> bool {
+        self.text.trim().is_empty() && self.comments.is_empty()
+    }
+}
+
+fn push_fountain_text(lines: &mut Vec<FountainLine>, number: &mut usize, text: &str) {
+    for (index, piece) in text.split('\n').enumerate() {
+        if index > 0 {
+            *number += 1;
+            lines.push(FountainLine::new(*number));
+        }
+        if let Some(line) = lines.last_mut() {
+            line.text.push_str(piece);
+        }
+    }
+}
+
+/// Splits the source into lines, turning `[[notes]]` and `/* boneyard */`
+/// into HTML comments attached to the line they start on.
+fn lift_comments(source: &str, report: &mut FountainReport) -> Vec<FountainLine> {
+    let mut lines = vec![FountainLine::new(1)];
+    let mut number = 1;
+    let mut rest = source;
+    loop {
+        let next = [("/*", "*/"), ("[[", "]]")]
+            .into_iter()
+            .filter_map(|(open, close)| rest.find(open).map(|start| (start, open, close)))
+            .min_by_key(|(start, ..)| *start);
+        let Some((start, open, close)) = next else {
+            push_fountain_text(&mut lines, &mut number, rest);
+            break;
+        };
+        push_fountain_text(&mut lines, &mut number, &rest[..start]);
+        let after = &rest[start + open.len()..];
+        let (inner, remaining) = match after.find(close) {
+            Some(end) => (&after[..end], &after[end + close.len()..]),
+            None => (after, ""),
+        };
+        let comment = if open == "[[" {
+            report.notes += 1;
+            html_comment(&format!("note: {}", inner.trim()))
+        } else {
+            report.boneyard += 1;
+            html_comment(inner)
+        };
+        if let Some(line) = lines.last_mut() {
+            line.comments.push(comment);
+        }
+        number += inner.matches('\n').count();
+        rest = remaining;
+    }
+    lines
+}
+
+fn title_page_key(line: &str) -> Option<(String, &str)> {
+    if line.starts_with([' ', '\t']) {
+        return None;
+    }
+    let (key, value) = line.split_once(':')?;
+    let key = key.trim();
+    (!key.is_empty() && key.chars().all(|ch| ch.is_ascii_alphabetic() || ch == ' '))
+        .then(|| (key.to_lowercase().replace(' ', "_"), value))
+}
+
+/// Reads a leading `Key: value` title page, returning its fields and the
+/// index of the first body line. Only a known key can open the page, so a
+/// script starting with `FADE IN:` has none.
+fn fountain_title_page(lines: &[FountainLine]) -> (Vec<(String, String)>, usize) {
+    let opens = lines
+        .first()
+        .and_then(|line| title_page_key(&line.text))
+        .is_some_and(|(key, _)| TITLE_PAGE_KEYS.contains(&key.as_str()));
+    if !opens {
+        return (Vec::new(), 0);
+    }
+
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.text.trim().is_empty() {
+            return (fields, index + 1);
+        }
+        let plain = |text: &str| text.trim().trim_matches(['*', '_']).trim().to_string();
+        if let Some((key, value)) = title_page_key(&line.text) {
+            fields.push((key, plain(value)));
+        } else if let Some((key, value)) = fields.last_mut() {
+            let joiner = if key == "title" { " " } else { ", " };
+            if !value.is_empty() {
+                value.push_str(joiner);
+            }
+            value.push_str(&plain(&line.text));
+        }
+    }
+    (fields, lines.len())
+}
+
+/// Splits a trailing `#12A#` scene number off a heading.
+fn split_scene_number(heading: &str) -> (&str, Option<&str>) {
+    heading
+        .strip_suffix('#')
+        .and_then(|rest| rest.rsplit_once('#'))
+        .filter(|(_, number)| !number.is_empty() && !number.contains(char::is_whitespace))
+        .map(|(heading, number)| (heading.trim_end(), Some(number)))
+        .unwrap_or((heading, None))
+}
+
+enum FountainElement<'a> {
+    PageBreak,
+    Synopsis(&'a str),
+    Section(&'a str),
+    SceneHeading(&'a str),
+    Centered(&'a str),
+    Transition(&'a str),
+    Cue(&'a str),
+    Action,
+}
+
+fn classify_fountain(trimmed: &str, after_blank: bool, next_blank: bool) -> FountainElement<'_> {
+    if trimmed.starts_with(['!', '~']) {
+        return FountainElement::Action;
+    }
+    if trimmed.len() >= 3 && trimmed.chars().all(|ch| ch == '=') {
+        return FountainElement::PageBreak;
+    }
+    if let Some(synopsis) = trimmed.strip_prefix('=') {
+        return FountainElement::Synopsis(synopsis.trim());
+    }
+    if trimmed.starts_with('#') {
+        return FountainElement::Section(trimmed.trim_start_matches('#').trim());
+    }
+    if let Some(heading) = trimmed
+        .strip_prefix('.')
+        .filter(|rest| rest.starts_with(|ch: char| ch.is_alphanumeric()))
+    {
+        return FountainElement::SceneHeading(heading);
+    }
+    if after_blank && has_scene_prefix(trimmed) {
+        return FountainElement::SceneHeading(trimmed);
+    }
+    if let Some(rest) = trimmed.strip_prefix('>') {
+        return match rest.strip_suffix('<') {
+            Some(centered) => FountainElement::Centered(centered.trim()),
+            None => FountainElement::Transition(rest.trim()),
+        };
+    }
+    if after_blank && next_blank && is_upper_case(trimmed) && trimmed.ends_with("TO:") {
+        return FountainElement::Transition(trimmed);
+    }
+    if !next_blank {
+        if let Some(cue) = trimmed.strip_prefix('@') {
+            return FountainElement::Cue(cue.trim());
+        }
+        if after_blank && is_upper_case(cue_name(trimmed.trim_end_matches('^'))) {
+            return FountainElement::Cue(trimmed);
+        }
+    }
+    FountainElement::Action
+}
+
+/// Converts a Fountain screenplay to the markdown conventions of
+/// [`structure_plaintext`]. The title page becomes the `#` title and front
+/// matter; notes and boneyard survive as HTML comments.
+pub fn fountain_to_markdown(fallback_title: &str, source: &str) -> FountainImport {
+    let source = source.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut report = FountainReport::default();
+    let lines = lift_comments(&source, &mut report);
+    let (fields, body_start) = fountain_title_page(&lines);
+
+    let mut title = fallback_title.to_string();
+    let mut front_matter = Vec::new();
+    for (key, value) in fields {
+        if value.is_empty() {
+            continue;
+        }
+        if key == "title" {
+            title = value;
+        } else {
+            front_matter.push((key, value));
+        }
+    }
+
+    let mut blocks: Vec<String> = lines[..body_start]
+        .iter()
+        .flat_map(|line| line.comments.iter().cloned())
+        .collect();
+    let mut index = body_start;
+    let mut after_blank = true;
+    while index < lines.len() {
+        let line = &lines[index];
+        let trimmed = line.text.trim();
+        index += 1;
+        if trimmed.is_empty() {
+            blocks.extend(line.comments.iter().cloned());
+            after_blank = after_blank || line.comments.is_empty();
+            continue;
+        }
+
+        let next_blank = match lines.get(index) {
+            Some(next) => next.text.trim().is_empty(),
+            None => true,
+        };
+        let mut comments = line.comments.clone();
+        match classify_fountain(trimmed, after_blank, next_blank) {
+            FountainElement::PageBreak => blocks.push("---".to_string()),
+            FountainElement::Synopsis(synopsis) => {
+                report
+                    .unmapped
+                    .push(unmapped(line.number, "synopsis", trimmed));
+                blocks.push(html_comment(&format!("synopsis: {synopsis}")));
+            }
+            FountainElement::Section(section) => {
+                report
+                    .unmapped
+                    .push(unmapped(line.number, "section", trimmed));
+                blocks.push(html_comment(&format!("section: {section}")));
+            }
+            FountainElement::SceneHeading(heading) => {
+                let (heading, number) = split_scene_number(heading);
+                if let Some(number) = number {
+                    report
+                        .unmapped
+                        .push(unmapped(line.number, "scene_number", number));
+                }
+                report.scene_headings += 1;
+                blocks.push(format!("## {heading}"));
+            }
+            FountainElement::Centered(centered) => {
+                report
+                    .unmapped
+                    .push(unmapped(line.number, "centered", trimmed));
+                report.action_lines += 1;
+                blocks.push(escape_block_marker(centered));
+            }
+            FountainElement::Transition(transition) => {
+                report.transitions += 1;
+                blocks.push(format!("*{transition}*"));
+            }
+            FountainElement::Cue(cue) => {
+                let dual = cue.ends_with('^');
+                let cue = cue.trim_end_matches('^').trim_end().to_uppercase();
+                if dual {
+                    report.dual_dialogue += 1;
+                    report
+                        .unmapped
+                        .push(unmapped(line.number, "dual_dialogue", trimmed));
+                }
+                report.character_cues += 1;
+                let name = cue_name(&cue);
+                if !report
+                    .characters
+                    .iter()
+                    .any(|known| known.to_lowercase() == name.to_lowercase())
+                {
+                    report.characters.push(name.to_string());
+                }
+
+                let mut block = vec![cue.clone()];
+                while let Some(next) = lines.get(index).filter(|next| !next.is_blank()) {
+                    index += 1;
+                    comments.extend(next.comments.iter().cloned());
+                    let text = next.text.trim();
+                    if text.is_empty() {
+                        continue;
+                    }
+                    if text.starts_with('(') {
+                        report.parentheticals += 1;
+                    } else {
+                        report.dialogue_lines += 1;
+                    }
+                    block.push(escape_block_marker(text));
+                }
+                blocks.push(block.join("\n"));
+            }
+            FountainElement::Action => {
+                let mut block = Vec::new();
+                let mut current = line;
+                loop {
+                    let text = current.text.trim();
+                    let text = match text.strip_prefix('~') {
+                        Some(lyric) => {
+                            report
+                                .unmapped
+                                .push(unmapped(current.number, "lyric", text));
+                            lyric.trim_start()
+                        }
+                        None => text.strip_prefix('!').unwrap_or(text),
+                    };
+                    if !text.is_empty() {
+                        report.action_lines += 1;
+                        block.push(escape_block_marker(text));
+                    }
+                    match lines.get(index).filter(|next| !next.is_blank()) {
+                        Some(next) => {
+                            index += 1;
+                            comments.extend(next.comments.iter().cloned());
+                            current = next;
+                        }
+                        None => break,
+                    }
+                }
+                if !block.is_empty() {
+                    blocks.push(block.join("\n"));
+                }
+            }
+        }
+        blocks.append(&mut comments);
+        after_blank = false;
+    }
+
+    let mut markdown = format!("# {title}\n");
+    for block in blocks {
+        markdown.push('\n');
+        markdown.push_str(&block);
+        markdown.push('\n');
+    }
+    for (key, value) in &front_matter {
+        markdown = set_front_matter_value(&markdown, key, Some(value));
+    }
+    FountainImport { markdown, report }
+}
+
+/// Reverses [`escape_block_marker`].
+fn unescape_block_marker(text: &str) -> String {
+    let digits = text.len()
+        - text
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    let rest = &text[digits..];
+    if digits > 0 && (rest.starts_with("\\.") || rest.starts_with("\\)")) {
+        format!("{}{}", &text[..digits], &rest[1..])
+    } else if digits == 0
+        && text.starts_with('\\')
+        && text[1..].starts_with(['#', '>', '-', '+', '*', '|', '=', '`', '~'])
+    {
+        text[1..].to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Rewrites HTML comments as Fountain notes, sections, synopses or boneyard.
+fn fountain_comments(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("<!--") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 4..];
+        let (inner, remaining) = match after.find("-->") {
+            Some(end) => (&after[..end], &after[end + 3..]),
+            None => (after, ""),
+        };
+        let inner = inner.trim();
+        if let Some(note) = inner.strip_prefix("note:") {
+            output.push_str(&format!("[[{}]]", note.trim()));
+        } else if let Some(section) = inner.strip_prefix("section:") {
+            output.push_str(&format!("# {}", section.trim()));
+        } else if let Some(synopsis) = inner.strip_prefix("synopsis:") {
+            output.push_str(&format!("= {}", synopsis.trim()));
+        } else {
+            output.push_str(&format!("/* {inner} */"));
+        }
+        rest = remaining;
+    }
+    output.push_str(rest);
+    output
+}
+
+fn ensure_blank(lines: &mut Vec<String>) {
+    if lines.last().is_some_and(|line| !line.is_empty()) {
+        lines.push(String::new());
+    }
+}
+
+fn title_page_label(key: &str) -> String {
+    let label = key.replace('_', " ");
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => label,
+    }
+}
+
+/// Writes a draft as Fountain, the reverse of [`fountain_to_markdown`]: `#`
+/// titles and title page front matter form the title page, other headings
+/// become scene headings and action that Fountain would misread is forced
+/// with `!`.
+pub fn markdown_to_fountain(content: &str) -> FountainExport {
+    let (fields, body_start) = split_front_matter(content);
+    let mut title_page: Vec<(String, String)> = fields
+        .into_iter()
+        .filter(|(key, value)| TITLE_PAGE_KEYS.contains(&key.as_str()) && !value.is_empty())
+        .collect();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut unmapped_elements = Vec::new();
+    let mut body: Vec<String> = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut index = body_start;
+
+    while index < lines.len() {
+        let number = index + 1;
+        let mut line = lines[index].to_string();
+        index += 1;
+        if let Some(marker) = fence {
+            if line.trim_start().starts_with(marker) {
+                fence = None;
+                body.push("*/".to_string());
+            } else {
+                body.push(line);
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| line.trim_start().starts_with(marker))
+        {
+            fence = Some(marker);
+            unmapped_elements.push(unmapped(number, "code_block", line.trim()));
+            ensure_blank(&mut body);
+            body.push("/*".to_string());
+            continue;
+        }
+        // Gather a comment that runs over several lines.
+        while line.rfind("<!--") > line.rfind("-->").map(|end| end + 1) {
+            let Some(next) = lines.get(index) else {
+                break;
+            };
+            line.push('\n');
+            line.push_str(next);
+            index += 1;
+        }
+        let whole_comment = {
+            let trimmed = line.trim();
+            trimmed.starts_with("<!--")
+                && trimmed.ends_with("-->")
+                && trimmed.matches("<!--").count() == 1
+        };
+        let converted = fountain_comments(&line);
+        let trimmed = converted.trim();
+        if whole_comment {
+            ensure_blank(&mut body);
+            body.push(trimmed.to_string());
+            ensure_blank(&mut body);
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            ensure_blank(&mut body);
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            let text = trimmed[level..].trim().trim_end_matches('#').trim_end();
+            let has_title = title_page.iter().any(|(key, _)| key == "title");
+            ensure_blank(&mut body);
+            if level == 1 && !has_title && body.is_empty() {
+                title_page.insert(0, ("title".to_string(), text.to_string()));
+                continue;
+            } else if level == 1 {
+                body.push(format!("# {text}"));
+            } else if has_scene_prefix(text) {
+                body.push(text.to_string());
+            } else {
+                body.push(format!(".{text}"));
+            }
+            body.push(String::new());
+            continue;
+        }
+
+        if trimmed.len() >= 3
+            && ['-', '*', '_']
+                .into_iter()
+                .any(|marker| trimmed.chars().all(|ch| ch == marker || ch == ' '))
+        {
+            ensure_blank(&mut body);
+            body.push("===".to_string());
+            body.push(String::new());
+            continue;
+        }
+
+        let italic = ['*', '_'].into_iter().find_map(|marker| {
+            trimmed
+                .strip_prefix(marker)
+                .and_then(|rest| rest.strip_suffix(marker))
+                .filter(|inner| !inner.starts_with(marker) && !inner.is_empty())
+        });
+        if let Some(transition) = italic.filter(|inner| is_all_caps(inner)) {
+            ensure_blank(&mut body);
+            if transition.ends_with("TO:") && is_upper_case(transition) {
+                body.push(transition.to_string());
+            } else {
+                body.push(format!("> {transition}"));
+            }
+            body.push(String::new());
+            continue;
+        }
+
+        if trimmed.starts_with("![") {
+            unmapped_elements.push(unmapped(number, "image", trimmed));
+            body.push(format!("[[{trimmed}]]"));
+            continue;
+        }
+
+        let next_blank = match lines.get(index) {
+            Some(next) => next.trim().is_empty(),
+            None => true,
+        };
+        if let Some(speaker) = dialogue_speaker(trimmed) {
+            let colon_form = trimmed.contains(':');
+            if colon_form || !next_blank {
+                // A cue on its own line keeps its `(V.O.)`-style extension.
+                let cue = if colon_form {
+                    speaker.to_uppercase()
+                } else {
+                    trimmed.to_string()
+                };
+                ensure_blank(&mut body);
+                if is_upper_case(&cue) {
+                    body.push(cue);
+                } else {
+                    body.push(format!("@{cue}"));
+                }
+                if colon_form {
+                    let dialogue = trimmed
+                        .split_once(':')
+                        .map(|(_, dialogue)| dialogue.trim())
+                        .unwrap_or_default();
+                    if !dialogue.is_empty() {
+                        body.push(unescape_block_marker(dialogue));
+                    }
+                }
+                while let Some(next) = lines.get(index).map(|next| next.trim()) {
+                    if next.is_empty() {
+                        break;
+                    }
+                    body.push(unescape_block_marker(&fountain_comments(next)));
+                    index += 1;
+                }
+                continue;
+            }
+        }
+
+        let text = match trimmed.strip_prefix('>') {
+            Some(quoted) => {
+                unmapped_elements.push(unmapped(number, "blockquote", trimmed));
+                quoted.trim_start()
+            }
+            None => trimmed,
+        };
+        let text = unescape_block_marker(text);
+        let misread = text.starts_with(['!', '@', '#', '~', '>', '=', '.'])
+            || has_scene_prefix(&text)
+            || is_all_caps(&text);
+        if misread {
+            body.push(format!("!{text}"));
+        } else {
+            body.push(text);
+        }
+    }
+    if fence.is_some() {
+        body.push("*/".to_string());
+    }
+
+    while body.last().is_some_and(|line| line.is_empty()) {
+        body.pop();
+    }
+    let mut fountain = String::new();
+    if !title_page.is_empty() {
+        title_page.sort_by_key(|(key, _)| TITLE_PAGE_KEYS.iter().position(|known| known == key));
+        for (key, value) in &title_page {
+            fountain.push_str(&format!("{}: {value}\n", title_page_label(key)));
+        }
+        fountain.push('\n');
+    }
+    let start = body
+        .iter()
+        .position(|line| !line.is_empty())
+        .unwrap_or(body.len());
+    for line in &body[start..] {
+        fountain.push_str(line);
+        fountain.push('\n');
+    }
+    FountainExport {
+        fountain,
+        unmapped: unmapped_elements,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Imports and re-exports `source`, dropping the title page the fallback
+    /// title adds when the source has none.
+    fn round_trip(source: &str) -> (FountainImport, String) {
+        let import = fountain_to_markdown("Untitled", source);
+        let export = markdown_to_fountain(&import.markdown);
+        assert!(export.unmapped.is_empty());
+        let fountain = export.fountain;
+        let fountain = match fountain.strip_prefix("Title: Untitled\n\n") {
+            Some(body) => body.to_string(),
+            None => fountain,
+        };
+        (import, fountain)
+    }
+
+    fn unmapped_kinds(elements: &[UnmappedElement]) -> Vec<(usize, &str, &str)> {
+        elements
+            .iter()
+            .map(|element| (element.line, element.kind, element.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn title_page_round_trips_through_front_matter() {
+        let source = "Title: The Night Shift\nCredit: Written by\nAuthor: Mala\nDraft date: 1 May\n\nRain hammers the tin roof.\n";
+        let (import, export) = round_trip(source);
+
+        assert!(import.markdown.starts_with(
+            "---\ncredit: Written by\nauthor: Mala\ndraft_date: 1 May\n---\n# The Night Shift\n"
+        ));
+        assert_eq!(export, source);
+        assert!(import.report.unmapped.is_empty());
+    }
+
+    #[test]
+    fn scene_headings_round_trip_and_report_scene_numbers() {
+        let source = "INT. TEA STALL - NIGHT #12A#\n\nRain hammers the tin roof.\n\n.FLASHBACK\n\nA younger Kannan.\n";
+        let (import, export) = round_trip(source);
+
+        assert!(import.markdown.contains("\n## INT. TEA STALL - NIGHT\n"));
+        assert!(import.markdown.contains("\n## FLASHBACK\n"));
+        assert_eq!(import.report.scene_headings, 2);
+        assert_eq!(
+            unmapped_kinds(&import.report.unmapped),
+            vec![(1, "scene_number", "12A")]
+        );
+        // The number is reported rather than carried over.
+        assert_eq!(
+            export,
+            "INT. TEA STALL - NIGHT\n\nRain hammers the tin roof.\n\n.FLASHBACK\n\nA younger Kannan.\n"
+        );
+    }
+
+    #[test]
+    fn cues_round_trip_with_dual_dialogue_reported() {
+        let source = "KANNAN (V.O.)\n(quietly)\nOne more cup.\n\nVALLI ^\nNo.\n";
+        let (import, export) = round_trip(source);
+
+        assert!(import
+            .markdown
+            .contains("\nKANNAN (V.O.)\n(quietly)\nOne more cup.\n\nVALLI\nNo.\n"));
+        assert_eq!(import.report.character_cues, 2);
+        assert_eq!(import.report.characters, vec!["KANNAN", "VALLI"]);
+        assert_eq!(import.report.parentheticals, 1);
+        assert_eq!(import.report.dialogue_lines, 2);
+        assert_eq!(import.report.dual_dialogue, 1);
+        assert_eq!(
+            unmapped_kinds(&import.report.unmapped),
+            vec![(5, "dual_dialogue", "VALLI ^")]
+        );
+        assert_eq!(
+            export,
+            "KANNAN (V.O.)\n(quietly)\nOne more cup.\n\nVALLI\nNo.\n"
+        );
+    }
+
+    #[test]
+    fn notes_and_boneyard_spanning_lines_survive_as_comments() {
+        let source =
+            "Valli turns. [[check the rain\nsound]]\n\n/* cut this\nbeat */\n\nKannan waits.\n";
+        let (import, export) = round_trip(source);
+
+        assert!(import.markdown.contains(
+            "\nValli turns.\n\n<!-- note: check the rain\nsound -->\n\n<!-- cut this\nbeat -->\n\nKannan waits.\n"
+        ));
+        assert_eq!(import.report.notes, 1);
+        assert_eq!(import.report.boneyard, 1);
+        assert_eq!(import.report.action_lines, 2);
+        assert_eq!(
+            export,
+            "Valli turns.\n\n[[check the rain\nsound]]\n\n/* cut this\nbeat */\n\nKannan waits.\n"
+        );
+    }
+
+    #[test]
+    fn centered_text_and_lyrics_become_reported_action() {
+        let source = "> THE END <\n\n~Kanmani anbodu\n";
+        let (import, export) = round_trip(source);
+
+        assert!(import.markdown.contains("\nTHE END\n\nKanmani anbodu\n"));
+        assert_eq!(
+            unmapped_kinds(&import.report.unmapped),
+            vec![
+                (1, "centered", "> THE END <"),
+                (3, "lyric", "~Kanmani anbodu")
+            ]
+        );
+        assert_eq!(export, "!THE END\n\nKanmani anbodu\n");
+    }
+
+    #[test]
+    fn forced_elements_round_trip() {
+        let source = "!KANNAN LOOKS UP.\n\n@McCLANE\nYippee.\n\n> FADE TO BLACK.\n";
+        let (import, export) = round_trip(source);
+
+        assert!(import
+            .markdown
+            .contains("\nKANNAN LOOKS UP.\n\nMCCLANE\nYippee.\n\n*FADE TO BLACK.*\n"));
+        assert_eq!(import.report.character_cues, 1);
+        assert_eq!(import.report.transitions, 1);
+        // Cues are imported in capitals, so the cue no longer needs its `@`.
+        assert_eq!(
+            export,
+            "!KANNAN LOOKS UP.\n\nMCCLANE\nYippee.\n\n> FADE TO BLACK.\n"
+        );
+    }
+
+    #[test]
+    fn markdown_without_a_fountain_counterpart_is_reported() {
+        let markdown = "# Draft\n\n> A quoted line.\n\n![Map](map.png)\n\n```\nraw\n```\n";
+        let export = markdown_to_fountain(markdown);
+
+        assert_eq!(
+            unmapped_kinds(&export.unmapped),
+            vec![
+                (3, "blockquote", "> A quoted line."),
+                (5, "image", "![Map](map.png)"),
+                (7, "code_block", "```"),
+            ]
+        );
+        assert_eq!(
+            export.fountain,
+            "Title: Draft\n\nA quoted line.\n\n[[![Map](map.png)]]\n\n/*\nraw\n*/\n"
+        );
+    }
+
+    #[test]
+    fn trailing_hash_on_a_scene_heading_is_lost() {
+        let (import, export) = round_trip("EXT. #\n");
+        assert!(import.markdown.contains("\n## EXT. #\n"));
+        assert_eq!(export, "EXT.\n");
+    }
+
+    #[test]
+    fn lone_transition_marker_becomes_empty_emphasis() {
+        let (import, export) = round_trip(">\n");
+        assert!(import.markdown.ends_with("\n**\n"));
+        assert_eq!(import.report.transitions, 1);
+        assert_eq!(export, "**\n");
+    }
+
+    #[test]
+    fn bare_lyric_and_force_markers_vanish() {
+        let (import, export) = round_trip("~\n\n!\n");
+        assert_eq!(import.markdown, "# Untitled\n");
+        assert_eq!(import.report.action_lines, 0);
+        // Only the lyric marker is reported.
+        assert_eq!(
+            unmapped_kinds(&import.report.unmapped),
+            vec![(1, "lyric", "~")]
+        );
+        assert_eq!(export, "");
+    }
+}
@@ -1,23 +1,54 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 use directories::ProjectDirs;
 use once_cell::sync::OnceCell;
-use sqlx::{sqlite::SqliteConnectOptions, SqlitePool};
-use tokio::sync::RwLock;
+use sqlx::{migrate::Migrator, sqlite::SqliteConnectOptions, SqlitePool};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, RwLock, Semaphore};
 
 use crate::{
     error::{AppError, AppResult},
-    models::UserProfile,
+    filesystem::DiskUsage,
+    jobs::JobQueue,
+    ml_bridge::{InputSuggestion, SpeechPreview, TtsVoice},
+    models::{UserProfile, DEFAULT_MAX_CONCURRENT_ML_JOBS},
+    watcher::ProjectWatcher,
 };
 
 static STORAGE_DIR: OnceCell<PathBuf> = OnceCell::new();
 static MODELS_DIR: OnceCell<PathBuf> = OnceCell::new();
+const SHUTDOWN_WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long a statement waits on a locked database before failing; long
+/// enough for `vacuum_database` to finish on a large library.
+const DATABASE_BUSY_TIMEOUT: Duration = Duration::from_secs(60);
+pub const DATABASE_FILE: &str = "scriptwriter.db";
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
 pub struct AppState {
     pub pool: SqlitePool,
     pub storage_root: PathBuf,
     pub models_root: PathBuf,
     pub session: RwLock<Option<UserProfile>>,
+    pub watchers: Mutex<HashMap<String, ProjectWatcher>>,
+    pub disk_usage: Mutex<HashMap<String, DiskUsage>>,
+    pub jobs: JobQueue,
+    pub tts_voices: Mutex<HashMap<String, Vec<TtsVoice>>>,
+    /// Language codes per speech-to-text model, checked before every transcription.
+    pub stt_languages: Mutex<HashMap<String, Vec<String>>>,
+    /// Suggestions keyed by limit and lowercased prefix; typing asks for the same prefixes often.
+    pub input_suggestions: Mutex<HashMap<String, Vec<InputSuggestion>>>,
+    /// Recently spoken transliteration candidates keyed by voice and text, so
+    /// replaying a word does not synthesize it again.
+    pub spoken_candidates: Mutex<HashMap<String, SpeechPreview>>,
+    /// Held for reading while a draft is written so shutdown can wait for it.
+    pub pending_writes: RwLock<()>,
+    /// Permits for Python processes started through the ML bridge; every
+    /// caller queues for one, so lookups made while typing wait behind a
+    /// running generation.
+    pub ml_jobs: Arc<Semaphore>,
+    pub ml_job_limit: Mutex<u32>,
 }
 
 impl AppState {
@@ -36,6 +67,60 @@ impl AppState {
     pub async fn set_user(&self, user: Option<UserProfile>) {
         *self.session.write().await = user;
     }
+
+    pub async fn invalidate_disk_usage(&self, project_id: &str) {
+        self.disk_usage.lock().await.remove(project_id);
+    }
+
+    /// Waits for a free ML job slot; the slot is released when the permit drops.
+    pub async fn ml_permit(&self) -> AppResult<OwnedSemaphorePermit> {
+        self.ml_jobs
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|err| AppError::Anyhow(err.into()))
+    }
+
+    /// Resizes the ML job pool. Shrinking waits for running jobs to hand their
+    /// permits back rather than cutting them off.
+    pub async fn set_ml_job_limit(&self, limit: u32) {
+        let mut current = self.ml_job_limit.lock().await;
+        if limit > *current {
+            self.ml_jobs.add_permits((limit - *current) as usize);
+        } else if limit < *current {
+            let surplus = *current - limit;
+            let semaphore = self.ml_jobs.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Ok(permits) = semaphore.acquire_many_owned(surplus).await {
+                    permits.forget();
+                }
+            });
+        }
+        *current = limit;
+    }
+
+    /// Cancels queued jobs, stops ML subprocesses, lets in-flight saves land
+    /// and closes the database.
+    pub async fn shutdown(&self) {
+        let dropped = self.jobs.cancel_pending().await;
+        if dropped > 0 {
+            log::info!("Cancelled {dropped} queued job(s) on exit");
+        }
+        let killed = crate::ml_bridge::cancel_all_tracked_processes();
+        if killed > 0 {
+            log::info!("Stopped {killed} ML process(es) on exit");
+        }
+
+        if tokio::time::timeout(SHUTDOWN_WRITE_TIMEOUT, self.pending_writes.write())
+            .await
+            .is_err()
+        {
+            log::warn!("Timed out waiting for pending writes on exit");
+        }
+
+        self.watchers.lock().await.clear();
+        self.pool.close().await;
+    }
 }
 
 pub async fn initialize_state() -> AppResult<AppState> {
@@ -44,7 +129,7 @@ pub async fn initialize_state() -> AppResult<AppState> {
 
     let data_dir = dirs.data_dir().to_path_buf();
     let models_dir = data_dir.join("models");
-    let db_path = data_dir.join("scriptwriter.db");
+    let db_path = data_dir.join(DATABASE_FILE);
 
     std::fs::create_dir_all(&data_dir)?;
     std::fs::create_dir_all(&models_dir)?;
@@ -58,16 +143,64 @@ pub async fn initialize_state() -> AppResult<AppState> {
 
     let connect_options = SqliteConnectOptions::new()
         .filename(&db_path)
-        .create_if_missing(true);
+        .create_if_missing(true)
+        .busy_timeout(DATABASE_BUSY_TIMEOUT);
 
     let pool = SqlitePool::connect_with(connect_options).await?;
 
-    sqlx::migrate!("./migrations").run(&pool).await?;
+    MIGRATOR.run(&pool).await?;
+
+    let ml_job_limit =
+        sqlx::query_scalar::<_, i64>("SELECT max_concurrent_ml_jobs FROM settings WHERE id = 1")
+            .fetch_optional(&pool)
+            .await?
+            .and_then(|limit| u32::try_from(limit).ok())
+            .filter(|limit| *limit > 0)
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_ML_JOBS);
 
     Ok(AppState {
         pool,
         storage_root: data_dir.to_path_buf(),
         models_root: models_dir,
         session: RwLock::new(None),
+        watchers: Mutex::new(HashMap::new()),
+        disk_usage: Mutex::new(HashMap::new()),
+        jobs: JobQueue::new(),
+        tts_voices: Mutex::new(HashMap::new()),
+        stt_languages: Mutex::new(HashMap::new()),
+        input_suggestions: Mutex::new(HashMap::new()),
+        spoken_candidates: Mutex::new(HashMap::new()),
+        pending_writes: RwLock::new(()),
+        ml_jobs: Arc::new(Semaphore::new(ml_job_limit as usize)),
+        ml_job_limit: Mutex::new(ml_job_limit),
     })
 }
+
+/// A state backed by a migrated in-memory database, for tests.
+#[cfg(test)]
+pub async fn in_memory_state() -> AppState {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("open in-memory database");
+    MIGRATOR.run(&pool).await.expect("run migrations");
+    let storage_root = std::env::temp_dir().join("scriptwriter-test");
+
+    AppState {
+        pool,
+        models_root: storage_root.join("models"),
+        storage_root,
+        session: RwLock::new(None),
+        watchers: Mutex::new(HashMap::new()),
+        disk_usage: Mutex::new(HashMap::new()),
+        jobs: JobQueue::new(),
+        tts_voices: Mutex::new(HashMap::new()),
+        stt_languages: Mutex::new(HashMap::new()),
+        input_suggestions: Mutex::new(HashMap::new()),
+        spoken_candidates: Mutex::new(HashMap::new()),
+        pending_writes: RwLock::new(()),
+        ml_jobs: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_ML_JOBS as usize)),
+        ml_job_limit: Mutex::new(DEFAULT_MAX_CONCURRENT_ML_JOBS),
+    }
+}
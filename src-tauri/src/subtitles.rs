@@ -0,0 +1,320 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::{AppError, AppResult};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("srt") => Some(Self::Srt),
+            Some("vtt") => Some(Self::Vtt),
+            _ => None,
+        }
+    }
+}
+
+/// How cue timestamps appear in the imported transcript.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampStyle {
+    /// A `##` heading per cue with its start and end time.
+    #[default]
+    Headings,
+    /// The start time in bold at the front of each cue's paragraph.
+    Inline,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub lines: Vec<String>,
+}
+
+fn parse_timestamp(raw: &str, format: SubtitleFormat) -> Option<u64> {
+    let (clock, millis) = match format {
+        SubtitleFormat::Srt => raw.split_once(','),
+        SubtitleFormat::Vtt => raw.split_once('.'),
+    }?;
+    if millis.len() != 3 || !millis.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let parts = clock.split(':').collect::<Vec<_>>();
+    let (hours, minutes, seconds) = match (format, parts.as_slice()) {
+        (_, [hours, minutes, seconds]) => (*hours, *minutes, *seconds),
+        // WebVTT may leave out the hours.
+        (SubtitleFormat::Vtt, [minutes, seconds]) => ("0", *minutes, *seconds),
+        _ => return None,
+    };
+    let number = |part: &str| -> Option<u64> {
+        (!part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()))
+            .then(|| part.parse().ok())
+            .flatten()
+    };
+    let (hours, minutes, seconds) = (number(hours)?, number(minutes)?, number(seconds)?);
+    if minutes > 59 || seconds > 59 {
+        return None;
+    }
+    // Hours are unbounded, so a hostile file could overflow the total.
+    hours
+        .checked_mul(60)?
+        .checked_add(minutes)?
+        .checked_mul(60)?
+        .checked_add(seconds)?
+        .checked_mul(1000)?
+        .checked_add(millis.parse::<u64>().ok()?)
+}
+
+fn parse_timing(line: &str, line_number: usize, format: SubtitleFormat) -> AppResult<(u64, u64)> {
+    let invalid = |detail: &str| AppError::Message(format!("Line {line_number}: {detail}"));
+    let (start, rest) = line
+        .split_once("-->")
+        .ok_or_else(|| invalid("expected a cue timing line"))?;
+    let start = start.trim();
+    // WebVTT cue settings may follow the end time.
+    let end = rest.split_whitespace().next().unwrap_or_default();
+    let start_ms = parse_timestamp(start, format)
+        .ok_or_else(|| invalid(&format!("invalid timestamp '{start}'")))?;
+    let end_ms = parse_timestamp(end, format)
+        .ok_or_else(|| invalid(&format!("invalid timestamp '{end}'")))?;
+    if end_ms < start_ms {
+        return Err(invalid("cue ends before it starts"));
+    }
+    Ok((start_ms, end_ms))
+}
+
+/// Drops markup from a cue line. WebVTT voice spans (`<v Name>`) become a
+/// `Name:` prefix so dialogue keeps its speaker.
+fn clean_cue_line(line: &str) -> String {
+    let mut cleaned = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(open) = rest.find(['<', '{']) {
+        cleaned.push_str(&rest[..open]);
+        let close = if rest[open..].starts_with('<') {
+            '>'
+        } else {
+            '}'
+        };
+        let Some(length) = rest[open..].find(close) else {
+            cleaned.push_str(&rest[open..]);
+            rest = "";
+            break;
+        };
+        let tag = &rest[open + 1..open + length];
+        if let Some(voice) = tag
+            .strip_prefix('v')
+            .filter(|voice| voice.starts_with(' ') || voice.starts_with('.'))
+        {
+            if let Some((_, name)) = voice.split_once(' ') {
+                let name = name.trim();
+                if !name.is_empty() {
+                    cleaned.push_str(name);
+                    cleaned.push_str(": ");
+                }
+            }
+        } else if close == '}' && !tag.starts_with('\\') {
+            cleaned.push_str(&rest[open..=open + length]);
+        }
+        rest = &rest[open + length + 1..];
+    }
+    cleaned.push_str(rest);
+    cleaned
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Parses an SRT or WebVTT document into cues. Errors name the 1-based line
+/// that could not be read.
+pub fn parse_subtitles(content: &str, format: SubtitleFormat) -> AppResult<Vec<Cue>> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut lines = content
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .peekable();
+
+    if format == SubtitleFormat::Vtt {
+        let header = lines.next().map(|(_, line)| line).unwrap_or_default();
+        let signature = header.strip_prefix("WEBVTT");
+        if !signature.is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t'])) {
+            return Err(AppError::Message(
+                "Line 1: WebVTT files must start with 'WEBVTT'".into(),
+            ));
+        }
+        // Header metadata runs until the first blank line.
+        while lines.next_if(|(_, line)| !line.trim().is_empty()).is_some() {}
+    }
+
+    let mut blocks: Vec<Vec<(usize, &str)>> = Vec::new();
+    let mut current = Vec::new();
+    for (line_number, line) in lines {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push((line_number, line));
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+
+    let mut cues = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        let first = block[0].1;
+        if format == SubtitleFormat::Vtt
+            && (first.starts_with("NOTE") || first == "STYLE" || first == "REGION")
+        {
+            continue;
+        }
+        // An optional cue number (SRT) or identifier (WebVTT) comes first.
+        let timing_index = usize::from(!first.contains("-->") && block.len() > 1);
+        let (timing_line, timing) = block[timing_index];
+        let (start_ms, end_ms) = parse_timing(timing, timing_line, format)?;
+        let lines = block[timing_index + 1..]
+            .iter()
+            .map(|(_, line)| clean_cue_line(line))
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        if !lines.is_empty() {
+            cues.push(Cue {
+                start_ms,
+                end_ms,
+                lines,
+            });
+        }
+    }
+    Ok(cues)
+}
+
+fn format_timestamp(ms: u64) -> String {
+    let seconds = ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Renders a speech-to-text result as a draft. Diarized results become one
+/// `SPEAKER N: text` paragraph per turn, so the transcript reads like a script.
+pub fn transcript_to_markdown(title: &str, transcript: &Value) -> String {
+    let mut markdown = format!("# {title}\n");
+    let turns = transcript
+        .get("segments")
+        .and_then(Value::as_array)
+        .map(|segments| {
+            segments
+                .iter()
+                .filter_map(|segment| {
+                    let speaker = segment.get("speaker")?.as_str()?;
+                    let text = segment.get("text")?.as_str()?.trim();
+                    (!text.is_empty()).then(|| format!("{speaker}: {text}"))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if turns.is_empty() {
+        let text = transcript
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        markdown.push_str(&format!("\n{}\n", text.trim()));
+    } else {
+        for turn in turns {
+            markdown.push_str(&format!("\n{turn}\n"));
+        }
+    }
+    markdown
+}
+
+pub fn subtitles_to_markdown(title: &str, cues: &[Cue], style: TimestampStyle) -> String {
+    let mut markdown = format!("# {title}\n");
+    for cue in cues {
+        match style {
+            TimestampStyle::Headings => markdown.push_str(&format!(
+                "\n## {} – {}\n\n{}\n",
+                format_timestamp(cue.start_ms),
+                format_timestamp(cue.end_ms),
+                cue.lines.join("\n")
+            )),
+            TimestampStyle::Inline => markdown.push_str(&format!(
+                "\n**[{}]** {}\n",
+                format_timestamp(cue.start_ms),
+                cue.lines.join(" ")
+            )),
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diarized_transcripts_prefix_each_turn_with_the_speaker() {
+        let transcript = json!({
+            "text": "Hello there. Hi.",
+            "segments": [
+                { "speaker": "SPEAKER 1", "start_secs": 0.0, "end_secs": 1.2, "text": "Hello there." },
+                { "speaker": "SPEAKER 2", "start_secs": 1.2, "end_secs": 2.0, "text": " Hi. " },
+            ],
+        });
+        assert_eq!(
+            transcript_to_markdown("episode-1", &transcript),
+            "# episode-1\n\nSPEAKER 1: Hello there.\n\nSPEAKER 2: Hi.\n"
+        );
+    }
+
+    #[test]
+    fn timestamps_that_overflow_are_rejected() {
+        assert_eq!(
+            parse_timestamp("01:02:03,456", SubtitleFormat::Srt),
+            Some(3_723_456)
+        );
+        assert_eq!(
+            parse_timestamp("02:03.456", SubtitleFormat::Vtt),
+            Some(123_456)
+        );
+        assert_eq!(
+            parse_timestamp("18446744073709551615:00:00,000", SubtitleFormat::Srt),
+            None
+        );
+        assert_eq!(
+            parse_timestamp("5124095576030431:00:00.000", SubtitleFormat::Vtt),
+            None
+        );
+    }
+
+    #[test]
+    fn plain_transcripts_keep_the_text() {
+        let transcript = json!({ "text": "Hello there.", "confidence": 1.0 });
+        assert_eq!(
+            transcript_to_markdown("memo", &transcript),
+            "# memo\n\nHello there.\n"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct Transformation {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// Everything `apply_transformation` accepts. Quote, ellipsis and whitespace
+/// changes leave front matter and fenced code alone; quote and ellipsis
+/// changes also skip inline code, HTML tags and link targets.
+pub const TRANSFORMATIONS: &[Transformation] = &[
+    Transformation {
+        name: "curly_quotes",
+        description: "Convert straight quotes and apostrophes to curly ones",
+    },
+    Transformation {
+        name: "straight_quotes",
+        description: "Convert curly quotes and apostrophes to straight ones",
+    },
+    Transformation {
+        name: "trim_trailing_whitespace",
+        description: "Remove whitespace at line ends, keeping two-space line breaks",
+    },
+    Transformation {
+        name: "collapse_blank_lines",
+        description: "Reduce runs of blank lines to a single blank line",
+    },
+    Transformation {
+        name: "ellipses",
+        description: "Replace three dots with an ellipsis character",
+    },
+];
+
+/// Runs the named transformation, returning the new content and how many
+/// changes it made, or `None` for a name outside [`TRANSFORMATIONS`].
+pub fn apply_transformation(name: &str, content: &str) -> Option<(String, usize)> {
+    let result = match name {
+        "curly_quotes" => map_prose(content, curly_quotes),
+        "straight_quotes" => map_prose(content, straight_quotes),
+        "trim_trailing_whitespace" => trim_trailing_whitespace(content),
+        "collapse_blank_lines" => collapse_blank_lines(content),
+        "ellipses" => map_prose(content, ellipses),
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Splits a line into its text and line ending, so endings survive as-is.
+fn split_ending(line: &str) -> (&str, &str) {
+    let text = line.trim_end_matches(['\n', '\r']);
+    (text, &line[text.len()..])
+}
+
+/// Applies `transform` to every line outside front matter and fenced code.
+fn map_prose(content: &str, mut transform: impl FnMut(&str) -> (String, usize)) -> (String, usize) {
+    let mut output = String::with_capacity(content.len());
+    let mut changes = 0;
+    let mut fence: Option<&str> = None;
+    let mut in_front_matter = false;
+
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let (text, ending) = split_ending(line);
+        let trimmed = text.trim_start();
+        let verbatim = if index == 0 && text.trim_end() == "---" {
+            in_front_matter = true;
+            true
+        } else if in_front_matter {
+            if matches!(text.trim_end(), "---" | "...") {
+                in_front_matter = false;
+            }
+            true
+        } else if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            true
+        } else if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            true
+        } else {
+            false
+        };
+
+        if verbatim {
+            output.push_str(line);
+        } else {
+            let (text, count) = transform(text);
+            output.push_str(&text);
+            output.push_str(ending);
+            changes += count;
+        }
+    }
+    (output, changes)
+}
+
+/// Applies `transform` to each run of prose in a line, skipping inline code,
+/// HTML tags and the targets of `[text](target)` links.
+fn map_text_runs(
+    line: &str,
+    mut transform: impl FnMut(&str, Option<char>) -> (String, usize),
+) -> (String, usize) {
+    let mut output = String::with_capacity(line.len());
+    let mut changes = 0;
+    let mut rest = line;
+    let mut previous: Option<char> = None;
+
+    while !rest.is_empty() {
+        let skipped_start = rest
+            .char_indices()
+            .find(|(index, ch)| *ch == '`' || *ch == '<' || rest[*index..].starts_with("]("))
+            .map(|(index, _)| index);
+        let prose_end = skipped_start.unwrap_or(rest.len());
+        let (text, count) = transform(&rest[..prose_end], previous);
+        output.push_str(&text);
+        changes += count;
+        rest = &rest[prose_end..];
+        if rest.is_empty() {
+            break;
+        }
+
+        let skipped_len = if rest.starts_with('`') {
+            let ticks = rest.len() - rest.trim_start_matches('`').len();
+            let marker = &rest[..ticks];
+            rest[ticks..]
+                .find(marker)
+                .map(|end| ticks + end + ticks)
+                .unwrap_or(ticks)
+        } else if rest.starts_with('<') {
+            rest.find('>').map(|end| end + 1).unwrap_or(1)
+        } else {
+            rest.find(')').map(|end| end + 1).unwrap_or(2)
+        };
+        output.push_str(&rest[..skipped_len]);
+        previous = rest[..skipped_len].chars().last();
+        rest = &rest[skipped_len..];
+    }
+    (output, changes)
+}
+
+fn curly_quotes(line: &str) -> (String, usize) {
+    map_text_runs(line, |text, mut previous| {
+        let mut output = String::with_capacity(text.len());
+        let mut changes = 0;
+        for ch in text.chars() {
+            let opening = match previous {
+                Some(before) => before.is_whitespace() || "([{<>—–-".contains(before),
+                None => true,
+            };
+            let replacement = match ch {
+                '"' if opening => '“',
+                '"' => '”',
+                '\'' if opening => '‘',
+                '\'' => '’',
+                other => other,
+            };
+            if replacement != ch {
+                changes += 1;
+            }
+            output.push(replacement);
+            previous = Some(ch);
+        }
+        (output, changes)
+    })
+}
+
+fn straight_quotes(line: &str) -> (String, usize) {
+    map_text_runs(line, |text, _| {
+        let mut changes = 0;
+        let output = text
+            .chars()
+            .map(|ch| match ch {
+                '“' | '”' | '„' => {
+                    changes += 1;
+                    '"'
+                }
+                '‘' | '’' | '‚' => {
+                    changes += 1;
+                    '\''
+                }
+                other => other,
+            })
+            .collect();
+        (output, changes)
+    })
+}
+
+fn ellipses(line: &str) -> (String, usize) {
+    map_text_runs(line, |text, _| {
+        let changes = text.matches("...").count();
+        (text.replace("...", "…"), changes)
+    })
+}
+
+fn trim_trailing_whitespace(content: &str) -> (String, usize) {
+    map_prose(content, |text| {
+        let trimmed = text.trim_end();
+        // Two trailing spaces are a markdown line break, not stray whitespace.
+        let kept = if !trimmed.is_empty() && text[trimmed.len()..].starts_with("  ") {
+            "  "
+        } else {
+            ""
+        };
+        let changes = usize::from(text.len() != trimmed.len() + kept.len());
+        (format!("{trimmed}{kept}"), changes)
+    })
+}
+
+fn collapse_blank_lines(content: &str) -> (String, usize) {
+    let mut output = String::with_capacity(content.len());
+    let mut changes = 0;
+    let mut fence: Option<&str> = None;
+    let mut previous_blank = false;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+        } else if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+        } else if trimmed.is_empty() {
+            if previous_blank {
+                changes += 1;
+                continue;
+            }
+            previous_blank = true;
+            output.push_str(line);
+            continue;
+        }
+        previous_blank = false;
+        output.push_str(line);
+    }
+    (output, changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trim_trailing_whitespace_keeps_hard_breaks() {
+        let (output, changes) =
+            trim_trailing_whitespace("First line  \nSecond line \t\r\nThird   \n   \n");
+        assert_eq!(output, "First line  \nSecond line\r\nThird  \n\n");
+        assert_eq!(changes, 3);
+    }
+
+    #[test]
+    fn trim_trailing_whitespace_skips_fenced_code() {
+        let content = "Prose \n```python\nprint('x')   \n```\n~~~\nkeep\t\n~~~\nEnd \n";
+        let (output, changes) = trim_trailing_whitespace(content);
+        assert_eq!(
+            output,
+            "Prose\n```python\nprint('x')   \n```\n~~~\nkeep\t\n~~~\nEnd\n"
+        );
+        assert_eq!(changes, 2);
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use notify_debouncer_full::notify::event::{EventKind, ModifyKind};
+use notify_debouncer_full::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, RecommendedCache};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{AppError, AppResult};
+
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(500);
+
+pub type ProjectWatcher = Debouncer<RecommendedWatcher, RecommendedCache>;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ProjectFileChange {
+    pub project_id: String,
+    pub path: String,
+    pub kind: &'static str,
+}
+
+fn change_kind(kind: &EventKind) -> Option<&'static str> {
+    match kind {
+        EventKind::Create(_) => Some("created"),
+        EventKind::Modify(ModifyKind::Name(_)) => Some("renamed"),
+        EventKind::Modify(_) => Some("modified"),
+        EventKind::Remove(_) => Some("removed"),
+        _ => None,
+    }
+}
+
+fn is_hidden(relative: &Path) -> bool {
+    relative.components().any(|component| match component {
+        Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+        _ => false,
+    })
+}
+
+fn emit_changes(app: &AppHandle, project_id: &str, root: &Path, result: DebounceEventResult) {
+    let events = match result {
+        Ok(events) => events,
+        Err(errors) => {
+            for err in errors {
+                log::warn!("Project watcher error: {err}");
+            }
+            return;
+        }
+    };
+
+    let mut seen = HashSet::new();
+    for event in events {
+        let Some(kind) = change_kind(&event.kind) else {
+            continue;
+        };
+        for path in &event.paths {
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            if relative.as_os_str().is_empty() || is_hidden(relative) {
+                continue;
+            }
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if !seen.insert((relative.clone(), kind)) {
+                continue;
+            }
+            let change = ProjectFileChange {
+                project_id: project_id.to_string(),
+                path: relative,
+                kind,
+            };
+            if let Err(err) = app.emit("project-file-changed", change) {
+                log::warn!("Failed to emit project change: {err}");
+            }
+        }
+    }
+}
+
+pub fn watch_project(
+    app: AppHandle,
+    project_id: String,
+    base_path: PathBuf,
+) -> AppResult<ProjectWatcher> {
+    let root = base_path.clone();
+    let mut debouncer = new_debouncer(DEBOUNCE_WINDOW, None, move |result| {
+        emit_changes(&app, &project_id, &root, result)
+    })
+    .map_err(|err| AppError::Anyhow(err.into()))?;
+
+    debouncer
+        .watch(&base_path, RecursiveMode::Recursive)
+        .map_err(|err| AppError::Anyhow(err.into()))?;
+
+    Ok(debouncer)
+}